@@ -0,0 +1,105 @@
+use crate::{Hint, OwnedWord, WordError};
+
+#[derive(Debug)]
+pub enum AlphabetError {
+    Empty,
+    TooLarge { len: usize },
+    Duplicate { ch: char },
+}
+
+/// An ordered set of characters from which the words of a [`Dictionary`](crate::Dictionary) are composed.
+/// The order of the characters defines the lexicographic ordering of words used for tie-breaking.
+/// An alphabet has at most [`Alphabet::MAX_LEN`] characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alphabet {
+    chars: Vec<char>,
+}
+impl Alphabet {
+    /// The maximum number of characters in an alphabet.
+    pub const MAX_LEN: usize = 32;
+
+    /// Creates a new alphabet from the given characters, in order.
+    /// If there are no characters, too many characters, or repeated characters, returns [`Err`].
+    pub fn new<T: IntoIterator<Item = char>>(chars: T) -> Result<Self, AlphabetError> {
+        let chars: Vec<char> = chars.into_iter().collect();
+        if chars.is_empty() {
+            return Err(AlphabetError::Empty);
+        }
+        if chars.len() > Self::MAX_LEN {
+            return Err(AlphabetError::TooLarge { len: chars.len() });
+        }
+        for (i, &ch) in chars.iter().enumerate() {
+            if chars[..i].contains(&ch) {
+                return Err(AlphabetError::Duplicate { ch });
+            }
+        }
+        Ok(Alphabet { chars })
+    }
+    /// The lowercase english alphabet `a..=z`.
+    pub fn english() -> Self {
+        Alphabet { chars: ('a'..='z').collect() }
+    }
+    /// Gets the characters of the alphabet, in order.
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+    /// Checks if the given character is part of the alphabet.
+    pub fn contains(&self, ch: char) -> bool {
+        self.chars.contains(&ch)
+    }
+    /// Like [`get_hint`](crate::get_hint), but for words made from this alphabet rather than lowercase `a..=z`.
+    /// Fails with [`WordError::NotInAlphabet`] if either word has other characters (checking the answer first),
+    /// or with [`WordError::WrongWordLen`] if the words have different lengths.
+    pub fn get_hint<'a>(&self, guess: &'a str, answer: &'a str) -> Result<Vec<Hint>, WordError<'a>> {
+        let answer = self.encode(answer.chars().count(), answer)?;
+        let guess = self.encode(answer.len(), guess)?;
+        Ok(crate::hint_impl(&guess, &answer))
+    }
+
+    pub(crate) fn index_of(&self, ch: char) -> Option<u8> {
+        self.chars.iter().position(|&x| x == ch).map(|x| x as u8)
+    }
+    pub(crate) fn encode<'a>(&self, expected_len: usize, word: &'a str) -> Result<OwnedWord, WordError<'a>> {
+        let mut res = Vec::with_capacity(expected_len);
+        for ch in word.chars() {
+            match self.index_of(ch) {
+                Some(x) => res.push(x),
+                None => return Err(WordError::NotInAlphabet { word }),
+            }
+        }
+        if res.len() != expected_len {
+            return Err(WordError::WrongWordLen { word, expected_len });
+        }
+        Ok(OwnedWord(res))
+    }
+    pub(crate) fn decode(&self, word: &[u8]) -> String {
+        word.iter().map(|&x| self.chars[x as usize]).collect()
+    }
+}
+
+#[test]
+fn test_alphabet() {
+    let english = Alphabet::english();
+    assert_eq!(english.chars().len(), 26);
+    assert_eq!(&*english.encode(5, "hello").unwrap(), &[7, 4, 11, 11, 14]);
+    assert_eq!(english.decode(&[7, 4, 11, 11, 14]), "hello");
+    assert!(matches!(english.encode(5, "hell"), Err(WordError::WrongWordLen { expected_len: 5, .. })));
+    assert!(matches!(english.encode(5, "Hello"), Err(WordError::NotInAlphabet { .. })));
+
+    let digits = Alphabet::new('0'..='9').unwrap();
+    assert_eq!(&*digits.encode(3, "907").unwrap(), &[9, 0, 7]);
+    assert_eq!(digits.decode(&[1, 2, 3]), "123");
+    assert!(matches!(digits.encode(3, "9a7"), Err(WordError::NotInAlphabet { .. })));
+
+    let accented = Alphabet::new(('a'..='n').chain(['ñ']).chain('o'..='z')).unwrap();
+    assert_eq!(&*accented.encode(4, "niño").unwrap(), &[13, 8, 14, 15]);
+    assert_eq!(accented.decode(&[13, 8, 14, 15]), "niño");
+    assert_eq!(accented.get_hint("niño", "ñoño").unwrap(), [Hint::Absent, Hint::Absent, Hint::Correct, Hint::Correct]);
+    assert!(matches!(accented.get_hint("niño", "Ñoño"), Err(WordError::NotInAlphabet { word: "Ñoño" })));
+    assert!(matches!(accented.get_hint("niños", "ñoño"), Err(WordError::WrongWordLen { word: "niños", expected_len: 4 })));
+    assert_eq!(english.get_hint("hatch", "catch").unwrap(), crate::get_hint("hatch", "catch").unwrap());
+
+    assert!(matches!(Alphabet::new([]), Err(AlphabetError::Empty)));
+    assert!(matches!(Alphabet::new("abca".chars()), Err(AlphabetError::Duplicate { ch: 'a' })));
+    assert!(matches!(Alphabet::new((0..33).map(|x| char::from_u32(0x100 + x).unwrap())), Err(AlphabetError::TooLarge { len: 33 })));
+}
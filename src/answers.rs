@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use crate::*;
+
+/// The words of a [`Dictionary`] which can be the answer, for games whose answer list is smaller than their list of legal guesses.
+/// The dictionary itself is then the list of legal guesses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerList {
+    members: Vec<bool>,
+    len: usize,
+}
+impl AnswerList {
+    /// Creates an answer list from words in the dictionary, or returns the first word which is not in it as [`Err`].
+    /// Duplicate words are ignored.
+    pub fn new<'a, I: IntoIterator<Item = &'a str>>(dictionary: &Dictionary, words: I) -> Result<Self, &'a str> {
+        let mut members = vec![false; dictionary.len()];
+        for word in words {
+            members[dictionary.index(word).ok_or(word)?.get()] = true;
+        }
+        let len = members.iter().filter(|&&x| x).count();
+        Ok(AnswerList { members, len })
+    }
+    /// Checks if the word at the given index can be the answer.
+    /// Panics if the index is out of bounds.
+    pub fn contains(&self, index: WordIndex) -> bool {
+        self.members[index.get()]
+    }
+    /// Gets the number of words which can be the answer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Checks if no word can be the answer.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// The scores of a guess counting both legal guesses and possible answers, as produced by [`Puzzle::dual_score`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DualScore {
+    /// The worst-case number of remaining words (legal guesses consistent with the hints) after making the guess.
+    pub legal_worst_case: u64,
+    /// The average-case number of remaining words after making the guess.
+    pub legal_average: Average,
+    /// The worst-case number of remaining possible answers after making the guess.
+    pub answer_worst_case: u64,
+    /// The average-case number of remaining possible answers after making the guess.
+    pub answer_average: Average,
+}
+
+impl<'a> Puzzle<'a> {
+    /// Counts the feasible words which are in the answer list.
+    pub fn remaining_answers(&self, answers: &AnswerList) -> usize {
+        self.candidate_indices().into_iter().filter(|&i| answers.contains(i)).count()
+    }
+    /// Scores a guess by how many words remain for each hint it could receive, counting both all the words in the dictionary
+    /// (legal guesses) and only those in the answer list. Averages are over the hints which leave at least one word of each kind.
+    /// Unlike [`Puzzle::best_guess`], the words are partitioned by their actual hints rather than by the solver's constraints.
+    /// The guess may be any valid word, even one outside the dictionary.
+    pub fn dual_score<'b>(&self, guess: &'b str, answers: &AnswerList) -> Result<DualScore, WordError<'b>> {
+        let guess = self.encode(guess)?;
+        let this = self.reduced();
+        let mut groups: HashMap<u32, (u64, u64)> = HashMap::new();
+        for word in this.feasible_words.iter() {
+            let group = groups.entry(hint_code(&guess, word)).or_default();
+            group.0 += 1;
+            if answers.contains(WordIndex::new(this.dictionary.index_of(word).unwrap())) { group.1 += 1; }
+        }
+        let score = |sizes: &mut dyn Iterator<Item = u64>| {
+            let (worst, total, count) = sizes.filter(|&x| x != 0).fold((0, 0, 0), |(w, t, c), x| (w.max(x), t + x, c + 1));
+            (worst, if count == 0 { Average::ZERO } else { Average::new(total, count) })
+        };
+        let (legal_worst_case, legal_average) = score(&mut groups.values().map(|x| x.0));
+        let (answer_worst_case, answer_average) = score(&mut groups.values().map(|x| x.1));
+        Ok(DualScore { legal_worst_case, legal_average, answer_worst_case, answer_average })
+    }
+}
+
+#[test]
+fn test_dual_score() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "champ", "lower"])).unwrap();
+    let answers = AnswerList::new(&dict, ["batch", "catch", "champ", "catch"]).unwrap();
+    assert_eq!((answers.len(), answers.is_empty()), (3, false));
+    assert!(answers.contains(dict.index("champ").unwrap()) && !answers.contains(dict.index("hatch").unwrap()));
+    assert_eq!(AnswerList::new(&dict, ["batch", "zzzzz"]), Err("zzzzz"));
+
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "catch").unwrap()).unwrap();
+    assert_eq!((puzzle.candidates().len(), puzzle.remaining_answers(&answers)), (7, 3));
+
+    // every answer gets its own hint from "chimp", but the answer "batch" shares its hint with "hatch"
+    let score = puzzle.dual_score("chimp", &answers).unwrap();
+    assert_eq!((score.legal_worst_case, score.answer_worst_case), (2, 1));
+    assert_eq!(score.answer_average, Average::new(3, 3));
+    assert_eq!(score.legal_average, Average::new(7, 6));
+    assert!(puzzle.dual_score("hat", &answers).is_err());
+    assert_eq!(puzzle.dual_score("zzzzz", &answers).unwrap().legal_worst_case, 7);
+
+    let none = AnswerList::new(&dict, []).unwrap();
+    assert_eq!(puzzle.dual_score("chimp", &none).unwrap().answer_average, Average::ZERO);
+}
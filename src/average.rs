@@ -0,0 +1,75 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// An exact average, stored as an integer `total` over an integer `count`.
+/// Comparisons are performed exactly (by cross-multiplication), so ties are broken identically on every platform.
+/// An average with a `count` of zero is treated as zero.
+#[derive(Debug, Clone, Copy)]
+pub struct Average {
+    pub total: u64,
+    pub count: u64,
+}
+impl Average {
+    /// The average of nothing, which is zero.
+    pub const ZERO: Average = Average { total: 0, count: 1 };
+
+    pub fn new(total: u64, count: u64) -> Self {
+        Average { total, count }
+    }
+    /// Converts the average to a floating point value (e.g., for further statistics).
+    pub fn to_f64(self) -> f64 {
+        if self.count == 0 { 0.0 } else { self.total as f64 / self.count as f64 }
+    }
+    fn parts(self) -> (u128, u128) {
+        if self.count == 0 { (0, 1) } else { (self.total as u128, self.count as u128) }
+    }
+}
+impl PartialEq for Average {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Average {}
+impl PartialOrd for Average {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Average {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = self.parts();
+        let (c, d) = other.parts();
+        (a * d).cmp(&(c * b))
+    }
+}
+impl fmt::Display for Average {
+    /// Formats the average as a decimal number (rounded to the format precision, or 4 digits by default) using only integer arithmetic.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (total, count) = self.parts();
+        let digits = f.precision().unwrap_or(4);
+        let scale = 10u128.pow(digits as u32);
+        let scaled = (total * scale * 2 + count) / (count * 2); // round half up
+        let (whole, frac) = (scaled / scale, scaled % scale);
+        if digits == 0 { write!(f, "{}", whole) } else { write!(f, "{}.{:0width$}", whole, frac, width = digits) }
+    }
+}
+
+#[test]
+fn test_average() {
+    assert_eq!(Average::new(1, 3), Average::new(2, 6));
+    assert!(Average::new(1, 3) < Average::new(1, 2));
+    assert!(Average::new(7, 2) > Average::new(10, 3));
+    assert_eq!(Average::new(0, 0), Average::ZERO);
+    assert!(Average::ZERO < Average::new(1, 1000));
+    assert_eq!(Average::new(u64::MAX, u64::MAX), Average::new(1, 1));
+    assert!(Average::new(u64::MAX, u64::MAX - 1) > Average::new(1, 1));
+
+    assert_eq!(Average::new(1, 3).to_string(), "0.3333");
+    assert_eq!(Average::new(2, 3).to_string(), "0.6667");
+    assert_eq!(format!("{:.2}", Average::new(15, 4)), "3.75");
+    assert_eq!(format!("{:.1}", Average::new(15, 4)), "3.8");
+    assert_eq!(format!("{:.0}", Average::new(7, 2)), "4");
+    assert_eq!(Average::new(124, 1).to_string(), "124.0000");
+    assert_eq!(Average::ZERO.to_string(), "0.0000");
+    assert_eq!(Average::new(1, 4).to_f64(), 0.25);
+}
@@ -0,0 +1,259 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::*;
+
+/// The summary of a benchmark over many answers, which can be saved as JSON (see [`BenchReport::to_json`])
+/// and compared against later runs (see [`BenchReport::compare`]) to measure the effect of strategy and performance changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchReport {
+    /// The fingerprint of the word list the benchmark was run on (see [`Dictionary::fingerprint`]).
+    pub fingerprint: u64,
+    /// The number of answers which were played.
+    pub words: usize,
+    /// The fewest guesses needed for any answer.
+    pub min_guesses: u8,
+    /// The most guesses needed for any answer.
+    pub max_guesses: u8,
+    /// The average number of guesses needed.
+    pub avg_guesses: f64,
+    /// The standard deviation of the number of guesses needed.
+    pub std_guesses: f64,
+    /// The time within which 95% of the timed answers were solved.
+    pub p95_time: Duration,
+    /// Where the word list came from, if known (see [`Dictionary::source`]).
+    pub source: Option<DictionarySource>,
+}
+impl BenchReport {
+    /// Summarizes the number of guesses needed for each answer and the time taken to solve each one, with no [`BenchReport::source`].
+    /// There may be fewer times than answers (e.g., for answers resumed from a checkpoint, whose times are unknown).
+    pub fn new(fingerprint: u64, guesses: &[u8], times: &[Duration]) -> Self {
+        let words = guesses.len();
+        let avg_guesses = guesses.iter().map(|&x| x as f64).sum::<f64>() / words.max(1) as f64;
+        let variance = guesses.iter().map(|&x| (x as f64 - avg_guesses).powi(2)).sum::<f64>() / words.max(1) as f64;
+
+        let mut times = times.to_vec();
+        times.sort_unstable();
+        let p95_time = match times.len() {
+            0 => Duration::ZERO,
+            n => times[(n * 95).div_ceil(100) - 1],
+        };
+
+        BenchReport {
+            fingerprint, words, p95_time,
+            min_guesses: guesses.iter().copied().min().unwrap_or(0),
+            max_guesses: guesses.iter().copied().max().unwrap_or(0),
+            avg_guesses, std_guesses: variance.sqrt(),
+            source: None,
+        }
+    }
+    /// Formats the report as a single line of JSON, with the fields of the source prefixed by `source_` (which are `null` when unknown).
+    /// For example: `{"fingerprint":"0123456789abcdef","words":100,"min_guesses":2,"max_guesses":6,"avg_guesses":3.6100,"std_guesses":0.7100,"p95_ms":12.500,`
+    /// `"source_name":"guesses.txt","source_version":null,"source_license":null,"source_url":null,"source_word_count":12972}`.
+    pub fn to_json(&self) -> String {
+        let mut res = String::new();
+        write!(res, "{{\"fingerprint\":\"{:016x}\",\"words\":{},\"min_guesses\":{},\"max_guesses\":{},", self.fingerprint, self.words, self.min_guesses, self.max_guesses).unwrap();
+        write!(res, "\"avg_guesses\":{:.4},\"std_guesses\":{:.4},\"p95_ms\":{:.3}", self.avg_guesses, self.std_guesses, self.p95_time.as_secs_f64() * 1000.0).unwrap();
+        let source = self.source.as_ref();
+        for (key, value) in [("name", source.map(|x| x.name.as_str())), ("version", source.and_then(|x| x.version.as_deref())),
+            ("license", source.and_then(|x| x.license.as_deref())), ("url", source.and_then(|x| x.url.as_deref()))]
+        {
+            write!(res, ",\"source_{}\":", key).unwrap();
+            write_json_string(&mut res, value);
+        }
+        match source.and_then(|x| x.word_count) {
+            Some(word_count) => write!(res, ",\"source_word_count\":{}}}", word_count).unwrap(),
+            None => res.push_str(",\"source_word_count\":null}"),
+        }
+        res
+    }
+    /// Parses a report in the format of [`BenchReport::to_json`], ignoring any fields which are not known (e.g., from newer versions).
+    /// The source fields are optional, so reports from older versions have no source.
+    pub fn from_json(text: &str) -> Result<Self, BenchReportError> {
+        let body = text.trim().strip_prefix('{').and_then(|x| x.strip_suffix('}')).ok_or(BenchReportError::Syntax)?;
+        let mut fields = vec![];
+        for field in split_json_fields(body).into_iter().filter(|x| !x.trim().is_empty()) {
+            let (key, value) = field.split_once(':').ok_or(BenchReportError::Syntax)?;
+            let key = key.trim().strip_prefix('"').and_then(|x| x.strip_suffix('"')).ok_or(BenchReportError::Syntax)?;
+            fields.push((key, value.trim()));
+        }
+
+        let get = |key: &'static str| fields.iter().find(|x| x.0 == key).map(|x| x.1).ok_or(BenchReportError::MissingField(key));
+        fn parse<T: std::str::FromStr>(key: &'static str, value: &str) -> Result<T, BenchReportError> {
+            value.parse().map_err(|_| BenchReportError::InvalidValue(key))
+        }
+        let fingerprint = get("fingerprint")?.strip_prefix('"').and_then(|x| x.strip_suffix('"')).and_then(|x| u64::from_str_radix(x, 16).ok()).ok_or(BenchReportError::InvalidValue("fingerprint"))?;
+        let p95_ms: f64 = parse("p95_ms", get("p95_ms")?)?;
+        let source_field = |key: &'static str| match fields.iter().find(|x| x.0 == key).map(|x| x.1) {
+            None | Some("null") => Ok(None),
+            Some(value) => parse_json_string(value).map(Some).ok_or(BenchReportError::InvalidValue(key)),
+        };
+        let source = match source_field("source_name")? {
+            None => None,
+            Some(name) => Some(DictionarySource {
+                name,
+                version: source_field("source_version")?,
+                license: source_field("source_license")?,
+                url: source_field("source_url")?,
+                word_count: match fields.iter().find(|x| x.0 == "source_word_count").map(|x| x.1) {
+                    None | Some("null") => None,
+                    Some(value) => Some(parse("source_word_count", value)?),
+                },
+            }),
+        };
+        Ok(BenchReport {
+            fingerprint,
+            words: parse("words", get("words")?)?,
+            min_guesses: parse("min_guesses", get("min_guesses")?)?,
+            max_guesses: parse("max_guesses", get("max_guesses")?)?,
+            avg_guesses: parse("avg_guesses", get("avg_guesses")?)?,
+            std_guesses: parse("std_guesses", get("std_guesses")?)?,
+            p95_time: Duration::try_from_secs_f64(p95_ms / 1000.0).map_err(|_| BenchReportError::InvalidValue("p95_ms"))?,
+            source,
+        })
+    }
+    /// Compares this report against an earlier baseline, flagging regressions which exceed the given thresholds.
+    pub fn compare(&self, baseline: &BenchReport, thresholds: &BenchThresholds) -> BenchComparison {
+        let avg_guesses = self.avg_guesses - baseline.avg_guesses;
+        let p95_time = match baseline.p95_time.is_zero() {
+            true => 0.0,
+            false => self.p95_time.as_secs_f64() / baseline.p95_time.as_secs_f64() - 1.0,
+        };
+        BenchComparison {
+            avg_guesses, p95_time,
+            max_guesses: self.max_guesses as i32 - baseline.max_guesses as i32,
+            same_words: self.fingerprint == baseline.fingerprint && self.words == baseline.words,
+            guess_regression: avg_guesses > thresholds.avg_guesses,
+            time_regression: p95_time > thresholds.p95_time,
+        }
+    }
+}
+
+/// Splits the body of a flat JSON object at the commas between its fields, skipping commas within strings.
+fn split_json_fields(body: &str) -> Vec<&str> {
+    let (mut res, mut start, mut in_string, mut escaped) = (vec![], 0, false, false);
+    for (i, ch) in body.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ',' if !in_string => {
+                res.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    res.push(&body[start..]);
+    res
+}
+
+/// Parses a JSON string literal with the escapes written by [`write_json_string`], or `None` if it is invalid.
+fn parse_json_string(value: &str) -> Option<String> {
+    let mut chars = value.strip_prefix('"')?.strip_suffix('"')?.chars();
+    let mut res = String::new();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => res.push(match chars.next()? {
+                '"' => '"',
+                '\\' => '\\',
+                'n' => '\n',
+                'u' => char::from_u32(u32::from_str_radix(&chars.by_ref().take(4).collect::<String>(), 16).ok()?)?,
+                _ => return None,
+            }),
+            '"' => return None,
+            ch => res.push(ch),
+        }
+    }
+    Some(res)
+}
+
+/// A problem with a saved [`BenchReport`], as reported by [`BenchReport::from_json`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BenchReportError {
+    /// The text is not a flat JSON object.
+    Syntax,
+    /// A required field is missing.
+    MissingField(&'static str),
+    /// A field has a value of the wrong type.
+    InvalidValue(&'static str),
+}
+
+/// The largest changes from a baseline which [`BenchReport::compare`] does not count as regressions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchThresholds {
+    /// The largest allowed increase in the average number of guesses.
+    pub avg_guesses: f64,
+    /// The largest allowed relative increase in the 95th percentile time (e.g., `0.2` for 20% slower).
+    pub p95_time: f64,
+}
+impl Default for BenchThresholds {
+    /// Allows the average to grow by `0.01` guesses (about one extra guess per hundred answers), and the time by 20% (to allow for noise).
+    fn default() -> Self {
+        BenchThresholds { avg_guesses: 0.01, p95_time: 0.2 }
+    }
+}
+
+/// The differences between a [`BenchReport`] and a baseline, as produced by [`BenchReport::compare`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchComparison {
+    /// The change in the average number of guesses (positive is worse).
+    pub avg_guesses: f64,
+    /// The change in the most guesses needed for any answer (positive is worse).
+    pub max_guesses: i32,
+    /// The relative change in the 95th percentile time (e.g., `0.1` for 10% slower), or zero if the baseline has no time.
+    pub p95_time: f64,
+    /// Whether both runs used the same word list and number of answers, without which the deltas may not be meaningful.
+    pub same_words: bool,
+    /// Whether the average number of guesses grew by more than the threshold.
+    pub guess_regression: bool,
+    /// Whether the 95th percentile time grew by more than the threshold.
+    pub time_regression: bool,
+}
+impl BenchComparison {
+    /// Checks if any regression exceeded its threshold.
+    pub fn regressed(&self) -> bool {
+        self.guess_regression || self.time_regression
+    }
+}
+
+#[test]
+fn test_bench_report() {
+    let ms = Duration::from_millis;
+    let report = BenchReport::new(0xabc, &[3, 4, 4, 5], &(1..=20).map(ms).collect::<Vec<_>>());
+    assert_eq!((report.words, report.min_guesses, report.max_guesses, report.avg_guesses), (4, 3, 5, 4.0));
+    assert!((report.std_guesses - 0.5f64.sqrt()).abs() < 1e-9);
+    assert_eq!(report.p95_time, ms(19));
+    assert_eq!(BenchReport::new(0, &[3], &[ms(5), ms(1)]).p95_time, ms(5));
+    assert_eq!(BenchReport::new(0, &[], &[]), BenchReport { fingerprint: 0, words: 0, min_guesses: 0, max_guesses: 0, avg_guesses: 0.0, std_guesses: 0.0, p95_time: Duration::ZERO, source: None });
+
+    let json = report.to_json();
+    assert_eq!(json, r#"{"fingerprint":"0000000000000abc","words":4,"min_guesses":3,"max_guesses":5,"avg_guesses":4.0000,"std_guesses":0.7071,"p95_ms":19.000,"source_name":null,"source_version":null,"source_license":null,"source_url":null,"source_word_count":null}"#);
+    let parsed = BenchReport::from_json(&format!("  {}\n", json.replace(",\"words\"", ",\"extra\":true, \"words\""))).unwrap();
+    assert_eq!(parsed, BenchReport { std_guesses: parsed.std_guesses, ..report.clone() }); // rounded to 4 places
+    assert!((parsed.std_guesses - report.std_guesses).abs() < 1e-4);
+    assert_eq!(BenchReport::from_json("[]"), Err(BenchReportError::Syntax));
+    assert_eq!(BenchReport::from_json(&json.replace("\"words\":4,", "")), Err(BenchReportError::MissingField("words")));
+    assert_eq!(BenchReport::from_json(&json.replace("5,", "-5,")), Err(BenchReportError::InvalidValue("max_guesses")));
+    assert_eq!(BenchReport::from_json(&json.replace("abc", "xyz")), Err(BenchReportError::InvalidValue("fingerprint")));
+
+    // the source survives a round trip, and reports from before sources were recorded have none
+    let source = DictionarySource::new("my, \"atch\" list\n").with_license("CC0-1.0").with_word_count(7);
+    let sourced = BenchReport { source: Some(source), ..report.clone() };
+    assert_eq!(BenchReport::from_json(&sourced.to_json()).unwrap().source, sourced.source);
+    let old = json.split(",\"source_name\"").next().unwrap().to_owned() + "}";
+    assert_eq!(BenchReport::from_json(&old).unwrap().source, None);
+    assert_eq!(BenchReport::from_json(&json.replace("\"source_name\":null", "\"source_name\":3")), Err(BenchReportError::InvalidValue("source_name")));
+    assert_eq!(parse_json_string(r#""a\\b\n\u0001ñ""#).as_deref(), Some("a\\b\n\u{1}ñ"));
+
+    let same = report.compare(&parsed, &BenchThresholds::default());
+    assert_eq!(same, BenchComparison { avg_guesses: 0.0, max_guesses: 0, p95_time: 0.0, same_words: true, guess_regression: false, time_regression: false });
+    let worse = BenchReport { avg_guesses: 4.02, max_guesses: 6, p95_time: ms(19 * 2), ..report.clone() };
+    let cmp = worse.compare(&report, &BenchThresholds::default());
+    assert!(cmp.regressed() && cmp.guess_regression && cmp.time_regression && cmp.same_words);
+    assert_eq!(cmp.max_guesses, 1);
+    assert!((cmp.p95_time - 1.0).abs() < 1e-9);
+    assert!(!worse.compare(&report, &BenchThresholds { avg_guesses: 0.05, p95_time: 1.5 }).regressed());
+    assert!(!report.compare(&BenchReport { fingerprint: 1, ..report.clone() }, &BenchThresholds::default()).same_words);
+    assert_eq!(report.compare(&BenchReport { p95_time: Duration::ZERO, ..report.clone() }, &BenchThresholds::default()).p95_time, 0.0);
+}
@@ -6,6 +6,9 @@ impl BitSet32 {
     pub fn new() -> Self {
         BitSet32(0)
     }
+    pub fn single(pos: u8) -> Self {
+        BitSet32(1 << pos)
+    }
     pub fn insert(&mut self, pos: u8) {
         self.0 |= 1 << pos;
     }
@@ -27,6 +30,9 @@ impl BitSet32 {
     pub fn len(&self) -> u32 {
         self.0.count_ones()
     }
+    pub fn bits(&self) -> u32 {
+        self.0
+    }
 }
 
 /// Iterates over the items of the bitset in ascending order.
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::*;
+
+/// Bumped whenever the meaning of cached values changes, so stale entries are never read back.
+const CACHE_VERSION: u64 = 4;
+
+/// A key-value store for cached solver results, keyed by a hash of the solver state (e.g., [`Puzzle::state_hash`]).
+/// Stores are shared between threads, so they must handle their own synchronization.
+/// Implementations backed by shared storage (like [`FileStore`]) let several processes share one cache.
+pub trait CacheStore: Send + Sync {
+    /// Gets the value stored for the key, or [`None`] if there is none.
+    fn get(&self, key: u64) -> io::Result<Option<Vec<u8>>>;
+    /// Stores a value for the key, replacing any previous value.
+    fn put(&self, key: u64, value: &[u8]) -> io::Result<()>;
+}
+
+/// A [`CacheStore`] which keeps everything in memory, for the lifetime of the process.
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: Mutex<HashMap<u64, Vec<u8>>>,
+}
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Gets the number of entries in the store.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+    /// Checks if the store has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+impl CacheStore for MemoryStore {
+    fn get(&self, key: u64) -> io::Result<Option<Vec<u8>>> {
+        Ok(self.entries.lock().unwrap().get(&key).cloned())
+    }
+    fn put(&self, key: u64, value: &[u8]) -> io::Result<()> {
+        self.entries.lock().unwrap().insert(key, value.to_vec());
+        Ok(())
+    }
+}
+
+/// A [`CacheStore`] which keeps one file per entry in a directory, named by the key in hex.
+/// Entries are written to a temporary file and then renamed into place, so several processes (and threads) can safely share the directory.
+/// For large caches, `SqliteStore` (with the `sqlite` feature) keeps everything in a single file instead.
+pub struct FileStore {
+    dir: PathBuf,
+}
+impl FileStore {
+    /// Opens a store in the given directory, creating it if needed.
+    pub fn open<P: Into<PathBuf>>(dir: P) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(FileStore { dir })
+    }
+    fn path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}", key))
+    }
+}
+impl CacheStore for FileStore {
+    fn get(&self, key: u64) -> io::Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)) {
+            Ok(x) => Ok(Some(x)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+    fn put(&self, key: u64, value: &[u8]) -> io::Result<()> {
+        // unique to this write, so concurrent writes of the same key (even from one process) never share a temporary file
+        static NEXT_TMP: AtomicU64 = AtomicU64::new(0);
+        let tmp = self.dir.join(format!("{:016x}.{}.{}.tmp", key, std::process::id(), NEXT_TMP.fetch_add(1, Ordering::Relaxed)));
+        fs::write(&tmp, value)?;
+        fs::rename(&tmp, self.path(key))
+    }
+}
+
+fn encode_best_guess(best: &BestGuess) -> Vec<u8> {
+    let mut res = vec![];
+    res.extend((best.word.len() as u32).to_le_bytes());
+    res.extend(best.word.as_bytes());
+    let expected = best.expected_guesses.unwrap_or(Average::new(0, 0));
+    for x in [best.worst_case, best.average.total, best.average.count, best.endgame as u64, expected.total, expected.count] {
+        res.extend(x.to_le_bytes());
+    }
+    for &(pattern, n) in best.partition.iter() {
+        res.extend(pattern.code().to_le_bytes());
+        res.push(pattern.word_len() as u8);
+        res.extend(n.to_le_bytes());
+    }
+    res
+}
+fn decode_best_guess(data: &[u8]) -> Option<BestGuess> {
+    let len = u32::from_le_bytes(data.get(..4)?.try_into().unwrap()) as usize;
+    let word = String::from_utf8(data.get(4..4 + len)?.to_vec()).ok()?;
+    let rest = data.get(4 + len..)?;
+    if rest.len() < 6 * 8 || (rest.len() - 6 * 8) % 13 != 0 { return None }
+    let (scores, partition) = rest.split_at(6 * 8);
+    let x: Vec<u64> = scores.chunks_exact(8).map(|x| u64::from_le_bytes(x.try_into().unwrap())).collect();
+    let partition = partition.chunks_exact(13).map(|x| {
+        let pattern = HintPattern::from_code(u32::from_le_bytes(x[..4].try_into().unwrap()), x[4] as usize)?;
+        Some((pattern, u64::from_le_bytes(x[5..].try_into().unwrap())))
+    }).collect::<Option<Vec<_>>>()?;
+    let feasible = partition.iter().any(|(pattern, _)| pattern.hints().iter().all(|&x| x == Hint::Correct)); // only the answer itself gives an all-correct hint
+    Some(BestGuess {
+        word,
+        worst_case: x[0],
+        average: Average::new(x[1], x[2]),
+        feasible,
+        endgame: x[3] != 0,
+        expected_guesses: if x[3] != 0 { Some(Average::new(x[4], x[5])) } else { None },
+        counters: SearchCounters::default(),
+        approximate: false,
+        partition,
+    })
+}
+
+/// Hashes a sequence of values with 64-bit FNV-1a, which (unlike [`std::hash::Hash`]) is stable across runs and platforms.
+pub(crate) fn stable_hash<I: IntoIterator<Item = u64>>(values: I) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in values.into_iter().flat_map(u64::to_le_bytes) {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Looks up a best guess in `store` by `key`, or computes and saves it if missing (see [`Puzzle::best_guess_cached`]).
+pub(crate) fn cache_best_guess<F: FnOnce() -> Result<BestGuess, SolveErr>>(store: &dyn CacheStore, key: u64, compute: F) -> Result<BestGuess, SolveErr> {
+    if let Some(best) = store.get(key).ok().flatten().and_then(|x| decode_best_guess(&x)) {
+        return Ok(best);
+    }
+    let best = compute()?;
+    store.put(key, &encode_best_guess(&best)).ok();
+    Ok(best)
+}
+
+impl Puzzle<'_> {
+    /// Computes a 64-bit hash of the (reduced) solver state, which identifies it across runs, platforms, and processes.
+    /// This covers the dictionary (see [`Dictionary::fingerprint`]), the current constraints, and the settings which affect [`Puzzle::best_guess`],
+    /// so two puzzles with the same hash get the same best guess, however they were reached.
+    pub fn state_hash(&self) -> u64 {
+        let this = self.reduced();
+        let mut values = vec![CACHE_VERSION, this.dictionary.fingerprint()];
+        values.extend(this.slots.iter().map(|slot| slot.bits() as u64));
+        values.extend(this.letter_counts.iter().flat_map(|&(min, max)| [min as u64, max as u64]));
+        values.extend([this.turns_left() as u64, this.endgame_threshold as u64]);
+        stable_hash(values)
+    }
+    /// Equivalent to [`Puzzle::best_guess`] with [`AnyWord`], but looks up the result in `store` first (by [`Puzzle::state_hash`])
+    /// and saves it there after computing it. Cached results have zero [`BestGuess::counters`], since no search was needed.
+    /// The cache is only an optimization, so errors from the store (and corrupt entries) are treated as misses and failed writes are ignored.
+    pub fn best_guess_cached(&self, threads: usize, store: &dyn CacheStore) -> Result<BestGuess, SolveErr> {
+        cache_best_guess(store, self.state_hash(), || self.best_guess(threads, AnyWord))
+    }
+}
+
+#[test]
+fn test_cache() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["wield", "blimp"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("wield", &get_hint("wield", "catch").unwrap()).unwrap();
+    assert_eq!(puzzle.state_hash(), puzzle.clone().state_hash());
+    assert_ne!(puzzle.state_hash(), Puzzle::new(&dict).state_hash());
+    let mut limited = puzzle.clone();
+    limited.set_max_guesses(2);
+    assert_ne!(puzzle.state_hash(), limited.state_hash());
+
+    let dir = std::env::temp_dir().join(format!("wordle-solver-test-cache-{}", std::process::id()));
+    let memory = MemoryStore::new();
+    let files = FileStore::open(&dir).unwrap();
+    for store in [&memory as &dyn CacheStore, &files] {
+        let best = puzzle.best_guess_cached(1, store).unwrap();
+        assert!(best.counters.evaluated > 0 || best.endgame);
+        assert_eq!(best, puzzle.best_guess(1, AnyWord).unwrap());
+        let cached = puzzle.best_guess_cached(1, store).unwrap();
+        assert_eq!(cached.counters, SearchCounters::default());
+        assert_eq!((cached.word, cached.worst_case, cached.average, cached.endgame, cached.expected_guesses, cached.partition),
+            (best.word, best.worst_case, best.average, best.endgame, best.expected_guesses, best.partition));
+
+        store.put(puzzle.state_hash(), b"corrupt").unwrap();
+        assert_eq!(puzzle.best_guess_cached(1, store).unwrap(), puzzle.best_guess(1, AnyWord).unwrap());
+    }
+    assert_eq!(memory.len(), 1);
+    assert_eq!(files.get(12345).unwrap(), None);
+
+    // threads writing the same key each use their own temporary file, so every read sees one whole value
+    let values: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; 1 << 16]).collect();
+    std::thread::scope(|s| {
+        for value in values.iter() {
+            s.spawn(|| for _ in 0..20 { files.put(1, value).unwrap(); });
+        }
+    });
+    assert!(values.contains(&files.get(1).unwrap().unwrap()));
+    assert!(fs::read_dir(&dir).unwrap().all(|x| x.unwrap().path().extension().map(|x| x != "tmp").unwrap_or(true)));
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,182 @@
+use crate::*;
+
+/// Evidence that a guess has the best worst case of all the admissible guesses, as produced by [`Puzzle::best_guess_certified`].
+/// It can be checked with [`Puzzle::verify_certificate`] using one reduction per admissible guess, rather than a full search.
+///
+/// Counts are those used by the minimax search: the number of feasible words which are consistent with the constraints
+/// after assuming a guess received a hint. For words with repeated letters, this can count a word under more than one hint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Certificate {
+    /// The certified guess.
+    pub guess: String,
+    /// The number of words remaining after each hint the guess could receive, in order of [`HintPattern::code`] (hints which leave no words are omitted).
+    pub hint_counts: Vec<(HintPattern, u64)>,
+    /// For every other admissible guess (in lexicographic order), a hint which leaves at least as many words as the worst case of [`Certificate::guess`].
+    pub witnesses: Vec<Witness>,
+}
+impl Certificate {
+    /// Gets the worst-case number of remaining words for the certified guess.
+    pub fn worst_case(&self) -> u64 {
+        self.hint_counts.iter().map(|x| x.1).max().unwrap_or(0)
+    }
+}
+
+/// A hint showing that a guess is no better in the worst case than the one in a [`Certificate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness {
+    /// The guess which is worse or equal.
+    pub word: String,
+    /// A hint the guess could receive.
+    pub pattern: HintPattern,
+    /// The number of words remaining after receiving the hint.
+    pub remaining: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertificateError {
+    /// The certified guess is not a valid word for the dictionary, or is not admissible.
+    InvalidGuess(String),
+    /// The count for a hint to the certified guess is wrong.
+    WrongHintCount { pattern: HintPattern, expected: u64, found: u64 },
+    /// An admissible guess has no witness.
+    MissingWitness(String),
+    /// A witness is for a guess which is not admissible, or its count is wrong or smaller than the certified worst case.
+    BadWitness(String),
+}
+
+impl<'a> Puzzle<'a> {
+    /// Equivalent to [`Puzzle::rank_guesses`] with `n = 1`, but also produces a [`Certificate`] that the guess has the best worst case
+    /// of all the words admitted by `filter` (with only one guess left, only words which could be the answer are considered, as in [`Puzzle::best_guess`]).
+    /// The endgame solver is never used. Finding the witnesses takes roughly as long as the search itself.
+    pub fn best_guess_certified<F: CandidateFilter>(&self, threads: usize, filter: F) -> Result<(BestGuess, Certificate), SolveErr> {
+        let this = self.reduced();
+        if this.slots.iter().any(BitSet32::is_empty) {
+            return Err(SolveErr::Inconsistent);
+        }
+        let admissible = this.certified_admissible(&filter);
+        if admissible.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+
+        let (ranked, counters) = this.rank_admissible(threads.max(1), &admissible, 1, &mut |_| ());
+        let ((worst_case, average), infeasible, guess) = *ranked.first().ok_or(SolveErr::Inconsistent)?;
+        let best = BestGuess { word: this.decode(&guess), worst_case, average, feasible: !infeasible, endgame: false, expected_guesses: None, counters, approximate: false, partition: this.partition(guess) };
+
+        let others: Vec<Word> = admissible.iter().copied().filter(|&w| w != guess).collect();
+        let chunk_size = others.len().div_ceil(threads.max(1)).max(1);
+        let witnesses = crossbeam::scope(|scope| {
+            let threads: Vec<_> = others.chunks(chunk_size).map(|words| {
+                let this = &this;
+                scope.spawn(move |_| words.iter().map(|&word| {
+                    let (pattern, remaining) = this.find_witness(word, worst_case).expect("no guess has a better worst case than the best guess");
+                    Witness { word: this.decode(&word), pattern, remaining }
+                }).collect::<Vec<_>>())
+            }).collect();
+            threads.into_iter().flat_map(|x| x.join().unwrap()).collect()
+        }).unwrap();
+
+        let certificate = Certificate { guess: best.word.clone(), hint_counts: this.hint_counts(guess), witnesses };
+        Ok((best, certificate))
+    }
+    /// Checks a [`Certificate`] for the given filter, which must be the same as the one used to create it.
+    pub fn verify_certificate<F: CandidateFilter>(&self, certificate: &Certificate, filter: F) -> Result<(), CertificateError> {
+        let this = self.reduced();
+        let guess = this.encode(&certificate.guess).map_err(|_| CertificateError::InvalidGuess(certificate.guess.clone()))?;
+
+        let mut claimed = certificate.hint_counts.iter().copied().peekable();
+        for (pattern, expected) in this.hint_counts(guess.as_ref()) {
+            let found = match claimed.next_if(|x| x.0 == pattern) {
+                Some((_, x)) => x,
+                None => 0,
+            };
+            if found != expected { return Err(CertificateError::WrongHintCount { pattern, expected, found }) }
+        }
+        if let Some((pattern, found)) = claimed.next() {
+            return Err(CertificateError::WrongHintCount { pattern, expected: 0, found });
+        }
+
+        let admissible = this.certified_admissible(&filter);
+        if !admissible.contains(&guess.as_ref()) {
+            return Err(CertificateError::InvalidGuess(certificate.guess.clone()));
+        }
+        let worst_case = certificate.worst_case();
+        let mut witnesses = certificate.witnesses.iter().peekable();
+        for word in admissible {
+            if word == guess.as_ref() { continue }
+            let word = this.decode(&word);
+            let witness = witnesses.next_if(|x| x.word == word).ok_or_else(|| CertificateError::MissingWitness(word.clone()))?;
+            let encoded = this.encode(&word).unwrap();
+            if witness.pattern.word_len() != this.slots.len() || witness.remaining < worst_case || this.remaining(encoded.as_ref(), witness.pattern) != witness.remaining {
+                return Err(CertificateError::BadWitness(word));
+            }
+        }
+        match witnesses.next() {
+            Some(x) => Err(CertificateError::BadWitness(x.word.clone())),
+            None => Ok(()),
+        }
+    }
+    /// Gets the guesses covered by a certificate for the given filter, in lexicographic order.
+    fn certified_admissible<F: CandidateFilter>(&self, filter: &F) -> Vec<Word<'a>> {
+        let mut res: Vec<_> = self.all_words.iter().copied().filter(|w| filter.admits(&self.decode(w))).collect();
+        if self.turns_left() == 1 && res.iter().any(|&w| self.could_be(w)) {
+            res.retain(|&w| self.could_be(w));
+        }
+        res
+    }
+    /// Counts the feasible words remaining after the guess receives the given hint, as in [`Puzzle::score_guess`].
+    fn remaining(&self, guess: Word, pattern: HintPattern) -> u64 {
+        let mut cpy = self.clone();
+        cpy.assume_impl(guess, &pattern.hints());
+        cpy.feasible_words.len() as u64
+    }
+    /// Gets the nonzero [`Puzzle::remaining`] counts for every hint to the guess, in order of [`HintPattern::code`].
+    fn hint_counts(&self, guess: Word) -> Vec<(HintPattern, u64)> {
+        (0..3u32.pow(guess.len() as u32)).map(|code| HintPattern::from_code_unchecked(code, guess.len()))
+            .map(|pattern| (pattern, self.remaining(guess, pattern))).filter(|x| x.1 != 0).collect()
+    }
+    /// Finds a hint to the guess which leaves at least `bound` words.
+    fn find_witness(&self, guess: Word, bound: u64) -> Option<(HintPattern, u64)> {
+        (0..3u32.pow(guess.len() as u32)).map(|code| HintPattern::from_code_unchecked(code, guess.len()))
+            .map(|pattern| (pattern, self.remaining(guess, pattern))).find(|x| x.1 >= bound)
+    }
+}
+
+#[test]
+fn test_certificate() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield", "lower"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+
+    let (best, certificate) = puzzle.best_guess_certified(2, AnyWord).unwrap();
+    let (single, single_cert) = puzzle.best_guess_certified(1, AnyWord).unwrap();
+    assert_eq!((&single.word, single.worst_case, single.average, &single_cert), (&best.word, best.worst_case, best.average, &certificate));
+    assert_eq!(best.word, puzzle.rank_guesses(1, AnyWord, 1).unwrap()[0].word);
+    assert_eq!((certificate.guess.as_str(), certificate.worst_case()), (best.word.as_str(), best.worst_case));
+    assert_eq!(certificate.hint_counts.len() as u64, best.average.count);
+    assert_eq!(certificate.hint_counts.iter().map(|x| x.1).sum::<u64>(), best.average.total);
+    assert_eq!(certificate.witnesses.len(), dict.len() - 1);
+    assert!(certificate.witnesses.iter().all(|x| x.remaining >= best.worst_case));
+    assert_eq!(puzzle.verify_certificate(&certificate, AnyWord), Ok(()));
+
+    let filter = |w: &str| w.ends_with("atch");
+    let (filtered, filtered_cert) = puzzle.best_guess_certified(2, filter).unwrap();
+    assert!(filter(&filtered.word) && filtered_cert.witnesses.len() == testkit::ATCH_WORDS.len() - 1);
+    assert_eq!(puzzle.verify_certificate(&filtered_cert, filter), Ok(()));
+    assert!(matches!(puzzle.verify_certificate(&filtered_cert, AnyWord), Err(CertificateError::MissingWitness(_))));
+    assert!(matches!(puzzle.verify_certificate(&certificate, filter), Err(CertificateError::InvalidGuess(x)) if x == certificate.guess));
+
+    let mut forged = certificate.clone();
+    forged.hint_counts[0].1 += 1;
+    assert!(matches!(puzzle.verify_certificate(&forged, AnyWord), Err(CertificateError::WrongHintCount { .. })));
+    let mut forged = certificate.clone();
+    forged.witnesses[0].remaining = 0;
+    assert!(matches!(puzzle.verify_certificate(&forged, AnyWord), Err(CertificateError::BadWitness(_))));
+    let mut forged = certificate.clone();
+    forged.witnesses.pop();
+    assert!(matches!(puzzle.verify_certificate(&forged, AnyWord), Err(CertificateError::MissingWitness(_))));
+    let mut forged = certificate;
+    forged.guess = "hat".into();
+    assert!(matches!(puzzle.verify_certificate(&forged, AnyWord), Err(CertificateError::InvalidGuess(_))));
+
+    assert!(matches!(puzzle.best_guess_certified(1, |_: &str| false), Err(SolveErr::NoAdmissibleGuess)));
+}
@@ -0,0 +1,248 @@
+// The command line interface, which only defines the arguments so that it can also be included by the xtask which generates the manual
+// (see xtask/src/main.rs). It is included rather than declared as a module, so it relies on the imports of the file including it.
+
+/// The exit codes of the program, so that shell scripts can branch on the outcome (panics still exit with 101).
+const EXIT_CODES: &str = "EXIT CODES:
+    0    solved, or a suggestion was produced
+    2    the hints are inconsistent (no word fits them)
+    3    invalid input (e.g., a malformed guess or hint)
+    4    dictionary error (e.g., an unreadable or invalid word list)
+    5    a benchmark regressed past its thresholds (see bench --baseline)";
+
+#[derive(Parser)]
+#[clap(version, after_help = EXIT_CODES)]
+struct Cli {
+    /// The language of messages (en or es), which doesn't change the word list
+    /// (by default, messages follow the locale from LC_ALL, LC_MESSAGES, or LANG)
+    #[clap(long, global = true, parse(try_from_str = parse_lang))]
+    lang: Option<Locale>,
+    /// A wordle clone to configure the word length, alphabet, and word list for (wordle, lewdle, primel, or taylordle)
+    #[clap(long, global = true, parse(try_from_str = parse_game))]
+    game: Option<GameProfile>,
+    /// A file of whitespace-separated words to use instead of the bundled word list
+    #[clap(long, global = true, value_name = "FILE")]
+    words: Option<std::path::PathBuf>,
+    /// A file of whitespace-separated words which can be the answer, when it is smaller than the list of legal guesses (which must contain all of them)
+    #[clap(long, global = true, value_name = "FILE")]
+    answers: Option<std::path::PathBuf>,
+    /// Write the progress of long computations to this file descriptor (e.g., 2 for stderr) as NDJSON (one JSON object per line)
+    /// (descriptors other than 1 and 2 are only supported on unix)
+    #[clap(long, global = true, value_name = "FD")]
+    progress_fd: Option<u32>,
+    /// A file of definitions to show next to suggestions, either a WordNet database file (named data.*) or one <word><TAB><definition> per line
+    #[clap(long, global = true, value_name = "FILE", multiple_occurrences = true)]
+    definitions: Vec<std::path::PathBuf>,
+    /// A config file defining named presets of solver settings for --preset (defaults to $XDG_CONFIG_HOME/wordle-solver/config, if it exists)
+    #[clap(long, global = true, value_name = "FILE")]
+    config: Option<std::path::PathBuf>,
+
+    #[clap(subcommand)]
+    command: Args,
+}
+
+fn parse_score_weights(spec: &str) -> Result<CompositeScorer, String> {
+    CompositeScorer::parse(spec).ok_or_else(|| "expected comma-separated <objective>=<weight> pairs, where the objectives are worst, average, entropy, and answer".into())
+}
+
+fn parse_lang(code: &str) -> Result<Locale, String> {
+    Locale::parse(code).ok_or_else(|| {
+        let available: Vec<_> = Locale::ALL.iter().map(|x| x.code()).collect();
+        format!("unknown language (available: {})", available.join(", "))
+    })
+}
+
+fn parse_game(name: &str) -> Result<GameProfile, String> {
+    GameProfile::from_name(name).ok_or_else(|| {
+        let available: Vec<_> = GameProfile::all().iter().map(|x| x.name()).collect();
+        format!("unknown game (available: {})", available.join(", "))
+    })
+}
+
+#[derive(Subcommand)]
+enum Args {
+    /// Solve a wordle puzzle by predicting the best guess to make next
+    Solve {
+        /// The number of threads to use (defaults to a recommendation based on the size of the search)
+        #[clap(short, long)]
+        threads: Option<usize>,
+        /// Explain each step of reasoning performed for the inputs
+        #[clap(long)]
+        explain: bool,
+        /// Print the best N guesses with their scores instead of only the best one
+        /// (ranked by worst and average case remaining words, so other ways of picking a guess and --histogram don't apply)
+        #[clap(long, value_name = "N",
+            conflicts_with_all = &["cache-dir", "histogram", "time-limit", "preset", "exact-depth", "score-script", "weights", "score-weights", "answer-tolerance"])]
+        suggest_n: Option<usize>,
+        /// A directory to cache best guesses in, which may be shared between runs and processes
+        #[clap(long, value_name = "DIR")]
+        cache_dir: Option<std::path::PathBuf>,
+        /// Also print a histogram of how many words remain after each hint the best guess could receive
+        #[clap(long)]
+        histogram: bool,
+        /// For a word list with a non-English alphabet (e.g., --game primel), search every word for the first guess instead of only the best few by letter coverage
+        /// (which is slow, but exact)
+        #[clap(long)]
+        exact_opening: bool,
+        /// Also list up to N of the words which could be the answer
+        #[clap(long, value_name = "N")]
+        list: Option<usize>,
+        /// The order to --list candidates in
+        #[clap(long, default_value = "alpha", possible_values = ["alpha", "positional"], requires = "list")]
+        order: String,
+        /// Stop searching after SECONDS and suggest the best guess found so far, which is marked as approximate
+        /// (this can't be combined with --progress-fd, and needs --exact-opening for the first guess of a non-English word list)
+        #[clap(long, value_name = "SECONDS", conflicts_with = "cache-dir")]
+        time_limit: Option<f64>,
+        /// A named bundle of solver settings, either built in (speed, for suggestions in real-time variants) or defined in the --config file
+        /// (this can't be combined with --progress-fd, and needs --exact-opening for the first guess of a non-English word list)
+        #[clap(long, value_name = "NAME", conflicts_with = "cache-dir")]
+        preset: Option<String>,
+        /// Search the full game tree for the guess which finds the answer in the fewest guesses in the worst case
+        /// (which is slow unless few words could be the answer)
+        #[clap(long, conflicts_with_all = &["cache-dir", "histogram", "time-limit", "preset"])]
+        exact_depth: bool,
+        /// A Rhai script defining fn score(guess, remaining, partition, feasible) to pick the guess with the highest score
+        /// (needs the scripting feature)
+        #[clap(long, value_name = "FILE", conflicts_with_all = &["cache-dir", "time-limit", "preset", "exact-depth"])]
+        score_script: Option<std::path::PathBuf>,
+        /// A file with a <word> <weight> line for each word with a known likelihood of being the answer (e.g., its usage frequency),
+        /// to pick the guess which leaves the fewest words on average with likely answers weighted more (other words keep a weight of 1000)
+        #[clap(long, value_name = "FILE", conflicts_with_all = &["cache-dir", "time-limit", "preset", "exact-depth", "score-script"])]
+        weights: Option<std::path::PathBuf>,
+        /// Pick the guess with the highest blend of objectives, given as weights like worst=1,entropy=0.5
+        /// (the objectives are the worst and average case remaining words, the entropy of the hint, and the chance of guessing the answer)
+        #[clap(long, value_name = "WEIGHTS", parse(try_from_str = parse_score_weights),
+            conflicts_with_all = &["cache-dir", "time-limit", "preset", "exact-depth", "score-script", "weights", "answer-tolerance"])]
+        score_weights: Option<CompositeScorer>,
+        /// Only suggest guesses which are legal in hard mode, i.e., which reuse the letters revealed by every guess so far
+        /// (even if another guess would narrow down the answer more)
+        #[clap(long, conflicts_with = "cache-dir")]
+        hard: bool,
+        /// Only suggest words which could still be the answer (e.g., for the last guess), instead of any word which narrows down the answer the most
+        #[clap(long, conflicts_with = "cache-dir")]
+        feasible_only: bool,
+        /// Suggest the best guess which could be the answer instead, if it leaves at most this many more words (in the worst and average case)
+        /// (this can't be combined with --progress-fd)
+        #[clap(long, value_name = "WORDS", default_value_t = 0.0, conflicts_with_all = &["cache-dir", "exact-depth", "score-script", "weights"])]
+        answer_tolerance: f64,
+
+        #[clap(flatten)]
+        inputs: PuzzleInputs,
+    },
+    /// Solve a wordle puzzle interactively, entering each <guess>:<response> as it is received
+    /// and seeing the most likely remaining answers after each one
+    /// (a partial guess ending in tab or '?' lists the words it could be completed to, and undo reverts the last guess)
+    Interactive {
+        /// The number of threads to use (defaults to a recommendation based on the size of the search)
+        #[clap(short, long)]
+        threads: Option<usize>,
+        /// The factor by which plurals and past tenses are considered less likely to be the answer
+        #[clap(long, default_value_t = 10)]
+        penalty: u32,
+
+        #[clap(flatten)]
+        inputs: PuzzleInputs,
+    },
+    /// Export the game tree from the current state in Graphviz DOT format
+    Dot {
+        /// The number of threads to use (defaults to a recommendation based on the size of the search)
+        #[clap(short, long)]
+        threads: Option<usize>,
+        /// The maximum number of guesses to expand
+        #[clap(short, long, default_value_t = 2)]
+        depth: usize,
+
+        #[clap(flatten)]
+        inputs: PuzzleInputs,
+    },
+    /// Solve a Waffle-style puzzle of scrambled letters by suggesting the fewest swaps to reach a consistent word
+    Swap {
+        /// The scrambled letters and their hint, as <letters>:<response>
+        input: String,
+    },
+    /// Infer the guesses that could have produced an emoji share grid (read from stdin) for a known answer
+    Infer {
+        answer: String,
+        /// The maximum number of guess sequences to output
+        #[clap(short, long, default_value_t = 20)]
+        limit: usize,
+        /// Only consider guess sequences which obey hard mode rules
+        #[clap(long)]
+        hard: bool,
+    },
+    /// Play a game against a secret answer in the terminal, then have the solver grade each guess
+    Train {
+        /// The number of threads to use (defaults to a recommendation based on the size of the search)
+        #[clap(short, long)]
+        threads: Option<usize>,
+        /// The seed used to pick the answer (defaults to the daily puzzle)
+        #[clap(long)]
+        seed: Option<u64>,
+        /// Play in hard mode, where every guess must reuse the letters revealed so far
+        #[clap(long)]
+        hard: bool,
+        /// After grading, show the best 3 alternatives to each guess and how the game would have unfolded after them (which is slow)
+        #[clap(long)]
+        alternatives: bool,
+    },
+    /// Print letter frequencies and other statistics about the word list
+    DictStats,
+    /// Print where the word list came from as JSON (name, version, license, URL, word count, and fingerprint), e.g., for auditing deployments
+    Source,
+    /// Benchmark the performance of the solver on all possible words
+    /// (includes words not used as answers by wordle itself)
+    Bench {
+        /// The number of threads to use (defaults to a recommendation based on the size of the search)
+        #[clap(short, long)]
+        threads: Option<usize>,
+        /// Also output the number of guesses needed for each tested word
+        /// (a consistent ordering of words in the output is not guaranteed)
+        #[clap(short, long)]
+        verbose: bool,
+        /// Only benchmark a random sample of this many words
+        #[clap(long)]
+        sample: Option<usize>,
+        /// The seed to use for random sampling
+        #[clap(long, default_value_t = 0)]
+        seed: u64,
+        /// Save the result for each answer to this file as it completes, and skip the answers it already contains
+        /// (so an interrupted run can be resumed by passing the same file again)
+        #[clap(long, value_name = "FILE")]
+        checkpoint: Option<std::path::PathBuf>,
+        /// Compare the results against a report saved by --save, printing the changes and failing if they regress past the thresholds
+        #[clap(long, value_name = "FILE")]
+        baseline: Option<std::path::PathBuf>,
+        /// Save a report of the results as JSON (e.g., to pass as the --baseline of a later run)
+        #[clap(long, value_name = "FILE")]
+        save: Option<std::path::PathBuf>,
+        /// The largest increase in the average number of guesses which is not a regression
+        #[clap(long, value_name = "GUESSES", default_value_t = BenchThresholds::default().avg_guesses, requires = "baseline")]
+        max_guess_regression: f64,
+        /// The largest increase in the 95th percentile time per word (in percent) which is not a regression
+        #[clap(long, value_name = "PERCENT", default_value_t = BenchThresholds::default().p95_time * 100.0, requires = "baseline")]
+        max_time_regression: f64,
+    },
+    /// Print a script for the given shell which completes subcommands and options (e.g., source it from .bashrc)
+    Completions {
+        /// The shell to generate completions for
+        #[clap(value_parser)]
+        shell: clap_complete::Shell,
+    },
+}
+
+/// The guesses made so far and the hints received for them
+#[derive(ClapArgs)]
+struct PuzzleInputs {
+    /// Guesses and hints as <guess>:<response> (e.g., crane:cpaaa)
+    inputs: Vec<String>,
+    /// A guess (alternative syntax, applied after <INPUTS> and paired in order with --hint)
+    #[clap(long = "guess", value_name = "WORD", requires = "hints")]
+    guesses: Vec<String>,
+    /// The hint for the corresponding --guess, as 'c' (correct), 'p' (present), or 'a' (absent) per letter
+    #[clap(long = "hint", value_name = "RESPONSE", parse(try_from_str = parse_response), requires = "guesses")]
+    hints: Vec<Vec<Hint>>,
+}
+
+fn parse_response(response: &str) -> Result<Vec<Hint>, String> {
+    response.chars().map(|ch| Hint::from_char(ch).ok_or_else(|| format!("unknown response '{}' (expected 'c' (correct), 'p' (present), or 'a' (absent))", ch))).collect()
+}
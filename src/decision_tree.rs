@@ -0,0 +1,170 @@
+use crate::*;
+use crate::endgame::Endgame;
+
+/// A state of a [`DecisionTree`]: the guess to make, and the state to go to after each hint which leaves words to find.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DecisionNode {
+    guess: String,
+    /// The next state for each hint (in order of [`HintPattern::code`]), as an index into the nodes of the tree.
+    /// Hints which no remaining word would give have no entry, and neither does the hint for guessing the answer.
+    children: Vec<(HintPattern, usize)>,
+}
+
+/// A complete strategy for finding the answer, giving the guess to make after every sequence of hints which could be received,
+/// as produced by [`Puzzle::decision_tree`]. Once built, looking up a guess only follows the hints received so far, so it is instant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionTree {
+    /// The nodes of the tree, starting with the root (parents come before their children).
+    nodes: Vec<DecisionNode>,
+    answers: u64,
+    unsolved: u64,
+    total_guesses: u64,
+}
+impl DecisionTree {
+    /// Gets the guess to make after receiving the given hints (in order) to the guesses previously given by the tree, starting from the first guess.
+    /// Returns [`None`] if the hints are not a line of play in the tree, e.g., if a hint could not have been received,
+    /// or if the previous guess was the answer, or if the guesses ran out.
+    pub fn next_guess(&self, hints: &[HintPattern]) -> Option<&str> {
+        let mut node = self.nodes.first()?;
+        for hint in hints {
+            let i = node.children.binary_search_by_key(&hint.code(), |x| x.0.code()).ok()?;
+            node = &self.nodes[node.children[i].1];
+        }
+        Some(&node.guess)
+    }
+    /// Plays the tree against the given answer, returning the guesses made (ending with the answer).
+    /// Returns [`None`] if the tree does not find the answer, e.g., if it was not one of the words which could be the answer
+    /// when the tree was built, or if the guesses run out first.
+    pub fn guesses_for(&self, answer: &str) -> Option<Vec<&str>> {
+        let mut res = vec![];
+        let mut hints = vec![];
+        loop {
+            let guess = self.next_guess(&hints)?;
+            res.push(guess);
+            if guess == answer { return Some(res) }
+            hints.push(HintPattern::from_hints(&get_hint_chars(guess, answer).ok()?));
+        }
+    }
+    /// Gets the average number of guesses needed to find the answer (including the last guess), over the words which could be the answer when the tree was built.
+    /// Words which are not found within the guesses left (see [`DecisionTree::unsolved`]) are not counted.
+    pub fn expected_guesses(&self) -> Average {
+        Average::new(self.total_guesses, self.answers - self.unsolved)
+    }
+    /// Gets the number of words which could be the answer that the tree does not find within the guesses left.
+    /// This is zero unless the turn limit makes it impossible to guarantee finding every word.
+    pub fn unsolved(&self) -> u64 {
+        self.unsolved
+    }
+    /// Gets the number of states in the tree, each of which has a guess.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+}
+
+impl<'a> Puzzle<'a> {
+    /// Computes a complete decision tree for finding the answer from the current state, with any word in the dictionary allowed as a guess.
+    /// The tree is optimal: it first minimizes the number of words which can't be found within the guesses left,
+    /// and then the average number of guesses needed to find the rest, assuming every word which could be the answer is equally likely.
+    ///
+    /// Every state is solved exactly, as by the endgame solver of [`Puzzle::best_guess`] (but regardless of [`Puzzle::set_endgame_threshold`]),
+    /// which takes time exponential in the number of words which could be the answer,
+    /// so it is only practical for small dictionaries or once the opening guesses have narrowed down the answer.
+    /// Fails with [`SolveErr::Inconsistent`] if no word could be the answer.
+    /// Panics if the words are longer than [`HintPattern::MAX_LEN`], or if more than [`Puzzle::MAX_ENDGAME_THRESHOLD`] words could be the answer.
+    pub fn decision_tree(&self) -> Result<DecisionTree, SolveErr> {
+        let this = self.reduced();
+        if this.feasible_words.is_empty() {
+            return Err(SolveErr::Inconsistent);
+        }
+        assert!(this.slots.len() <= HintPattern::MAX_LEN, "words are too long for a decision tree");
+        assert!(this.feasible_words.len() <= Self::MAX_ENDGAME_THRESHOLD, "too many words could be the answer for a decision tree");
+
+        let mut solver = Endgame::new(&this.all_words, &this.feasible_words);
+        let answers: Vec<u16> = (0..this.feasible_words.len()).map(|x| x as u16).collect();
+        let turns = this.turns_left();
+        let mut res = DecisionTree { nodes: vec![], answers: answers.len() as u64, unsolved: answers.len() as u64, total_guesses: 0 };
+        if turns > 0 {
+            let (_, cost) = solver.best_overall(&answers, turns).expect("guessing one of the answers always gains information");
+            (res.unsolved, res.total_guesses) = cost;
+            this.decision_node(&mut solver, &answers, turns, &mut res.nodes);
+        }
+        Ok(res)
+    }
+    /// Adds the node for the given answers (and its descendants) to `nodes`, returning its index.
+    fn decision_node(&self, solver: &mut Endgame, answers: &[u16], turns: usize, nodes: &mut Vec<DecisionNode>) -> usize {
+        let (guess, _) = solver.best_overall(answers, turns).expect("guessing one of the answers always gains information");
+        let index = nodes.len();
+        nodes.push(DecisionNode { guess: self.decode(&self.all_words[guess]), children: vec![] });
+        if turns > 1 {
+            for (code, group) in solver.split(guess, answers) {
+                let child = self.decision_node(solver, &group, turns - 1, nodes);
+                nodes[index].children.push((HintPattern::from_code_unchecked(code, self.slots.len()), child));
+            }
+        }
+        index
+    }
+}
+
+#[test]
+fn test_decision_tree() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield", "flows"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("wield", &get_hint("wield", "catch").unwrap()).unwrap();
+
+    // the tree agrees with the endgame solver, and playing it out for every answer adds up to its expected guesses
+    let tree = puzzle.decision_tree().unwrap();
+    let best = puzzle.best_guess(1, AnyWord).unwrap();
+    assert_eq!((tree.next_guess(&[]), Some(tree.expected_guesses())), (Some(best.word.as_str()), best.expected_guesses));
+    assert_eq!(tree.unsolved(), 0);
+    let mut total = 0;
+    for answer in puzzle.candidates() {
+        let guesses = tree.guesses_for(answer).unwrap();
+        assert_eq!(guesses.last(), Some(&answer));
+        total += guesses.len() as u64;
+
+        // each state of the tree plays optimally from there
+        let mut state = puzzle.clone();
+        let mut hints = vec![];
+        for &guess in &guesses[..guesses.len() - 1] {
+            assert_eq!(state.best_guess(1, AnyWord).unwrap().word, guess);
+            let hint = get_hint(guess, answer).unwrap();
+            state.guess(guess, &hint).unwrap();
+            hints.push(HintPattern::from_hints(&hint));
+            assert_eq!(tree.next_guess(&hints), guesses.get(hints.len()).copied());
+        }
+    }
+    assert_eq!(tree.expected_guesses(), Average::new(total, puzzle.candidates().len() as u64));
+
+    // hints which can't be received, and moves past the answer, aren't in the tree
+    let first = tree.next_guess(&[]).unwrap().to_owned();
+    assert_eq!(tree.next_guess(&[HintPattern::from_hints(&get_hint(&first, "flows").unwrap())]), None);
+    assert_eq!(tree.guesses_for("flows"), None);
+    assert!(tree.node_count() > 1);
+
+    // with too few guesses left, the tree gives up on some words
+    let mut limited = puzzle.clone();
+    limited.set_max_guesses(2);
+    let tree = limited.decision_tree().unwrap();
+    assert!(tree.unsolved() > 0);
+    assert_eq!(puzzle.candidates().into_iter().filter(|w| tree.guesses_for(w).is_none()).count() as u64, tree.unsolved());
+    limited.set_max_guesses(1);
+    assert_eq!(limited.decision_tree().unwrap().next_guess(&[]), None);
+
+    // a tree for a whole dictionary finds every word
+    let dict = testkit::dictionary(testkit::MINI_WORDS);
+    let tree = Puzzle::new(&dict).decision_tree().unwrap();
+    let lens: Vec<u64> = dict.words().map(|w| tree.guesses_for(w).unwrap().len() as u64).collect();
+    assert_eq!(tree.expected_guesses(), Average::new(lens.iter().sum(), lens.len() as u64));
+
+    let mut inconsistent = puzzle.clone();
+    inconsistent.guess("batch", &get_hint("batch", "flows").unwrap()).unwrap();
+    assert!(matches!(inconsistent.decision_tree(), Err(SolveErr::Inconsistent)));
+}
+
+#[test]
+#[should_panic(expected = "too many words could be the answer")]
+fn test_decision_tree_too_large() {
+    let words: Vec<String> = (0..300).map(|i| (0..5).map(|j| (b'a' + (i / 4usize.pow(j) % 4) as u8) as char).collect()).collect();
+    let dict = Dictionary::with_words(5, words.iter().map(String::as_str)).unwrap();
+    Puzzle::new(&dict).decision_tree().ok();
+}
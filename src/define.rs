@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+/// A source of short definitions for words, used to explain unfamiliar suggestions.
+/// Any `Fn(&str) -> Option<String>` closure can be used as a definer, as can a table of [`Definitions`].
+pub trait Definer {
+    /// Gets a short definition of the word, or [`None`] if it is unknown.
+    fn define(&self, word: &str) -> Option<String>;
+}
+impl<F: Fn(&str) -> Option<String>> Definer for F {
+    fn define(&self, word: &str) -> Option<String> {
+        self(word)
+    }
+}
+
+/// A table of definitions loaded from offline files.
+/// Only the first definition added for each word is kept.
+#[derive(Debug, Clone, Default)]
+pub struct Definitions {
+    definitions: HashMap<String, String>,
+}
+impl Definitions {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Adds a definition for the word (which is lowercased), unless it already has one.
+    pub fn insert(&mut self, word: &str, definition: &str) {
+        let definition = definition.trim();
+        if !definition.is_empty() {
+            self.definitions.entry(word.trim().to_lowercase()).or_insert_with(|| definition.into());
+        }
+    }
+    /// Adds the definitions from text with one `<word>\t<definition>` entry per line.
+    /// Blank lines and lines starting with `#` are ignored, as are lines without a tab.
+    pub fn add_tsv(&mut self, text: &str) {
+        for line in text.lines().filter(|x| !x.trim().is_empty() && !x.starts_with('#')) {
+            if let Some((word, definition)) = line.split_once('\t') {
+                self.insert(word, definition);
+            }
+        }
+    }
+    /// Adds the definitions from the contents of a WordNet database file (e.g., `data.noun`).
+    /// Each word in a synset is given the first clause of the synset's gloss (up to the first `;`), with underscores in words treated as spaces.
+    /// Lines which are not synsets (such as the license header) are ignored.
+    pub fn add_wordnet(&mut self, text: &str) {
+        for line in text.lines() {
+            let (data, gloss) = match line.split_once(" | ") {
+                Some(x) if line.starts_with(|ch: char| ch.is_ascii_digit()) => x,
+                _ => continue,
+            };
+            let gloss = gloss.split(';').next().unwrap();
+            let fields: Vec<&str> = data.split_whitespace().collect();
+            let count = match fields.get(3).and_then(|x| usize::from_str_radix(x, 16).ok()) {
+                Some(x) => x,
+                None => continue,
+            };
+            for word in fields.iter().skip(4).step_by(2).take(count) {
+                let word = word.split('(').next().unwrap(); // adjectives may have a syntactic marker like "(a)"
+                self.insert(&word.replace('_', " "), gloss);
+            }
+        }
+    }
+    /// Gets the number of words with a definition.
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+    /// Checks if there are no definitions.
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
+impl Definer for Definitions {
+    fn define(&self, word: &str) -> Option<String> {
+        self.definitions.get(word).cloned()
+    }
+}
+
+#[test]
+fn test_definitions() {
+    let mut defs = Definitions::new();
+    defs.add_tsv("# word list\nhatch\tan opening in a deck\n\nlatch no tab\nLATCH\ta fastening\nhatch\tto emerge from an egg\n");
+    assert_eq!(defs.len(), 2);
+    assert_eq!(defs.define("hatch").as_deref(), Some("an opening in a deck"));
+    assert_eq!(defs.define("latch").as_deref(), Some("a fastening"));
+    assert_eq!(defs.define("batch"), None);
+
+    defs.add_wordnet(concat!(
+        "  1 This software and database is being provided to you, the LICENSEE, by | Princeton University\n",
+        "07951464 14 n 02 batch 0 clutch 0 003 @ 07951068 n 0000 | (often followed by `of') a large number or amount or extent; \"a batch of letters\"\n",
+        "00740577 00 s 02 awry(p) 0 askew 0 001 & 00740336 a 0000 | turned or twisted toward one side; \"a...\"\n",
+        "02896442 06 n 01 ice_cream 0 000 | frozen dessert\n",
+        "not a synset | ignored\n",
+    ));
+    assert_eq!(defs.define("batch").as_deref(), Some("(often followed by `of') a large number or amount or extent"));
+    assert_eq!(defs.define("clutch"), defs.define("batch"));
+    assert_eq!(defs.define("awry").as_deref(), Some("turned or twisted toward one side"));
+    assert_eq!(defs.define("ice cream").as_deref(), Some("frozen dessert"));
+    assert_eq!(defs.len(), 7);
+
+    let closure = |w: &str| if w == "hatch" { Some("custom".to_owned()) } else { None };
+    assert_eq!((closure.define("hatch").as_deref(), closure.define("latch")), (Some("custom"), None));
+}
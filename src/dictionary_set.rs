@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use crate::*;
+
+/// A problem with routing a puzzle to a dictionary of a [`DictionarySet`].
+#[derive(Debug)]
+pub enum DictionarySetError<'a> {
+    /// The history has no guesses to choose a dictionary by.
+    EmptyHistory,
+    /// No dictionary in the set has words of the same length as `word`.
+    UnsupportedLength { word: &'a str, word_len: usize },
+    /// A guess in the history could not be applied to the puzzle.
+    Guess(GuessError<'a>),
+}
+
+/// A collection of dictionaries with different word lengths behind a single object, e.g., for a frontend supporting variants from 4 to 11 letters.
+/// Puzzles are routed to the dictionary for the length of their first guess (see [`DictionarySet::puzzle_for`]),
+/// so the frontend doesn't need to know the length up front.
+#[derive(Clone, Default)]
+pub struct DictionarySet {
+    dictionaries: BTreeMap<usize, Dictionary>,
+}
+impl DictionarySet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Creates a set from a list of words of mixed lengths, with a dictionary (over the english alphabet) for each length which occurs.
+    /// Empty words are ignored. If a word is invalid (not lowercase alphabetic), returns [`Err`].
+    pub fn with_words<'a, T: IntoIterator<Item = &'a str>>(words: T) -> Result<Self, WordError<'a>> {
+        let mut groups: BTreeMap<usize, Vec<&'a str>> = BTreeMap::new();
+        for word in words.into_iter().filter(|w| !w.is_empty()) {
+            groups.entry(word.chars().count()).or_default().push(word);
+        }
+        let mut res = Self::new();
+        for (word_len, words) in groups {
+            res.insert(Dictionary::with_words(word_len, words)?);
+        }
+        Ok(res)
+    }
+    /// Adds a dictionary to the set, returning the dictionary it replaced with the same word length (if any).
+    pub fn insert(&mut self, dictionary: Dictionary) -> Option<Dictionary> {
+        self.dictionaries.insert(dictionary.word_len(), dictionary)
+    }
+    /// Removes the dictionary with the given word length, returning it (if any).
+    pub fn remove(&mut self, word_len: usize) -> Option<Dictionary> {
+        self.dictionaries.remove(&word_len)
+    }
+    /// Gets the dictionary with the given word length (if any).
+    pub fn get(&self, word_len: usize) -> Option<&Dictionary> {
+        self.dictionaries.get(&word_len)
+    }
+    /// Gets the dictionary for words with the same length (in characters) as `word`, e.g., the first guess of a puzzle.
+    pub fn dictionary_for(&self, word: &str) -> Option<&Dictionary> {
+        self.get(word.chars().count())
+    }
+    /// Iterates over the dictionaries in the set, from shortest to longest word length.
+    pub fn dictionaries(&self) -> impl Iterator<Item = &Dictionary> {
+        self.dictionaries.values()
+    }
+    /// Iterates over the word lengths which have a dictionary in the set, from shortest to longest.
+    pub fn word_lens(&self) -> impl Iterator<Item = usize> + '_ {
+        self.dictionaries.keys().copied()
+    }
+    /// Gets the total number of words over all the dictionaries.
+    pub fn word_count(&self) -> usize {
+        self.dictionaries.values().map(Dictionary::len).sum()
+    }
+    /// Checks if the set has no dictionaries.
+    pub fn is_empty(&self) -> bool {
+        self.dictionaries.is_empty()
+    }
+    /// Checks if the word is in the dictionary for its length.
+    pub fn contains(&self, word: &str) -> bool {
+        self.dictionary_for(word).map(|d| d.contains(word)).unwrap_or(false)
+    }
+    /// Computes a fingerprint of every dictionary in the set (see [`Dictionary::fingerprint`]),
+    /// e.g., to detect when a cache shared by all the word lengths is stale.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325; // FNV-1a
+        for dictionary in self.dictionaries.values() {
+            for b in dictionary.fingerprint().to_le_bytes() {
+                hash ^= b as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        hash
+    }
+    /// Starts a puzzle with the dictionary for the length of `first_guess` (which is not applied).
+    /// Fails with [`DictionarySetError::UnsupportedLength`] if there is no dictionary for that length.
+    pub fn puzzle_for<'b>(&self, first_guess: &'b str) -> Result<Puzzle<'_>, DictionarySetError<'b>> {
+        match self.dictionary_for(first_guess) {
+            Some(dictionary) => Ok(Puzzle::new(dictionary)),
+            None => Err(DictionarySetError::UnsupportedLength { word: first_guess, word_len: first_guess.chars().count() }),
+        }
+    }
+    /// Starts a puzzle with the dictionary for the length of the first guess in the history, and then applies the whole history (see [`Puzzle::apply_history`]).
+    pub fn puzzle_with_history<'b, W: AsRef<str>, H: AsRef<[Hint]>>(&self, history: &'b [(W, H)]) -> Result<Puzzle<'_>, DictionarySetError<'b>> {
+        let first = history.first().ok_or(DictionarySetError::EmptyHistory)?;
+        let mut puzzle = self.puzzle_for(first.0.as_ref())?;
+        puzzle.apply_history(history).map_err(DictionarySetError::Guess)?;
+        Ok(puzzle)
+    }
+}
+
+#[test]
+fn test_dictionary_set() {
+    let set = DictionarySet::with_words(testkit::ATCH_WORDS.iter().copied().chain(["bath", "math", "path", "oath", "", "thatch"])).unwrap();
+    assert_eq!(set.word_lens().collect::<Vec<_>>(), [4, 5, 6]);
+    assert_eq!((set.word_count(), set.is_empty()), (12, false));
+    assert_eq!(set.get(4).unwrap().words().collect::<Vec<_>>(), ["bath", "math", "oath", "path"]);
+    assert_eq!(set.dictionary_for("hatch").unwrap().fingerprint(), testkit::dictionary(testkit::ATCH_WORDS).fingerprint());
+    assert!(set.contains("oath") && set.contains("thatch") && !set.contains("moth") && !set.contains("batches"));
+    assert!(matches!(DictionarySet::with_words(["bath", "Bath"]), Err(WordError::NotInAlphabet { word: "Bath" })));
+
+    // puzzles are routed by the length of the first guess
+    let puzzle = set.puzzle_with_history(&[("math", get_hint("math", "oath").unwrap())]).unwrap();
+    assert_eq!((puzzle.word_len(), puzzle.candidates()), (4, vec!["bath", "oath", "path"]));
+    assert_eq!(set.puzzle_for("watch").unwrap().candidates().len(), testkit::ATCH_WORDS.len());
+    assert!(matches!(set.puzzle_for("ab"), Err(DictionarySetError::UnsupportedLength { word: "ab", word_len: 2 })));
+    assert!(matches!(set.puzzle_with_history::<&str, Vec<Hint>>(&[]), Err(DictionarySetError::EmptyHistory)));
+    let mixed = [("math", get_hint("math", "oath").unwrap()), ("hatch", get_hint("hatch", "watch").unwrap())];
+    assert!(matches!(set.puzzle_with_history(&mixed), Err(DictionarySetError::Guess(GuessError::WrongWordLen { word: "hatch", expected_len: 4 }))));
+
+    // the fingerprint covers every dictionary
+    let mut changed = set.clone();
+    assert_eq!(changed.fingerprint(), set.fingerprint());
+    assert_eq!(changed.insert(Dictionary::with_words(4, ["moth", "math"]).unwrap()).unwrap().len(), 4);
+    assert_ne!(changed.fingerprint(), set.fingerprint());
+    assert_eq!(changed.remove(6).unwrap().word_len(), 6);
+    assert_eq!(changed.word_lens().collect::<Vec<_>>(), [4, 5]);
+    assert!(DictionarySet::new().is_empty());
+}
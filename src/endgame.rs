@@ -0,0 +1,280 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::iter;
+
+use crate::*;
+
+/// The cost of a strategy over a set of answers: `(answers not solved within the turn limit, total guesses over all answers)`.
+pub(crate) type Cost = (u64, u64);
+
+/// An exact solver for small sets of feasible words, which minimizes the expected number of guesses under a turn limit.
+/// All answers are assumed equally likely, and any dictionary word may be guessed.
+pub(crate) struct Endgame {
+    answers: usize,
+    /// The first guess of each distinct partition of all the answers, which is also the first guess of its partition of any subset.
+    distinct: Arc<Vec<usize>>,
+    /// The hint code for each pair of guess and answer, indexed by `guess * answers + answer`.
+    codes: Vec<u32>,
+    solved_code: u32,
+    memo: HashMap<(Vec<u16>, usize), Cost>,
+}
+impl Endgame {
+    /// The most answers the solver supports. Answers are labeled by a [`u8`] per distinct hint (with [`u8::MAX`] reserved), so there can be at most this many.
+    pub(crate) const MAX_ANSWERS: usize = u8::MAX as usize;
+
+    /// Creates a solver for the given (nonempty) answers, with the given guesses allowed.
+    /// Panics if there are more than [`Endgame::MAX_ANSWERS`] answers, or if the words are longer than [`HintPattern::MAX_LEN`].
+    pub(crate) fn new(guesses: &[Word], answers: &[Word]) -> Self {
+        assert!(answers.len() <= Self::MAX_ANSWERS, "too many answers for the endgame solver");
+        assert!(answers[0].len() <= HintPattern::MAX_LEN, "words are too long for the endgame solver");
+        let solved_code = 3u32.pow(answers[0].len() as u32) - 1;
+        let codes = guesses.iter().flat_map(|g| answers.iter().map(move |a| hint_code(g, a))).collect();
+        let mut res = Endgame { answers: answers.len(), distinct: Default::default(), codes, solved_code, memo: Default::default() };
+
+        let all: Vec<u16> = (0..answers.len() as u16).collect();
+        let mut seen = HashSet::new();
+        let mut labels = Vec::with_capacity(all.len());
+        res.distinct = Arc::new((0..guesses.len()).filter(|&g| {
+            res.label(g, &all, &mut labels);
+            seen.insert(labels.clone())
+        }).collect());
+        res
+    }
+    /// A lower bound on the cost of solving `n` answers with `turns` turns remaining.
+    fn lower_bound(n: u64, turns: usize) -> Cost {
+        match (n, turns) {
+            (0, _) => (0, 0),
+            (n, 0) => (n, 0),
+            (n, 1) => (n - 1, 1),
+            (n, _) => (0, 2 * n - 1), // at best, one answer is guessed immediately and the rest on the next turn
+        }
+    }
+    /// Checks if [`Endgame::lower_bound`] is the exact cost for `n` answers with `turns` turns remaining.
+    fn is_exact(n: usize, turns: usize) -> bool {
+        n <= 2 || turns <= 1 // with one turn left, the best option is always to guess one of the answers
+    }
+    /// Labels each answer by the hint it would give for the guess, returning the number of distinct labels.
+    /// Labels are assigned in order of first appearance, so guesses which partition the answers identically produce identical labels.
+    /// An answer equal to the guess is labeled [`u8::MAX`] instead.
+    fn label(&self, guess: usize, answers: &[u16], labels: &mut Vec<u8>) -> usize {
+        let codes = &self.codes[guess * self.answers..(guess + 1) * self.answers];
+        let mut distinct: Vec<u32> = Vec::with_capacity(answers.len());
+        labels.clear();
+        for &answer in answers {
+            let code = codes[answer as usize];
+            if code == self.solved_code {
+                labels.push(u8::MAX);
+                continue;
+            }
+            let label = match distinct.iter().position(|&x| x == code) {
+                Some(x) => x,
+                None => { distinct.push(code); distinct.len() - 1 }
+            };
+            labels.push(label as u8);
+        }
+        distinct.len()
+    }
+    /// A lower bound on the cost of making a guess with the given partition with `turns` turns remaining (including the guess).
+    fn partition_bound(solved: bool, groups: &[Vec<u16>], turns: usize) -> Cost {
+        let mut res = (0, solved as u64);
+        for group in groups {
+            let sub = Self::lower_bound(group.len() as u64, turns - 1);
+            res = (res.0 + sub.0, res.1 + sub.1 + group.len() as u64);
+        }
+        res
+    }
+    /// Finds the best guess from `guesses` (indices into the guess list) for the given answers (indices into the answer list),
+    /// returning the guess index and its cost.
+    fn best(&mut self, guesses: &[usize], answers: &[u16], turns: usize) -> Option<(usize, Cost)> {
+        debug_assert!(turns > 0 && !answers.is_empty());
+
+        let ideal = Self::lower_bound(answers.len() as u64, turns);
+
+        // many guesses split the answers identically, so only the first of each distinct partition needs to be considered
+        let mut seen: HashSet<Vec<u8>> = HashSet::new();
+        let mut labels = Vec::with_capacity(answers.len());
+        let mut options = vec![];
+        for &guess in guesses {
+            let count = self.label(guess, answers, &mut labels);
+            let solved = labels.contains(&u8::MAX);
+            if !solved && count == 1 { continue } // no information gained
+            if seen.contains(&labels) { continue }
+            seen.insert(labels.clone());
+
+            let mut groups = vec![vec![]; count];
+            for (&answer, &label) in iter::zip(answers, &labels) {
+                if label != u8::MAX { groups[label as usize].push(answer); }
+            }
+            let bound = Self::partition_bound(solved, &groups, turns);
+            if bound == ideal && groups.iter().all(|g| Self::is_exact(g.len(), turns - 1)) {
+                return Some((guess, bound)); // nothing can do better
+            }
+            options.push((bound, guess, groups));
+        }
+        options.sort_by_key(|x| x.0); // stable, so ties remain in guess order
+
+        let mut best: Option<(usize, Cost)> = None;
+        for (bound, guess, groups) in options {
+            if let Some((_, cost)) = best {
+                if bound >= cost { break }
+            }
+            // refine the bound one group at a time, giving up as soon as it can't beat the best so far
+            let mut cost = bound;
+            for group in groups.iter() {
+                let (lower, exact) = (Self::lower_bound(group.len() as u64, turns - 1), self.solve(group, turns - 1));
+                cost = (cost.0 + exact.0 - lower.0, cost.1 + exact.1 - lower.1);
+                if best.map(|x| cost >= x.1).unwrap_or(false) { break }
+            }
+            if best.map(|x| cost < x.1).unwrap_or(true) { best = Some((guess, cost)); }
+        }
+        best
+    }
+    /// Finds the best guess from all the guesses for the given answers, like [`Endgame::best`], returning the guess index and its cost.
+    pub(crate) fn best_overall(&mut self, answers: &[u16], turns: usize) -> Option<(usize, Cost)> {
+        let all = self.distinct.clone();
+        self.best(&all, answers, turns)
+    }
+    /// Splits the answers by the hint code they would give for the guess (in order of code), leaving out the answer equal to the guess (if any).
+    pub(crate) fn split(&self, guess: usize, answers: &[u16]) -> Vec<(u32, Vec<u16>)> {
+        let codes = &self.codes[guess * self.answers..(guess + 1) * self.answers];
+        let mut groups: BTreeMap<u32, Vec<u16>> = BTreeMap::new();
+        for &answer in answers {
+            if codes[answer as usize] != self.solved_code { groups.entry(codes[answer as usize]).or_default().push(answer); }
+        }
+        groups.into_iter().collect()
+    }
+    /// Computes the optimal cost of solving the given answers with `turns` turns remaining.
+    fn solve(&mut self, answers: &[u16], turns: usize) -> Cost {
+        if Self::is_exact(answers.len(), turns) {
+            return Self::lower_bound(answers.len() as u64, turns);
+        }
+        let key = (answers.to_vec(), turns);
+        if let Some(&cost) = self.memo.get(&key) { return cost }
+
+        let cost = self.best_overall(answers, turns).expect("guessing one of the answers always gains information").1;
+        self.memo.insert(key, cost);
+        cost
+    }
+}
+
+impl<'a> Puzzle<'a> {
+    /// Runs the exact endgame solver over the feasible words, restricted to the given top-level guesses.
+    /// Returns the chosen guess and the exact average number of guesses needed (including this one), or [`None`] if not applicable.
+    pub(crate) fn endgame_guess(&self, admissible: &[Word<'a>]) -> Option<(Word<'a>, Average)> {
+        let turns = self.turns_left();
+        let n = self.feasible_words.len();
+        if turns == 0 || n == 0 || n > self.endgame_threshold || self.slots.len() > HintPattern::MAX_LEN { return None }
+        if n == 2 {
+            if let Some(res) = self.pair_guess(admissible, turns) { return Some(res) }
+        }
+
+        // admissible words outside the dictionary (see Puzzle::best_guess_with_extra) may also be guessed on later turns
+        let mut pool = self.all_words.to_vec();
+        pool.extend(admissible.iter().filter(|w| self.all_words.binary_search(w).is_err()));
+
+        let mut solver = Endgame::new(&pool, &self.feasible_words);
+        let answers: Vec<u16> = (0..n as u16).collect();
+        let extra = &pool[self.all_words.len()..];
+        let guesses: Vec<usize> = admissible.iter().map(|w| match self.all_words.binary_search(w) {
+            Ok(i) => i,
+            Err(_) => self.all_words.len() + extra.iter().position(|x| x == w).unwrap(),
+        }).collect();
+        let (guess, cost) = solver.best(&guesses, &answers, turns)?;
+        Some((pool[guess], Average::new(cost.1, n as u64)))
+    }
+    /// Solves the endgame for exactly two feasible words directly, which is the common `_atch` style trap.
+    /// Guessing either word always tells them apart, so the first admissible one is best (1.5 guesses expected, or a coin flip on the last turn).
+    /// If neither is admissible, the first admissible word which gives them different hints is best (2 guesses each), given a turn to spare.
+    /// Returns [`None`] in the remaining cases, which are left to the full solver.
+    fn pair_guess(&self, admissible: &[Word<'a>], turns: usize) -> Option<(Word<'a>, Average)> {
+        let (a, b) = (self.feasible_words[0], self.feasible_words[1]);
+        if let Some(&guess) = admissible.iter().find(|&&w| w == a || w == b) {
+            let total = if turns == 1 { 2 } else { 3 };
+            return Some((guess, Average::new(total, 2)));
+        }
+        if turns == 1 { return None }
+        let guess = admissible.iter().find(|&&w| hint_code(&w, &a) != hint_code(&w, &b))?;
+        Some((*guess, Average::new(4, 2)))
+    }
+}
+
+#[test]
+fn test_endgame() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield", "flows"])).unwrap();
+    let puzzle = Puzzle::new(&dict);
+    let mut atch = puzzle.clone();
+    atch.guess("wield", &get_hint("wield", "catch").unwrap()).unwrap();
+    assert_eq!(atch.candidates(), &["batch", "catch", "hatch", "match", "patch"]);
+
+    // guessing a feasible word can take up to 5 guesses, but the probe "blimp" isolates all but "catch" and "hatch"
+    let best = atch.best_guess(1, AnyWord).unwrap();
+    assert!(best.endgame);
+    assert_eq!(best.word, "blimp");
+    assert_eq!(best.expected_guesses, Some(Average::new(2 + 2 + 2 + 2 + 3, 5)));
+
+    // with only one guess left, only a feasible word has any chance of winning
+    let mut limited = atch.clone();
+    limited.set_max_guesses(2);
+    let best = limited.best_guess(1, AnyWord).unwrap();
+    assert!(best.endgame && atch.candidates().contains(&best.word.as_str()));
+
+    let mut minimax = atch.clone();
+    minimax.set_endgame_threshold(0);
+    let best = minimax.best_guess(1, AnyWord).unwrap();
+    assert!(!best.endgame && best.expected_guesses.is_none());
+
+    // thresholds are capped at what the solver supports, so larger sets of feasible words still use minimax
+    let words: Vec<String> = (0..300).map(|i| (0..5).map(|j| (b'a' + (i / 4usize.pow(j) % 4) as u8) as char).collect()).collect();
+    let dict = Dictionary::with_words(5, words.iter().map(String::as_str)).unwrap();
+    let mut large = Puzzle::new(&dict);
+    large.set_endgame_threshold(usize::MAX);
+    assert_eq!(large.endgame_threshold, Puzzle::MAX_ENDGAME_THRESHOLD);
+    assert!(!large.best_guess(1, AnyWord).unwrap().endgame);
+
+    // the filter only applies to this turn, so the remaining four can still be split by "chimp" on the next turn
+    let feasible_only = atch.best_guess(1, |w: &str| w.ends_with("atch")).unwrap();
+    assert!(feasible_only.endgame);
+    assert_eq!(feasible_only.expected_guesses, Some(Average::new(1 + 3 * 4, 5)));
+}
+
+#[test]
+fn test_endgame_pair() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield", "flows"])).unwrap();
+    let mut pair = Puzzle::new(&dict);
+    pair.guess("blimp", &get_hint("blimp", "catch").unwrap()).unwrap();
+    pair.guess("wield", &get_hint("wield", "catch").unwrap()).unwrap();
+    assert_eq!(pair.candidates(), &["catch", "hatch"]);
+    let pair = pair.reduced();
+
+    // the shortcut must agree with the full solver, including which word wins ties
+    let all: Vec<Word> = pair.all_words.to_vec();
+    let admissible_sets: [Vec<Word>; 3] = [
+        all.clone(),
+        all.iter().copied().filter(|w| pair.decode(w) != "catch").collect(),
+        all.iter().copied().filter(|w| !pair.decode(w).ends_with("atch")).collect(),
+    ];
+    for admissible in admissible_sets.iter() {
+        for turns in 1..=3 {
+            let full = {
+                let mut solver = Endgame::new(&pair.all_words, &pair.feasible_words);
+                let guesses: Vec<usize> = admissible.iter().map(|w| pair.all_words.binary_search(w).unwrap()).collect();
+                solver.best(&guesses, &[0, 1], turns).map(|(g, cost)| (pair.decode(&pair.all_words[g]), Average::new(cost.1, 2)))
+            };
+            if let Some(fast) = pair.pair_guess(admissible, turns) {
+                assert_eq!(Some((pair.decode(&fast.0), fast.1)), full, "{} turns", turns);
+            }
+        }
+    }
+
+    // guessing a candidate is best, but if neither can be guessed (e.g., in hard mode), a probe like "chimp" still tells them apart
+    assert_eq!(pair.pair_guess(&admissible_sets[0], 2).map(|x| pair.decode(&x.0)), Some("catch".into()));
+    assert_eq!(pair.pair_guess(&admissible_sets[1], 2).map(|x| pair.decode(&x.0)), Some("hatch".into()));
+    let probe = pair.pair_guess(&admissible_sets[2], 2).unwrap();
+    assert_eq!((pair.decode(&probe.0).as_str(), probe.1), ("chimp", Average::new(4, 2)));
+    assert!(pair.pair_guess(&admissible_sets[2], 1).is_none());
+
+    let best = pair.best_guess(1, |w: &str| !w.ends_with("atch")).unwrap();
+    assert_eq!((best.word.as_str(), best.endgame, best.expected_guesses), ("chimp", true, Some(Average::new(4, 2))));
+    let mut minimax = pair.clone();
+    minimax.set_endgame_threshold(0);
+    assert!(!minimax.best_guess(1, AnyWord).unwrap().endgame);
+}
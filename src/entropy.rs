@@ -0,0 +1,100 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::*;
+
+/// A guess chosen to maximize the expected information from its hint, as produced by [`Puzzle::best_guess_entropy`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntropyGuess {
+    /// The word to guess.
+    pub word: String,
+    /// The Shannon entropy (in bits) of the hint the guess will receive, if every word which could be the answer is equally likely.
+    pub entropy: f64,
+    /// Whether the guess could itself be the answer.
+    pub feasible: bool,
+}
+
+/// Computes the entropy (in bits) of a partition of `total` words into groups of the given sizes.
+/// The sizes are summed in sorted order, so guesses which split the words the same way get exactly the same entropy.
+pub(crate) fn partition_entropy(mut sizes: Vec<u64>, total: u64) -> f64 {
+    sizes.sort_unstable();
+    let total = total as f64;
+    let weighted: f64 = sizes.into_iter().map(|n| n as f64 * (n as f64).log2()).sum();
+    total.log2() - weighted / total
+}
+
+impl<'a> Puzzle<'a> {
+    /// From the words in the dictionary which are admitted by `filter`, finds the guess whose hint has the most Shannon entropy
+    /// over the words which could still be the answer, i.e., which maximizes the expected information gained from making it.
+    /// This is an alternative objective to [`Puzzle::best_guess`], which minimizes the worst case instead, and can be selected
+    /// for [`Puzzle::best_guess_opts`] with [`Strategy::Entropy`].
+    /// Ties are broken by preferring words which could be the answer, then lexicographically.
+    /// With only one guess left, only words which could be the answer are suggested (unless the filter rejects all of them).
+    ///
+    /// The guesses are scored in parallel using the given number of threads (minimum `1`).
+    /// Fails under the same conditions as [`Puzzle::best_guess`].
+    pub fn best_guess_entropy<F: CandidateFilter>(&self, threads: usize, filter: F) -> Result<EntropyGuess, SolveErr> {
+        let this = self.reduced();
+        if this.feasible_words.is_empty() {
+            return Err(SolveErr::Inconsistent);
+        }
+        let mut admissible: Vec<_> = this.all_words.iter().copied().filter(|w| filter.admits(&this.decode(w))).collect();
+        if admissible.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+        if this.turns_left() == 1 && admissible.iter().any(|&w| this.could_be(w)) {
+            admissible.retain(|&w| this.could_be(w));
+        }
+
+        let better = |a: &(f64, bool, Reverse<Word>), b: &(f64, bool, Reverse<Word>)| a.0.total_cmp(&b.0).then_with(|| (a.1, a.2).cmp(&(b.1, b.2)));
+        let chunk_size = admissible.len().div_ceil(threads.max(1));
+        let (entropy, feasible, Reverse(guess)) = crossbeam::scope(|scope| {
+            let threads: Vec<_> = admissible.chunks(chunk_size).map(|guesses| {
+                let this = &this;
+                scope.spawn(move |_| guesses.iter().map(|&guess| (this.entropy(guess), this.could_be(guess), Reverse(guess))).max_by(better).unwrap())
+            }).collect();
+            threads.into_iter().map(|x| x.join().unwrap()).max_by(better).unwrap()
+        }).unwrap();
+
+        Ok(EntropyGuess { word: this.decode(&guess), entropy, feasible })
+    }
+    /// Computes the entropy of the hint to `guess` over the feasible words.
+    fn entropy(&self, guess: Word) -> f64 {
+        let mut groups: HashMap<u32, u64> = HashMap::new();
+        for answer in self.feasible_words.iter() {
+            *groups.entry(hint_code(&guess, answer)).or_default() += 1;
+        }
+        partition_entropy(groups.into_values().collect(), self.feasible_words.len() as u64)
+    }
+}
+
+#[test]
+fn test_best_guess_entropy() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "lower", "wombs"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+    let candidates = puzzle.candidates();
+
+    let entropy = |guess: &str| {
+        let groups = partition_counts(guess, &candidates).unwrap();
+        partition_entropy(groups.into_iter().map(|x| x.1 as u64).collect(), candidates.len() as u64)
+    };
+    let best = puzzle.best_guess_entropy(3, AnyWord).unwrap();
+    assert_eq!(best, puzzle.best_guess_entropy(1, AnyWord).unwrap());
+    assert_eq!(best.entropy, entropy(&best.word));
+    assert!(dict.words().all(|w| entropy(w) <= best.entropy));
+    assert_eq!(best.feasible, candidates.contains(&best.word.as_str()));
+
+    // a uniform split of n words has log2(n) bits, and a guess which can't split them has none
+    assert_eq!(partition_entropy(vec![1; 8], 8), 3.0);
+    assert_eq!(partition_entropy(vec![8], 8), 0.0);
+    let filtered = puzzle.best_guess_entropy(1, |w: &str| w == "wombs").unwrap();
+    assert_eq!((filtered.word.as_str(), filtered.feasible), ("wombs", false));
+    assert!(filtered.entropy < best.entropy);
+
+    let opts = SolveOptions::new().with_strategy(Strategy::Entropy);
+    let scored = puzzle.best_guess_opts(&opts).unwrap();
+    assert_eq!((scored.word.as_str(), scored.feasible), (best.word.as_str(), best.feasible));
+    assert_eq!(puzzle.suggest(&Strategy::Entropy, 2).unwrap(), best.word);
+    assert!(matches!(puzzle.best_guess_entropy(1, |_: &str| false), Err(SolveErr::NoAdmissibleGuess)));
+}
@@ -0,0 +1,213 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+
+use crate::*;
+
+/// A guess which minimizes the worst-case number of guesses needed to solve the puzzle, as produced by [`Puzzle::best_guess_exact`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExactGuess {
+    /// The word to guess.
+    pub word: String,
+    /// The number of guesses (including this one) which is proven to find every word which could be the answer when playing on optimally,
+    /// and which no admissible guess can improve on. This is not limited by the number of guesses left in the game.
+    pub depth: usize,
+    /// Whether the guess could itself be the answer.
+    pub feasible: bool,
+}
+
+/// The hint code for each pair of guess and answer, which is computed once and shared by the [`DepthSearch`] of every thread.
+struct HintTable {
+    answers: usize,
+    guesses: usize,
+    /// Indexed by `guess * answers + answer`.
+    codes: Vec<u32>,
+    solved_code: u32,
+}
+impl HintTable {
+    fn new(guesses: &[Word], answers: &[Word]) -> Self {
+        assert!(answers.len() <= Puzzle::MAX_EXACT_ANSWERS, "too many answers for an exact search");
+        let solved_code = 3u32.pow(answers[0].len() as u32) - 1;
+        let codes = guesses.iter().flat_map(|g| answers.iter().map(move |a| hint_code(g, a))).collect();
+        HintTable { answers: answers.len(), guesses: guesses.len(), codes, solved_code }
+    }
+}
+
+/// A full game-tree search over a set of answers (labeled by their index in the [`HintTable`]), which decides if they can all be found within a number of guesses.
+/// Any dictionary word may be guessed, and the sets proven (un)solvable at each depth are memoized.
+struct DepthSearch {
+    table: Arc<HintTable>,
+    /// The fewest guesses known to be enough for each set of answers.
+    enough: HashMap<Vec<u16>, usize>,
+    /// The most guesses known not to be enough for each set of answers.
+    not_enough: HashMap<Vec<u16>, usize>,
+}
+impl DepthSearch {
+    fn new(table: Arc<HintTable>) -> Self {
+        DepthSearch { table, enough: Default::default(), not_enough: Default::default() }
+    }
+    /// Splits the answers by the hint they would give for the guess, leaving out the answer equal to the guess (if any).
+    /// The groups are sorted largest first, since those are the most likely to need too many guesses.
+    fn groups(&self, guess: usize, answers: &[u16]) -> Vec<Vec<u16>> {
+        let table = &*self.table;
+        let codes = &table.codes[guess * table.answers..(guess + 1) * table.answers];
+        let mut groups: Vec<(u32, Vec<u16>)> = vec![];
+        for &answer in answers {
+            let code = codes[answer as usize];
+            if code == table.solved_code { continue }
+            match groups.iter_mut().find(|x| x.0 == code) {
+                Some(group) => group.1.push(answer),
+                None => groups.push((code, vec![answer])),
+            }
+        }
+        groups.sort_by_key(|x| std::cmp::Reverse(x.1.len())); // stable, so groups of equal size keep the order of the answers
+        groups.into_iter().map(|x| x.1).collect()
+    }
+    /// Checks if guessing `guess` and then playing on optimally finds every answer within `depth` guesses (including this one).
+    fn guess_solves(&mut self, guess: usize, answers: &[u16], depth: usize) -> bool {
+        if depth == 0 { return false }
+        let groups = self.groups(guess, answers);
+        // with one guess left after this, every group must be a single word (checked up front, since it's the most common failure)
+        if depth == 2 && groups.first().map(|x| x.len() > 1).unwrap_or(false) { return false }
+        groups.iter().all(|group| self.solvable(group, depth - 1))
+    }
+    /// Checks if every one of the answers can be found within `depth` guesses.
+    fn solvable(&mut self, answers: &[u16], depth: usize) -> bool {
+        match (answers.len(), depth) {
+            (0, _) => return true,
+            (_, 0) => return false,
+            (1, _) => return true,
+            (_, 1) => return false,
+            (2, _) => return true, // guess either one
+            _ => (),
+        }
+        if self.enough.get(answers).map(|&x| x <= depth).unwrap_or(false) { return true }
+        if self.not_enough.get(answers).map(|&x| x >= depth).unwrap_or(false) { return false }
+
+        // many guesses split the answers identically, so only the first of each distinct partition needs to be checked
+        let table = &*self.table;
+        let mut seen = HashSet::new();
+        let mut options = vec![];
+        for guess in 0..table.guesses {
+            let codes = &table.codes[guess * table.answers..(guess + 1) * table.answers];
+            let labels: Vec<u32> = answers.iter().map(|&a| codes[a as usize]).collect();
+            if labels.iter().all(|&x| x == labels[0] && x != table.solved_code) { continue } // no information gained
+            if !seen.insert(labels) { continue }
+            options.push((self.groups(guess, answers)[0].len(), guess));
+        }
+        // guesses with the smallest worst case are the most likely to succeed, so they are checked first
+        options.sort_unstable();
+        let res = options.into_iter().any(|(_, guess)| self.guess_solves(guess, answers, depth));
+
+        let memo = if res { &mut self.enough } else { &mut self.not_enough };
+        memo.insert(answers.to_vec(), depth);
+        res
+    }
+}
+
+impl<'a> Puzzle<'a> {
+    /// The most words which may still be the answer for [`Puzzle::best_guess_exact`].
+    /// This bounds the hint table it shares between threads (four bytes per guess and answer, about 100MB for 12,000 guesses),
+    /// and keeps the answers within the range of the `u16` labels used while searching. The search is impractical long before this anyway.
+    pub const MAX_EXACT_ANSWERS: usize = 2048;
+
+    /// From the words in the dictionary which are admitted by `filter`, finds the guess which guarantees finding the answer in the fewest guesses,
+    /// along with that number (see [`ExactGuess::depth`]). Unlike [`Puzzle::best_guess`], which minimizes the words left after one guess,
+    /// this searches the full game tree (with any dictionary word allowed as a follow-up), so the result is exact.
+    /// Ties are broken by preferring words which could be the answer, then lexicographically.
+    ///
+    /// The search deepens one guess at a time and prunes guesses which split the remaining words the same way as one already checked,
+    /// but it is still exponential, so it is only practical once few words could be the answer.
+    /// The guesses for this turn are checked in parallel using the given number of threads (minimum `1`).
+    /// Fails under the same conditions as [`Puzzle::best_guess`], or with [`SolveErr::TooManyAnswers`] if more than [`Puzzle::MAX_EXACT_ANSWERS`] words could be the answer.
+    pub fn best_guess_exact<F: CandidateFilter>(&self, threads: usize, filter: F) -> Result<ExactGuess, SolveErr> {
+        let this = self.reduced();
+        if this.feasible_words.is_empty() {
+            return Err(SolveErr::Inconsistent);
+        }
+        if this.feasible_words.len() > Self::MAX_EXACT_ANSWERS {
+            return Err(SolveErr::TooManyAnswers { answers: this.feasible_words.len(), max: Self::MAX_EXACT_ANSWERS });
+        }
+        let mut admissible: Vec<_> = this.all_words.iter().copied().filter(|w| filter.admits(&this.decode(w))).collect();
+        if admissible.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+        admissible.sort_by_key(|&w| !this.could_be(w)); // stable, so ties remain in lexicographic order
+        let index = |w: &Word| this.all_words.binary_search(w).unwrap();
+
+        // checking every guess at one depth before the next avoids searching deeply for guesses which are beaten by shallower ones,
+        // and each thread keeps its search (and what it has proven) from one depth to the next
+        let answers: Vec<u16> = (0..this.feasible_words.len()).map(|x| u16::try_from(x).unwrap()).collect();
+        let chunk_size = admissible.len().div_ceil(threads.max(1));
+        let table = Arc::new(HintTable::new(&this.all_words, &this.feasible_words));
+        let mut searches: Vec<_> = admissible.chunks(chunk_size).map(|_| DepthSearch::new(Arc::clone(&table))).collect();
+        // any guess followed by the feasible words one at a time finds the answer, so some depth up to one more than their number succeeds
+        let (depth, position) = (1..=answers.len() + 1).find_map(|depth| {
+            let found = AtomicUsize::new(usize::MAX);
+            crossbeam::scope(|scope| {
+                for (chunk, (guesses, search)) in admissible.chunks(chunk_size).zip(searches.iter_mut()).enumerate() {
+                    let (answers, found, index) = (&answers, &found, &index);
+                    scope.spawn(move |_| {
+                        let start = chunk * chunk_size;
+                        // once an earlier guess has succeeded, the rest of the chunk can't win the tie
+                        let success = guesses.iter().enumerate().take_while(|_| found.load(Ordering::Relaxed) > start)
+                            .find(|(_, guess)| search.guess_solves(index(guess), answers, depth));
+                        if let Some((i, _)) = success { found.fetch_min(start + i, Ordering::Relaxed); }
+                    });
+                }
+            }).unwrap();
+            Some(found.into_inner()).filter(|&x| x != usize::MAX).map(|x| (depth, x))
+        }).unwrap();
+
+        let guess = admissible[position];
+        Ok(ExactGuess { word: this.decode(&guess), depth, feasible: this.could_be(guess) })
+    }
+}
+
+#[test]
+fn test_best_guess_exact() {
+    // finds the fewest guesses for the answers from scratch with get_hint, trying every word at every step
+    fn reference(words: &[&str], answers: &[&str], guess: &str) -> usize {
+        let mut groups: HashMap<Vec<Hint>, Vec<&str>> = HashMap::new();
+        for &answer in answers { groups.entry(get_hint(guess, answer).unwrap()).or_default().push(answer); }
+        if groups.len() == 1 && !answers.contains(&guess) { return usize::MAX } // no progress
+        1 + groups.into_iter().filter(|x| x.0.iter().any(|&h| h != Hint::Correct)).map(|(_, group)| {
+            words.iter().map(|w| reference(words, &group, w)).min().unwrap()
+        }).max().unwrap_or(0)
+    }
+
+    for words in [testkit::ATCH_WORDS, testkit::ANAGRAM_WORDS, testkit::DUPLICATE_WORDS] {
+        let dict = testkit::dictionary(words);
+        let mut puzzle = Puzzle::new(&dict);
+        let opener = words[words.len() - 1];
+        for history in [vec![], vec![(opener, get_hint(opener, words[0]).unwrap())]] {
+            puzzle.apply_history(&history).unwrap();
+            let answers = puzzle.candidates();
+            let best = puzzle.best_guess_exact(2, AnyWord).unwrap();
+            let (depth, _, word) = words.iter().map(|&w| (reference(words, &answers, w), !answers.contains(&w), w)).min().unwrap();
+            assert_eq!((best.word.as_str(), best.depth, best.feasible), (word, depth, answers.contains(&word)), "{:?} {:?}", words, history);
+            assert_eq!(best, puzzle.best_guess_exact(1, AnyWord).unwrap());
+        }
+    }
+
+    // only guessing the _atch words finds one at a time, but following up with a probe from outside the family finds any of them by the third guess
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield", "flows"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("wield", &get_hint("wield", "catch").unwrap()).unwrap();
+    assert_eq!(puzzle.best_guess_exact(1, AnyWord).unwrap(), ExactGuess { word: "batch".into(), depth: 3, feasible: true });
+    assert_eq!(puzzle.best_guess_exact(3, |w: &str| !w.ends_with("atch")).unwrap(), ExactGuess { word: "blimp".into(), depth: 3, feasible: false });
+    assert_eq!(puzzle.best_guess_exact(2, |w: &str| w == "flows").unwrap().depth, 4);
+    puzzle.guess("batch", &get_hint("batch", "catch").unwrap()).unwrap();
+    puzzle.guess("match", &get_hint("match", "catch").unwrap()).unwrap();
+    assert_eq!(puzzle.best_guess_exact(1, AnyWord).unwrap().depth, 2);
+
+    let mut solved = Puzzle::new(&dict);
+    solved.guess("hatch", &get_hint("hatch", "hatch").unwrap()).unwrap();
+    assert_eq!(solved.best_guess_exact(1, AnyWord).unwrap(), ExactGuess { word: "hatch".into(), depth: 1, feasible: true });
+    assert!(matches!(puzzle.best_guess_exact(1, |_: &str| false), Err(SolveErr::NoAdmissibleGuess)));
+
+    // too many answers are refused before anything is computed
+    let words: Vec<String> = (0..Puzzle::MAX_EXACT_ANSWERS + 1).map(|i| (0..5).map(|j| (b'a' + (i >> (j * 3) & 7) as u8) as char).collect()).collect();
+    let dict = Dictionary::with_words(5, words.iter().map(String::as_str)).unwrap();
+    assert!(matches!(Puzzle::new(&dict).best_guess_exact(1, AnyWord), Err(SolveErr::TooManyAnswers { answers, max: Puzzle::MAX_EXACT_ANSWERS }) if answers == words.len()));
+}
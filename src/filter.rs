@@ -0,0 +1,113 @@
+use std::iter;
+
+use crate::*;
+
+/// The number of words tested at once by [`Puzzle::filter_feasible`].
+const LANES: usize = 16;
+
+impl<'a> Puzzle<'a> {
+    /// Equivalent to filtering `words` by [`Puzzle::could_be`], but tests the slots of [`LANES`] words at a time.
+    /// Each slot's allowed letters (minus any banned letters) are broadcast to every lane and tested against the letters of the words in the block
+    /// (see [`block_passes`]), rather than word by word with an early exit.
+    /// Only the words which pass are then checked against the letter counts, and only if any count is actually constrained.
+    pub(crate) fn filter_feasible(&self, words: &[Word<'a>]) -> Vec<Word<'a>> {
+        self.filter_feasible_by(words, block_passes)
+    }
+    /// Like [`Puzzle::filter_feasible`], but with the given test for each block (so that the paths of [`block_passes`] can be compared).
+    fn filter_feasible_by(&self, words: &[Word<'a>], block_passes: fn(&[u32], &[Word]) -> u32) -> Vec<Word<'a>> {
+        let masks: Vec<u32> = self.slots.iter().map(|s| s.bits() & !self.banned_letters.bits()).collect();
+        let counts_matter = self.letter_counts.iter().any(|&(min, max)| min > 0 || max < self.slots.len());
+
+        let mut res = Vec::with_capacity(words.len());
+        let mut blocks = words.chunks_exact(LANES);
+        for block in &mut blocks {
+            let passed = block_passes(&masks, block);
+            for (lane, &word) in block.iter().enumerate() {
+                if passed >> lane & 1 != 0 && (!counts_matter || self.fits_counts(word)) { res.push(word); }
+            }
+        }
+        res.extend(blocks.remainder().iter().copied().filter(|&w| self.could_be(w)));
+        res
+    }
+}
+
+/// Tests the letters of a block of [`LANES`] words against the allowed letters of each slot, as a bitmask with bit `i` set if word `i` passes.
+/// This uses AVX2 (8 words per instruction) where the CPU supports it, and otherwise the same steps on plain arrays.
+fn block_passes(masks: &[u32], block: &[Word]) -> u32 {
+    debug_assert_eq!(block.len(), LANES);
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: the CPU was just checked to support AVX2
+        return unsafe { block_passes_avx2(masks, block) };
+    }
+    block_passes_portable(masks, block)
+}
+fn block_passes_portable(masks: &[u32], block: &[Word]) -> u32 {
+    let mut passed = [1u32; LANES];
+    for (i, &mask) in masks.iter().enumerate() {
+        for (passed, word) in iter::zip(&mut passed, block) { *passed &= mask >> word[i]; }
+    }
+    passed.iter().enumerate().fold(0, |acc, (lane, &x)| acc | (x & 1) << lane)
+}
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn block_passes_avx2(masks: &[u32], block: &[Word]) -> u32 {
+    use std::arch::x86_64::*;
+
+    let mut low = _mm256_set1_epi32(1);
+    let mut high = low;
+    for (i, &mask) in masks.iter().enumerate() {
+        let letters: [i32; LANES] = std::array::from_fn(|lane| block[lane][i] as i32);
+        let mask = _mm256_set1_epi32(mask as i32);
+        // SAFETY: both loads are within the 16 letters, and loadu has no alignment requirement
+        low = _mm256_and_si256(low, _mm256_srlv_epi32(mask, _mm256_loadu_si256(letters.as_ptr() as *const __m256i)));
+        high = _mm256_and_si256(high, _mm256_srlv_epi32(mask, _mm256_loadu_si256(letters.as_ptr().add(8) as *const __m256i)));
+    }
+    // move each lane's pass bit to its sign bit, which movemask collects
+    let bits = |x: __m256i| _mm256_movemask_ps(_mm256_castsi256_ps(_mm256_slli_epi32(x, 31))) as u32;
+    bits(low) | bits(high) << 8
+}
+
+#[test]
+fn test_filter_feasible() {
+    let dict = GameProfile::Wordle.dictionary().unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    let check = |puzzle: &Puzzle| {
+        let words: Vec<Word> = puzzle.all_words.iter().copied().collect();
+        for len in [0, 1, LANES - 1, LANES, LANES + 1, words.len()] {
+            let expected: Vec<Word> = words[..len].iter().copied().filter(|&w| puzzle.could_be(w)).collect();
+            assert!(puzzle.filter_feasible(&words[..len]) == expected);
+            assert!(puzzle.filter_feasible_by(&words[..len], block_passes_portable) == expected);
+        }
+    };
+    check(&puzzle);
+    for (guess, answer) in [("crane", "slate"), ("sissy", "sushi"), ("eerie", "geese")] {
+        puzzle.guess(guess, &get_hint(guess, answer).unwrap()).unwrap();
+        check(&puzzle); // not reduced yet, so the slots and counts are checked independently
+        puzzle.force_reduce();
+        check(&puzzle);
+    }
+}
+
+#[test]
+#[ignore = "a timing measurement rather than a test, run with --release -- --ignored --nocapture"]
+fn bench_filter_feasible() {
+    use std::time::Instant;
+    const ROUNDS: usize = 500;
+
+    let dict = GameProfile::Wordle.dictionary().unwrap();
+    let words: Vec<Word> = Puzzle::new(&dict).all_words.iter().copied().collect();
+    for history in [&[][..], &["toned"], &["toned", "slump"]] {
+        let mut puzzle = Puzzle::new(&dict);
+        for guess in history { puzzle.guess(guess, &get_hint(guess, "weary").unwrap()).unwrap(); }
+        let time = |name: &str, f: &dyn Fn() -> usize| {
+            let start = Instant::now();
+            let mut kept = 0;
+            for _ in 0..ROUNDS { kept += std::hint::black_box(f()); }
+            println!("{:?} {:>6}: {:>5.2}ns/word ({} kept)", history, name, start.elapsed().as_nanos() as f64 / (ROUNDS * words.len()) as f64, kept / ROUNDS);
+        };
+        time("scalar", &|| words.iter().filter(|&&w| puzzle.could_be(w)).count());
+        time("blocks", &|| puzzle.filter_feasible_by(&words, block_passes_portable).len());
+        time("simd", &|| puzzle.filter_feasible(&words).len());
+    }
+}
@@ -0,0 +1,351 @@
+use crate::*;
+
+#[derive(Debug)]
+pub enum GameError<'a> {
+    InvalidWord(WordError<'a>),
+    /// The guess is not in the dictionary. Includes the closest words which are, if any, as suggested corrections.
+    NotInDictionary { word: &'a str, suggestions: Vec<String> },
+    /// In hard mode, the guess doesn't reuse the letters revealed by an earlier guess (the one given).
+    HardModeViolation { word: &'a str, previous: String },
+    GameOver,
+}
+impl<'a> From<WordError<'a>> for GameError<'a> {
+    fn from(e: WordError<'a>) -> Self {
+        GameError::InvalidWord(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus { InProgress, Won, Lost }
+
+/// The solver's assessment of a single turn of a [`Game`], as produced by [`Game::grade`].
+#[derive(Debug, Clone)]
+pub struct TurnGrade {
+    /// The guess that was made.
+    pub guess: String,
+    /// The number of words which could have been the answer before making the guess.
+    pub candidates: usize,
+    /// The worst-case number of remaining words after making the guess.
+    pub worst_case: u64,
+    /// The average-case number of remaining words after making the guess.
+    pub average: Average,
+    /// The guess that the solver would have made instead.
+    pub best_guess: String,
+    /// The worst-case number of remaining words after making the solver's guess.
+    pub best_worst_case: u64,
+    /// The average-case number of remaining words after making the solver's guess.
+    pub best_average: Average,
+}
+
+/// One of the alternatives to a guess in a [`TurnPostmortem`], followed by the rest of the game as the solver would have played it.
+#[derive(Debug, Clone)]
+pub struct AlternativeLine {
+    /// The alternative guess.
+    pub guess: String,
+    /// The worst-case number of remaining words after making the alternative guess.
+    pub worst_case: u64,
+    /// The average-case number of remaining words after making the alternative guess.
+    pub average: Average,
+    /// Each guess of the line (starting with [`AlternativeLine::guess`] and followed by the solver's guesses)
+    /// and the number of words which could still be the answer after it.
+    pub steps: Vec<(String, usize)>,
+    /// Whether the line finds the answer within the guess limit (in which case the last step is the answer).
+    pub won: bool,
+}
+
+/// The alternatives to a single turn of a [`Game`], as produced by [`Game::postmortem`].
+#[derive(Debug, Clone)]
+pub struct TurnPostmortem {
+    /// The guess that was made.
+    pub guess: String,
+    /// The number of words which could still be the answer after the guess that was made.
+    pub remaining: usize,
+    /// The best guesses other than the one that was made, in order of score.
+    pub alternatives: Vec<AlternativeLine>,
+}
+
+/// A game of wordle with a known answer, which only accepts guesses from the [`Dictionary`].
+#[derive(Clone)]
+pub struct Game<'a> {
+    dictionary: &'a Dictionary,
+    answer: String,
+    max_guesses: usize,
+    hard_mode: bool,
+    history: Vec<(String, Vec<Hint>)>,
+}
+impl<'a> Game<'a> {
+    /// The number of guesses allowed in the standard game.
+    pub const DEFAULT_MAX_GUESSES: usize = 6;
+    /// The maximum edit distance of the corrections suggested for guesses not in the dictionary.
+    pub const SUGGESTION_DISTANCE: usize = 2;
+    /// The maximum number of corrections suggested for guesses not in the dictionary.
+    pub const MAX_SUGGESTIONS: usize = 5;
+
+    /// Creates a new game with the given answer, which must be a valid word for the dictionary (but need not be in it).
+    pub fn new<'b>(dictionary: &'a Dictionary, answer: &'b str) -> Result<Self, WordError<'b>> {
+        dictionary.alphabet.encode(dictionary.word_len, answer)?;
+        Ok(Game { dictionary, answer: answer.into(), max_guesses: Self::DEFAULT_MAX_GUESSES, hard_mode: false, history: vec![] })
+    }
+    /// Creates a new game with an answer picked uniformly at random from the dictionary.
+    /// Panics if the dictionary is empty.
+    pub fn random(dictionary: &'a Dictionary, rng: &mut SolverRng) -> Self {
+        let answer = rng.choose(&dictionary.words).expect("empty dictionary");
+        Game { dictionary, answer: answer.clone(), max_guesses: Self::DEFAULT_MAX_GUESSES, hard_mode: false, history: vec![] }
+    }
+    /// Creates a new game whose answer is determined by the day number (e.g., days since the unix epoch),
+    /// so that everyone playing on the same day with the same dictionary gets the same answer.
+    /// Panics if the dictionary is empty.
+    pub fn daily(dictionary: &'a Dictionary, day: u64) -> Self {
+        Self::random(dictionary, &mut SolverRng::new(day))
+    }
+    /// Sets the maximum number of guesses before the game is lost.
+    pub fn with_max_guesses(mut self, max_guesses: usize) -> Self {
+        self.max_guesses = max_guesses;
+        self
+    }
+    /// Sets whether the game is played in hard mode, where every guess must reuse the letters revealed so far
+    /// (greens in place and yellows anywhere).
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+    /// Makes a guess, which must be a word from the dictionary (and follow the hard mode rules, if enabled), and returns the resulting hint.
+    /// Invalid guesses do not count toward the guess limit.
+    pub fn guess<'b>(&mut self, word: &'b str) -> Result<Vec<Hint>, GameError<'b>> {
+        if self.status() != GameStatus::InProgress {
+            return Err(GameError::GameOver);
+        }
+        let encoded = self.dictionary.alphabet.encode(self.dictionary.word_len, word)?;
+        if !self.dictionary.contains(word) {
+            let suggestions = self.dictionary.nearest(word, Self::SUGGESTION_DISTANCE).into_iter().take(Self::MAX_SUGGESTIONS).map(Into::into).collect();
+            return Err(GameError::NotInDictionary { word, suggestions });
+        }
+        if self.hard_mode {
+            for (prev, hint) in self.history.iter() {
+                let encoded_prev = self.dictionary.alphabet.encode(self.dictionary.word_len, prev).unwrap();
+                if !obeys_hard_mode(&encoded, &encoded_prev, hint) {
+                    return Err(GameError::HardModeViolation { word, previous: prev.clone() });
+                }
+            }
+        }
+
+        let hint = self.dictionary.alphabet.get_hint(word, &self.answer).unwrap();
+        self.history.push((word.into(), hint.clone()));
+        Ok(hint)
+    }
+    pub fn status(&self) -> GameStatus {
+        match self.history.last() {
+            Some((guess, _)) if *guess == self.answer => GameStatus::Won,
+            _ if self.history.len() >= self.max_guesses => GameStatus::Lost,
+            _ => GameStatus::InProgress,
+        }
+    }
+    /// Gets the guesses made so far and the hints received for them.
+    pub fn history(&self) -> &[(String, Vec<Hint>)] {
+        &self.history
+    }
+    pub fn max_guesses(&self) -> usize {
+        self.max_guesses
+    }
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+    /// Gets the score line of a finished game, such as `4/6*` (`*` for hard mode), or `X/6` if the game was lost.
+    /// Returns [`None`] if the game is still in progress.
+    pub fn score(&self) -> Option<String> {
+        let guesses = match self.status() {
+            GameStatus::InProgress => return None,
+            GameStatus::Won => self.history.len().to_string(),
+            GameStatus::Lost => "X".into(),
+        };
+        Some(format!("{}/{}{}", guesses, self.max_guesses, if self.hard_mode { "*" } else { "" }))
+    }
+    /// Formats the standard emoji share grid for a finished game, headed by the given title (e.g., `Wordle 1234`) and the [`Game::score`].
+    /// This reveals the hints but not the guesses, and returns [`None`] if the game is still in progress.
+    pub fn share_grid(&self, title: &str) -> Option<String> {
+        let mut res = format!("{} {}\n", title, self.score()?);
+        for (_, hint) in self.history.iter() {
+            res.push('\n');
+            res.extend(hint.iter().map(|h| h.to_emoji()));
+        }
+        Some(res)
+    }
+    /// Describes a finished game in words without revealing any guesses (e.g., for screen readers), with one line per guess
+    /// counting its correct and present letters. Returns [`None`] if the game is still in progress.
+    pub fn summary(&self) -> Option<String> {
+        let mut res = match self.status() {
+            GameStatus::InProgress => return None,
+            GameStatus::Won => format!("solved in {} of {} guesses", self.history.len(), self.max_guesses),
+            GameStatus::Lost => format!("not solved in {} guesses", self.max_guesses),
+        };
+        if self.hard_mode { res.push_str(" (hard mode)"); }
+        for (i, (_, hint)) in self.history.iter().enumerate() {
+            let count = |x: Hint| hint.iter().filter(|&&h| h == x).count();
+            res.push_str(&match (count(Hint::Correct), count(Hint::Present)) {
+                (c, _) if c == hint.len() => format!("\n{}: all correct", i + 1),
+                (c, p) => format!("\n{}: {} correct, {} present", i + 1, c, p),
+            });
+        }
+        Some(res)
+    }
+    /// Gets the answer to the puzzle (spoilers!).
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+    /// Has the solver grade each guess made so far by comparing it to the guess the solver would have made in the same position.
+    /// The `threads` input is forwarded to [`Puzzle::best_guess`].
+    pub fn grade(&self, threads: usize) -> Result<Vec<TurnGrade>, SolveErr> {
+        let mut puzzle = Puzzle::new(self.dictionary);
+        puzzle.set_max_guesses(self.max_guesses);
+        #[cfg(feature = "debug-invariants")]
+        puzzle.set_known_answer(&self.answer);
+        let mut res = Vec::with_capacity(self.history.len());
+        for (guess, hint) in self.history.iter() {
+            let candidates = puzzle.feasible_words.len();
+            let BestGuess { word: best_guess, worst_case: best_worst_case, average: best_average, .. } = puzzle.best_guess(threads, AnyWord)?;
+
+            let word = puzzle.encode(guess).unwrap();
+            let (worst_case, average) = if candidates == 1 && puzzle.feasible_words[0] == word.as_ref() {
+                (0, Average::ZERO) // consistent with best_guess for solved puzzles
+            } else {
+                puzzle.score_guess(word.as_ref(), None).ok_or(SolveErr::Inconsistent)?
+            };
+
+            res.push(TurnGrade { guess: guess.clone(), candidates, worst_case, average, best_guess, best_worst_case, best_average });
+            puzzle.guess(guess, hint).unwrap();
+            puzzle.force_reduce();
+        }
+        Ok(res)
+    }
+    /// Has the solver suggest up to `n` alternatives to each guess made so far, and play out the rest of the game after each one
+    /// (with [`Puzzle::best_guess`]) to show how it would have unfolded, e.g., for a "what if" review of a finished game.
+    /// In hard mode, only alternatives (and later guesses) which follow the hard mode rules are considered.
+    /// A simulated game (e.g., from [`play`]) can be reviewed by replaying its guesses into a [`Game`].
+    /// The `threads` input is forwarded to [`Puzzle::rank_guesses`] and [`Puzzle::best_guess`].
+    pub fn postmortem(&self, threads: usize, n: usize) -> Result<Vec<TurnPostmortem>, SolveErr> {
+        let mut puzzle = Puzzle::new(self.dictionary);
+        puzzle.set_max_guesses(self.max_guesses);
+        let mut res = Vec::with_capacity(self.history.len());
+        for (guess, hint) in self.history.iter() {
+            let hard_mode = self.hard_mode.then(|| puzzle.hard_mode_filter());
+            let filter = |word: &str| word != guess && hard_mode.as_ref().map(|f| f.admits(word)).unwrap_or(true);
+            let mut alternatives = vec![];
+            for ranked in puzzle.rank_guesses(threads, filter, n)? {
+                let steps = self.play_out(&puzzle, &ranked.word, threads)?;
+                let won = steps.last().map(|x| x.0 == self.answer).unwrap_or(false);
+                alternatives.push(AlternativeLine { guess: ranked.word, worst_case: ranked.worst_case, average: ranked.average, steps, won });
+            }
+
+            puzzle.guess(guess, hint).unwrap();
+            puzzle.force_reduce();
+            res.push(TurnPostmortem { guess: guess.clone(), remaining: puzzle.feasible_words.len(), alternatives });
+        }
+        Ok(res)
+    }
+    /// Plays the rest of the game from the given position, starting with `first` and continuing with the solver's guesses,
+    /// until the answer is found or the guess limit is reached.
+    fn play_out(&self, puzzle: &Puzzle, first: &str, threads: usize) -> Result<Vec<(String, usize)>, SolveErr> {
+        let mut puzzle = puzzle.clone();
+        let mut steps = vec![];
+        let mut guess = first.to_owned();
+        loop {
+            puzzle.guess(&guess, &self.dictionary.alphabet.get_hint(&guess, &self.answer).unwrap()).unwrap();
+            puzzle.force_reduce();
+            let done = guess == self.answer;
+            steps.push((guess, puzzle.feasible_words.len()));
+            if done || puzzle.turns_left() == 0 { return Ok(steps) }
+            guess = puzzle.best_guess_opts(&SolveOptions::new().with_threads(threads).with_hard_mode(self.hard_mode))?.word;
+        }
+    }
+}
+
+#[test]
+fn test_game() {
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "match", "chimp", "champ", "lower"]).unwrap();
+    assert!(dict.contains("hatch") && !dict.contains("hutch") && !dict.contains("hat"));
+
+    let mut game = Game::new(&dict, "latch").unwrap().with_max_guesses(3);
+    assert_eq!(game.status(), GameStatus::InProgress);
+    match game.guess("hutch") {
+        Err(GameError::NotInDictionary { word: "hutch", suggestions }) => assert_eq!(suggestions, &["hatch", "batch", "catch", "latch", "match"]),
+        x => panic!("{:?}", x),
+    }
+    assert!(matches!(game.guess("zzzzz"), Err(GameError::NotInDictionary { suggestions, .. }) if suggestions.is_empty()));
+    assert!(matches!(game.guess("hat"), Err(GameError::InvalidWord(_))));
+    assert_eq!(game.guess("lower").unwrap(), get_hint("lower", "latch").unwrap());
+    assert_eq!(game.guess("latch").unwrap(), &[Hint::Correct; 5]);
+    assert_eq!(game.status(), GameStatus::Won);
+    assert!(matches!(game.guess("batch"), Err(GameError::GameOver)));
+    assert_eq!(game.history().len(), 2);
+
+    let grades = game.grade(1).unwrap();
+    assert_eq!(grades.len(), 2);
+    assert_eq!((grades[0].guess.as_str(), grades[0].candidates), ("lower", 8));
+    assert!((grades[0].best_worst_case, grades[0].best_average) <= (grades[0].worst_case, grades[0].average));
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "latch").unwrap()).unwrap();
+    assert_eq!(grades[1].candidates, puzzle.candidates().len());
+
+    let mut game = Game::new(&dict, "champ").unwrap().with_max_guesses(2);
+    game.guess("batch").unwrap();
+    game.guess("catch").unwrap();
+    assert_eq!(game.status(), GameStatus::Lost);
+    assert!(matches!(game.guess("champ"), Err(GameError::GameOver)));
+
+    let mut game = Game::new(&dict, "hatch").unwrap().with_hard_mode(true);
+    assert_eq!((game.score(), game.share_grid("Wordle 1"), game.summary()), (None, None, None));
+    game.guess("chimp").unwrap();
+    assert!(matches!(game.guess("lower"), Err(GameError::HardModeViolation { word: "lower", previous }) if previous == "chimp"));
+    game.guess("catch").unwrap();
+    game.guess("hatch").unwrap();
+    assert_eq!(game.score().as_deref(), Some("3/6*"));
+    assert_eq!(game.share_grid("Wordle 1").unwrap(), "Wordle 1 3/6*\n\n🟨🟨⬛⬛⬛\n⬛🟩🟩🟩🟩\n🟩🟩🟩🟩🟩");
+    assert_eq!(parse_share_grid(&game.share_grid("Wordle 1").unwrap()).len(), 3);
+    assert_eq!(game.summary().unwrap(), "solved in 3 of 6 guesses (hard mode)\n1: 0 correct, 2 present\n2: 4 correct, 0 present\n3: all correct");
+
+    let mut game = Game::new(&dict, "champ").unwrap().with_max_guesses(2);
+    game.guess("lower").unwrap();
+    game.guess("latch").unwrap();
+    assert_eq!(game.share_grid("Test").unwrap(), "Test X/2\n\n⬛⬛⬛⬛⬛\n⬛🟨⬛🟨🟨");
+    assert_eq!(game.summary().unwrap(), "not solved in 2 guesses\n1: 0 correct, 0 present\n2: 0 correct, 3 present");
+
+    let a = Game::daily(&dict, 19000);
+    assert_eq!(a.answer(), Game::daily(&dict, 19000).answer());
+    assert!(dict.contains(a.answer()));
+    let answers: std::collections::BTreeSet<_> = (0..50).map(|d| Game::daily(&dict, d).answer().to_owned()).collect();
+    assert!(answers.len() > 1);
+}
+
+
+#[test]
+fn test_postmortem() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "lower"])).unwrap();
+    let mut game = Game::new(&dict, "watch").unwrap().with_max_guesses(4);
+    for guess in ["blimp", "catch", "hatch", "watch"] { game.guess(guess).unwrap(); }
+    let report = game.postmortem(1, 3).unwrap();
+    assert_eq!(report.iter().map(|x| x.guess.as_str()).collect::<Vec<_>>(), &["blimp", "catch", "hatch", "watch"]);
+    assert_eq!(report.iter().map(|x| x.remaining).collect::<Vec<_>>(), &[3, 2, 1, 1]);
+
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_max_guesses(4);
+    let expected: Vec<_> = puzzle.rank_guesses(1, |w: &str| w != "blimp", 3).unwrap().into_iter().map(|x| x.word).collect();
+    assert_eq!(report[0].alternatives.iter().map(|x| x.guess.clone()).collect::<Vec<_>>(), expected);
+    for (turn, entry) in report.iter().enumerate() {
+        assert!(entry.alternatives.len() <= 3 && entry.alternatives.iter().all(|x| x.guess != entry.guess));
+        for line in entry.alternatives.iter() {
+            assert_eq!(line.steps[0].0, line.guess);
+            assert!(turn + line.steps.len() <= 4);
+            assert!(line.steps.windows(2).all(|x| x[1].1 <= x[0].1));
+            assert_eq!(line.won, line.steps.last().unwrap().0 == "watch");
+            assert!(!line.won || line.steps.last().unwrap().1 == 1);
+        }
+    }
+    // the answer is known after "hatch", so every alternative to guessing it on the last turn loses
+    assert!(report[3].alternatives.iter().all(|x| !x.won && x.steps.len() == 1));
+
+    let mut hard = Game::new(&dict, "watch").unwrap().with_hard_mode(true);
+    hard.guess("latch").unwrap();
+    hard.guess("watch").unwrap();
+    let report = hard.postmortem(1, 10).unwrap();
+    assert!(report[1].alternatives.iter().all(|x| x.guess.ends_with("atch")));
+}
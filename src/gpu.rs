@@ -0,0 +1,191 @@
+use std::fmt;
+use std::sync::mpsc;
+
+use wgpu::util::DeviceExt;
+
+use crate::*;
+
+/// A problem with setting up or using a [`GpuPartitioner`].
+#[derive(Debug)]
+pub enum GpuError {
+    /// There is no GPU (or software adapter) to run on.
+    NoAdapter,
+    /// The GPU could not be set up for computation.
+    Device(wgpu::RequestDeviceError),
+    /// The words are longer than [`GpuPartitioner::MAX_WORD_LEN`].
+    WordTooLong { word_len: usize },
+    /// There are more guesses than the GPU can take in one dispatch, or their counts don't fit in one buffer.
+    TooLarge { guesses: usize, answers: usize },
+    /// The counts could not be read back from the GPU.
+    Readback(wgpu::BufferAsyncError),
+    /// The GPU stopped (e.g., the device was lost) before reporting whether the counts could be read back.
+    Lost,
+    /// The puzzle can't be solved, for the same reasons as on the CPU.
+    Solve(SolveErr),
+}
+impl fmt::Display for GpuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GpuError::NoAdapter => write!(f, "no GPU adapter is available"),
+            GpuError::Device(e) => write!(f, "failed to set up the GPU: {}", e),
+            GpuError::WordTooLong { word_len } => write!(f, "words of {} letters are too long for the GPU (at most {})", word_len, GpuPartitioner::MAX_WORD_LEN),
+            GpuError::TooLarge { guesses, answers } => write!(f, "{} guesses and {} answers are too many for the GPU", guesses, answers),
+            GpuError::Readback(e) => write!(f, "failed to read the results back from the GPU: {}", e),
+            GpuError::Lost => write!(f, "the GPU stopped before the results were read back"),
+            GpuError::Solve(SolveErr::Inconsistent) => write!(f, "no word is consistent with the hints"),
+            GpuError::Solve(SolveErr::NoAdmissibleGuess) => write!(f, "no word in the word list can be guessed"),
+            GpuError::Solve(SolveErr::InvalidExtraGuess(word)) => write!(f, "invalid extra guess '{}'", word),
+            GpuError::Solve(SolveErr::TooManyAnswers { answers, max }) => write!(f, "{} words could be the answer, but at most {} are supported", answers, max),
+        }
+    }
+}
+
+/// A GPU set up to compute hints and partition counts for many `(guess, answer)` pairs at once with a compute shader
+/// (see [`Puzzle::partition_counts_gpu`] and [`Puzzle::rank_guesses_gpu`]).
+/// Creating one is slow, so it should be reused for many queries.
+///
+/// This is experimental: the results are exactly the same as on the CPU, but it has not been benchmarked against the CPU search,
+/// which prunes guesses that can't beat the best so far, so it is not necessarily faster.
+pub struct GpuPartitioner {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+impl GpuPartitioner {
+    /// The longest word length supported, which keeps the count buffer (one counter per guess and hint) small.
+    pub const MAX_WORD_LEN: usize = 5;
+    const WORKGROUP_SIZE: usize = 64;
+
+    /// Sets up the default GPU, failing with [`GpuError::NoAdapter`] if there is none.
+    pub fn new() -> Result<Self, GpuError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle());
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default())).map_err(|_| GpuError::NoAdapter)?;
+        let (device, queue) = pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default())).map_err(GpuError::Device)?;
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("partition"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("partition.wgsl").into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("partition"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+        Ok(GpuPartitioner { device, queue, pipeline })
+    }
+    /// Counts the answers which give each hint for every guess, as a row-major matrix with one row of `3^word_len` counts per guess (indexed by [`HintPattern::code`]).
+    fn counts(&self, guesses: &[Word], answers: &[Word], word_len: usize) -> Result<Vec<u32>, GpuError> {
+        if word_len > Self::MAX_WORD_LEN {
+            return Err(GpuError::WordTooLong { word_len });
+        }
+        let buckets = 3usize.pow(word_len as u32);
+        if guesses.is_empty() || answers.is_empty() {
+            return Ok(vec![0; guesses.len() * buckets]);
+        }
+        let limits = self.device.limits();
+        let counts_size = (guesses.len() * buckets * 4) as u64;
+        if guesses.len() > limits.max_compute_workgroups_per_dimension as usize || counts_size > limits.max_storage_buffer_binding_size {
+            return Err(GpuError::TooLarge { guesses: guesses.len(), answers: answers.len() });
+        }
+
+        let to_bytes = |words: &[Word]| -> Vec<u8> { words.iter().flat_map(|w| w.iter().flat_map(|&x| (x as u32).to_le_bytes())).collect() };
+        let storage = |label, contents: &[u8]| self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor { label: Some(label), contents, usage: wgpu::BufferUsages::STORAGE });
+        let params: Vec<u8> = [guesses.len(), answers.len(), word_len, buckets].iter().flat_map(|&x| (x as u32).to_le_bytes()).collect();
+        let params = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor { label: Some("params"), contents: &params, usage: wgpu::BufferUsages::UNIFORM });
+        let guesses_buf = storage("guesses", &to_bytes(guesses));
+        let answers_buf = storage("answers", &to_bytes(answers));
+        let counts = self.device.create_buffer(&wgpu::BufferDescriptor { label: Some("counts"), size: counts_size, usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC, mapped_at_creation: false });
+        let readback = self.device.create_buffer(&wgpu::BufferDescriptor { label: Some("readback"), size: counts_size, usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST, mapped_at_creation: false });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("partition"),
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: guesses_buf.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: answers_buf.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: counts.as_entire_binding() },
+            ],
+        });
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("partition") });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor { label: Some("partition"), timestamp_writes: None });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(answers.len().div_ceil(Self::WORKGROUP_SIZE) as u32, guesses.len() as u32, 1);
+        }
+        encoder.copy_buffer_to_buffer(&counts, 0, &readback, 0, counts_size);
+        self.queue.submit([encoder.finish()]);
+
+        let (tx, rx) = mpsc::channel();
+        readback.slice(..).map_async(wgpu::MapMode::Read, move |res| { let _ = tx.send(res); });
+        self.device.poll(wgpu::PollType::wait_indefinitely()).ok();
+        rx.recv().map_err(|_| GpuError::Lost)?.map_err(GpuError::Readback)?;
+        let res = readback.slice(..).get_mapped_range().chunks_exact(4).map(|x| u32::from_le_bytes(x.try_into().unwrap())).collect();
+        readback.unmap();
+        Ok(res)
+    }
+}
+
+impl<'a> Puzzle<'a> {
+    /// For each word in the dictionary (in order) as a guess, counts the words which could be the answer that would give each hint,
+    /// indexed by [`HintPattern::code`] (so each row has `3^word_len` entries and sums to the number of [`Puzzle::candidates`]).
+    /// This is the same information as [`BestGuess::partition`] for every guess at once, computed on the GPU.
+    pub fn partition_counts_gpu(&self, gpu: &GpuPartitioner) -> Result<Vec<Vec<u32>>, GpuError> {
+        let this = self.reduced();
+        let buckets = 3usize.pow(this.slots.len() as u32);
+        let counts = gpu.counts(&this.all_words, &this.feasible_words, this.slots.len())?;
+        Ok(counts.chunks(buckets.max(1)).map(<[u32]>::to_vec).collect())
+    }
+    /// Equivalent to [`Puzzle::rank_guesses`], but scores every admitted guess on the GPU (see [`GpuPartitioner`]).
+    /// This gives the same results, but all guesses are scored in full, so it is only faster when the GPU more than makes up for the lack of pruning.
+    pub fn rank_guesses_gpu<F: CandidateFilter>(&self, gpu: &GpuPartitioner, filter: F, n: usize) -> Result<Vec<RankedGuess>, GpuError> {
+        let this = self.reduced();
+        if this.slots.iter().any(BitSet32::is_empty) || this.feasible_words.is_empty() {
+            return Err(GpuError::Solve(SolveErr::Inconsistent));
+        }
+        let admissible: Vec<_> = this.all_words.iter().copied().filter(|w| filter.admits(&this.decode(w))).collect();
+        if admissible.is_empty() {
+            return Err(GpuError::Solve(SolveErr::NoAdmissibleGuess));
+        }
+        let buckets = 3usize.pow(this.slots.len() as u32);
+        let counts = gpu.counts(&admissible, &this.feasible_words, this.slots.len())?;
+        let mut ranked: Vec<_> = iter::zip(&admissible, counts.chunks(buckets)).map(|(&guess, row)| {
+            let worst_case = row.iter().copied().max().unwrap_or(0) as u64;
+            let groups = row.iter().filter(|&&x| x != 0).count() as u64;
+            ((worst_case, Average::new(this.feasible_words.len() as u64, groups)), !this.is_feasible(guess), guess)
+        }).collect();
+        ranked.sort_unstable();
+        Ok(ranked.into_iter().take(n).map(|((worst_case, average), infeasible, guess)| {
+            RankedGuess { word: this.decode(&guess), worst_case, average, feasible: !infeasible }
+        }).collect())
+    }
+}
+
+#[test]
+#[ignore = "needs a GPU (or a software adapter), run with --ignored"]
+fn test_gpu() {
+    let gpu = GpuPartitioner::new().unwrap_or_else(|e| panic!("{}", e));
+    let dict = Dictionary::with_words(5, testkit::MINI_WORDS.iter().copied().chain(testkit::DUPLICATE_WORDS.iter().copied())).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    assert_eq!(puzzle.rank_guesses_gpu(&gpu, AnyWord, 10).unwrap(), puzzle.rank_guesses(2, AnyWord, 10).unwrap());
+    puzzle.guess("crane", &get_hint("crane", "three").unwrap()).unwrap();
+    assert_eq!(puzzle.rank_guesses_gpu(&gpu, AnyWord, usize::MAX).unwrap(), puzzle.rank_guesses(2, AnyWord, usize::MAX).unwrap());
+
+    let counts = puzzle.partition_counts_gpu(&gpu).unwrap();
+    let best = puzzle.best_guess(1, AnyWord).unwrap();
+    let row = &counts[dict.words().position(|w| w == best.word).unwrap()];
+    for &(pattern, count) in best.partition.iter() {
+        assert_eq!(row[pattern.code() as usize] as u64, count);
+    }
+    assert_eq!(row.iter().sum::<u32>() as usize, puzzle.candidates().len());
+    assert!(matches!(Puzzle::new(&Dictionary::with_words(6, ["abcdef"]).unwrap()).partition_counts_gpu(&gpu), Err(GpuError::WordTooLong { word_len: 6 })));
+}
+
+#[test]
+fn test_gpu_error() {
+    assert_eq!(GpuError::WordTooLong { word_len: 6 }.to_string(), "words of 6 letters are too long for the GPU (at most 5)");
+    assert_eq!(GpuError::Solve(SolveErr::InvalidExtraGuess("h4tch".into())).to_string(), "invalid extra guess 'h4tch'");
+}
@@ -0,0 +1,63 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::*;
+
+impl Puzzle<'_> {
+    /// Exports the game tree rooted at the current state in Graphviz DOT format.
+    /// Each node is a solver state labeled with the number of words which could still be the answer (and the best guess, if expanded),
+    /// and each edge is a hint pattern (as `c`/`p`/`a` codes) which could be received after making that guess.
+    /// The tree is expanded at most `depth` guesses deep, and states with a single remaining word are not expanded.
+    /// The `threads` input is forwarded to [`Puzzle::best_guess`].
+    pub fn to_dot(&self, depth: usize, threads: usize) -> Result<String, SolveErr> {
+        let mut res = String::from("digraph {\n");
+        let mut next_id = 0;
+        self.reduced().dot_node(&mut res, &mut next_id, depth, threads)?;
+        res.push_str("}\n");
+        Ok(res)
+    }
+    fn dot_node(&self, out: &mut String, next_id: &mut usize, depth: usize, threads: usize) -> Result<usize, SolveErr> {
+        let id = *next_id;
+        *next_id += 1;
+
+        let feasible = self.feasible_words.len();
+        if feasible == 1 {
+            writeln!(out, "    n{} [label=\"{}\"];", id, self.decode(&self.feasible_words[0])).unwrap();
+            return Ok(id);
+        }
+        if depth == 0 || feasible == 0 {
+            writeln!(out, "    n{} [label=\"{} words\"];", id, feasible).unwrap();
+            return Ok(id);
+        }
+
+        let guess = self.best_guess(threads, AnyWord)?.word;
+        writeln!(out, "    n{} [label=\"{} words\\nguess: {}\"];", id, feasible, guess).unwrap();
+
+        let guess = self.encode(&guess).unwrap();
+        let patterns: BTreeSet<_> = self.feasible_words.iter().map(|&w| hint_impl(&guess, &w)).collect();
+        for pattern in patterns {
+            let mut child = self.clone();
+            child.guess_impl(guess.as_ref(), &pattern);
+            let child_id = child.dot_node(out, next_id, depth - 1, threads)?;
+            let label: String = pattern.iter().map(|h| h.to_char()).collect();
+            writeln!(out, "    n{} -> n{} [label=\"{}\"];", id, child_id, label).unwrap();
+        }
+        Ok(id)
+    }
+}
+
+#[test]
+fn test_to_dot() {
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "lower", "chimp"]).unwrap();
+    let puzzle = Puzzle::new(&dict);
+
+    let shallow = puzzle.to_dot(0, 1).unwrap();
+    assert_eq!(shallow, "digraph {\n    n0 [label=\"5 words\"];\n}\n");
+
+    let deep = puzzle.to_dot(5, 1).unwrap();
+    assert!(deep.starts_with("digraph {\n    n0 [label=\"5 words\\nguess: "));
+    for word in ["batch", "catch", "hatch", "lower", "chimp"] {
+        assert!(deep.contains(&format!("[label=\"{}\"]", word)), "{}", deep);
+    }
+    assert!(deep.contains("-> n1 [label=\""));
+}
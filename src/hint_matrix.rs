@@ -0,0 +1,178 @@
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::*;
+
+const MAGIC: &[u8; 4] = b"WSHM";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 24;
+
+#[derive(Debug)]
+pub enum HintMatrixError {
+    Io(io::Error),
+    WordTooLong { word_len: usize },
+    BadHeader,
+    FingerprintMismatch { expected: u64, found: u64 },
+    WrongSize { expected: usize, found: usize },
+}
+impl From<io::Error> for HintMatrixError {
+    fn from(e: io::Error) -> Self {
+        HintMatrixError::Io(e)
+    }
+}
+
+enum Storage {
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+}
+
+/// The precomputed hint for every `(guess, answer)` pair of words in a [`Dictionary`], stored as one byte per pair.
+/// Each byte is the base-3 code of the hint (absent is `0`, present is `1`, correct is `2`, and the first slot is the least significant digit),
+/// so only dictionaries with words of at most 5 characters are supported.
+///
+/// Because this is expensive to compute for large dictionaries, it can be saved to disk and memory-mapped back in later.
+/// The file format is a 24-byte header (magic `WSHM`, version byte, word length byte, two reserved bytes,
+/// little-endian `u32` row and column counts, and the little-endian `u64` [`Dictionary::fingerprint`])
+/// followed by the row-major hint codes, with one row per guess in dictionary order.
+pub struct HintMatrix {
+    storage: Storage,
+    size: usize,
+}
+impl HintMatrix {
+    /// The longest word length which can be stored in a hint matrix.
+    pub const MAX_WORD_LEN: usize = 5;
+
+    /// Computes the hint matrix for the dictionary, using the given number of threads (minimum `1`).
+    pub fn compute(dictionary: &Dictionary, threads: usize) -> Result<Self, HintMatrixError> {
+        if dictionary.word_len > Self::MAX_WORD_LEN {
+            return Err(HintMatrixError::WordTooLong { word_len: dictionary.word_len });
+        }
+
+        let words = dictionary.to_words();
+        let size = words.len();
+        let mut data = vec![0u8; size * size];
+        if size != 0 {
+            let rows_per_thread = size.div_ceil(threads.max(1));
+            crossbeam::scope(|scope| {
+                for (i, chunk) in data.chunks_mut(rows_per_thread * size).enumerate() {
+                    let words = &words;
+                    scope.spawn(move |_| {
+                        for (j, row) in chunk.chunks_exact_mut(size).enumerate() {
+                            let guess = words[i * rows_per_thread + j];
+                            for (code, answer) in iter::zip(row, words.iter()) {
+                                *code = hint_code(&guess, answer) as u8;
+                            }
+                        }
+                    });
+                }
+            }).unwrap();
+        }
+
+        Ok(HintMatrix { storage: Storage::Owned(data), size })
+    }
+    /// Gets the number of words (rows and columns) in the matrix.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    fn codes(&self) -> &[u8] {
+        match &self.storage {
+            Storage::Owned(x) => x,
+            Storage::Mapped(x) => &x[HEADER_LEN..],
+        }
+    }
+    /// Gets the hint code for the given guess and answer, which are indices into the dictionary's (sorted) words.
+    /// Panics if either index is out of bounds.
+    pub fn code(&self, guess: usize, answer: usize) -> u8 {
+        assert!(guess < self.size && answer < self.size);
+        self.codes()[guess * self.size + answer]
+    }
+    /// Gets all the hint codes for the given guess, indexed by answer.
+    pub fn row(&self, guess: usize) -> &[u8] {
+        &self.codes()[guess * self.size..(guess + 1) * self.size]
+    }
+
+    /// Writes the matrix for the given dictionary to a file.
+    pub fn save<P: AsRef<Path>>(&self, dictionary: &Dictionary, path: P) -> Result<(), HintMatrixError> {
+        if dictionary.len() != self.size {
+            return Err(HintMatrixError::WrongSize { expected: dictionary.len(), found: self.size });
+        }
+        let mut f = io::BufWriter::new(File::create(path)?);
+        f.write_all(MAGIC)?;
+        f.write_all(&[VERSION, dictionary.word_len as u8, 0, 0])?;
+        f.write_all(&(self.size as u32).to_le_bytes())?;
+        f.write_all(&(self.size as u32).to_le_bytes())?;
+        f.write_all(&dictionary.fingerprint().to_le_bytes())?;
+        f.write_all(self.codes())?;
+        f.flush()?;
+        Ok(())
+    }
+    /// Memory-maps a matrix previously written by [`HintMatrix::save`], checking that it was computed for the given dictionary.
+    pub fn load<P: AsRef<Path>>(dictionary: &Dictionary, path: P) -> Result<Self, HintMatrixError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only, and the file format is fully validated below before use
+        let map = unsafe { Mmap::map(&file)? };
+
+        if map.len() < HEADER_LEN || &map[..4] != MAGIC || map[4] != VERSION {
+            return Err(HintMatrixError::BadHeader);
+        }
+        let word_len = map[5] as usize;
+        let rows = u32::from_le_bytes(map[8..12].try_into().unwrap()) as usize;
+        let cols = u32::from_le_bytes(map[12..16].try_into().unwrap()) as usize;
+        let fingerprint = u64::from_le_bytes(map[16..24].try_into().unwrap());
+
+        if rows != cols || word_len != dictionary.word_len {
+            return Err(HintMatrixError::BadHeader);
+        }
+        if fingerprint != dictionary.fingerprint() {
+            return Err(HintMatrixError::FingerprintMismatch { expected: dictionary.fingerprint(), found: fingerprint });
+        }
+        if rows != dictionary.len() || map.len() - HEADER_LEN != rows * cols {
+            return Err(HintMatrixError::WrongSize { expected: HEADER_LEN + dictionary.len() * dictionary.len(), found: map.len() });
+        }
+
+        Ok(HintMatrix { storage: Storage::Mapped(map), size: rows })
+    }
+}
+
+#[test]
+fn test_hint_matrix() {
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "chimp", "champ", "lower", "geese", "eerie"]).unwrap();
+    let matrix = HintMatrix::compute(&dict, 3).unwrap();
+    assert_eq!(matrix.size(), dict.len());
+
+    let words: Vec<_> = dict.words().collect();
+    for (i, guess) in words.iter().enumerate() {
+        for (j, answer) in words.iter().enumerate() {
+            let expected = get_hint(guess, answer).unwrap().iter().rev().fold(0, |acc, h| acc * 3 + match h {
+                Hint::Absent => 0,
+                Hint::Present => 1,
+                Hint::Correct => 2,
+            });
+            assert_eq!(matrix.code(i, j), expected);
+            assert_eq!(matrix.row(i)[j], expected);
+        }
+    }
+    assert_eq!(matrix.code(0, 0), 242);
+
+    let path = std::env::temp_dir().join(format!("wordle-solver-test-{}.hints", std::process::id()));
+    matrix.save(&dict, &path).unwrap();
+    let loaded = HintMatrix::load(&dict, &path).unwrap();
+    assert_eq!(loaded.size(), matrix.size());
+    assert_eq!(loaded.codes(), matrix.codes());
+
+    let other = Dictionary::with_words(5, ["batch", "catch", "hatch", "chimp", "champ", "lower", "geese", "eeris"]).unwrap();
+    assert!(matches!(HintMatrix::load(&other, &path), Err(HintMatrixError::FingerprintMismatch { .. })));
+    let other = Dictionary::with_words(5, ["batch"]).unwrap();
+    assert!(matches!(matrix.save(&other, &path), Err(HintMatrixError::WrongSize { .. })));
+
+    std::fs::write(&path, b"WSHM").unwrap();
+    assert!(matches!(HintMatrix::load(&dict, &path), Err(HintMatrixError::BadHeader)));
+    std::fs::remove_file(&path).unwrap();
+
+    let long = Dictionary::with_words(6, ["abcdef"]).unwrap();
+    assert!(matches!(HintMatrix::compute(&long, 1), Err(HintMatrixError::WordTooLong { word_len: 6 })));
+    assert_eq!(HintMatrix::compute(&Dictionary::with_words(5, []).unwrap(), 2).unwrap().size(), 0);
+}
@@ -0,0 +1,115 @@
+use crate::*;
+
+#[derive(Debug)]
+pub enum InferError<'a> {
+    InvalidAnswer(WordError<'a>),
+    WrongHintLen { row: usize, expected_len: usize },
+}
+
+/// Parses the rows of an emoji share grid (as copied from the game) into hints.
+/// Lines which are not made up entirely of hint emoji (e.g., the title line) are ignored.
+pub fn parse_share_grid(text: &str) -> Vec<Vec<Hint>> {
+    text.lines().filter_map(|line| {
+        let row: Option<Vec<_>> = line.chars().filter(|&c| !c.is_whitespace() && c != '\u{fe0f}').map(Hint::from_emoji).collect();
+        row.filter(|r| !r.is_empty())
+    }).collect()
+}
+
+/// Checks if `word` reuses the letters revealed by guessing `prev` (greens in place and yellows anywhere), as required by hard mode.
+pub(crate) fn obeys_hard_mode(word: &[u8], prev: &[u8], prev_hint: &[Hint]) -> bool {
+    let mut required = [0u8; Alphabet::MAX_LEN];
+    for ((&w, &p), &h) in iter::zip(iter::zip(word, prev), prev_hint) {
+        match h {
+            Hint::Correct if w != p => return false,
+            Hint::Correct | Hint::Present => required[p as usize] += 1,
+            Hint::Absent => (),
+        }
+    }
+    for &w in word {
+        required[w as usize] = required[w as usize].saturating_sub(1);
+    }
+    required.iter().all(|&x| x == 0)
+}
+
+/// Enumerates the sequences of dictionary words which, guessed in order against the given `answer`, produce exactly the rows of `grid`.
+/// The guesses in a sequence are distinct, and only the final row may be the answer.
+/// If `hard_mode` is set, each guess must also reuse the letters revealed by all previous rows (greens in place and yellows anywhere).
+/// At most `limit` sequences are returned, in lexicographic order.
+pub fn infer_guesses<'a, 'b>(grid: &[Vec<Hint>], answer: &'b str, dictionary: &'a Dictionary, hard_mode: bool, limit: usize) -> Result<Vec<Vec<&'a str>>, InferError<'b>> {
+    let answer = dictionary.alphabet.encode(dictionary.word_len, answer).map_err(InferError::InvalidAnswer)?;
+    if let Some(row) = grid.iter().position(|h| h.len() != dictionary.word_len) {
+        return Err(InferError::WrongHintLen { row, expected_len: dictionary.word_len });
+    }
+
+    // only the last row may be all correct, and if it's not then the game isn't over, which is fine
+    if grid.iter().rev().skip(1).any(|h| h.iter().all(|&x| x == Hint::Correct)) {
+        return Ok(vec![]);
+    }
+
+    let words: Vec<_> = iter::zip(dictionary.to_words(), dictionary.words()).collect();
+    let mut candidates = Vec::with_capacity(grid.len());
+    for hint in grid {
+        let row: Vec<_> = words.iter().copied().filter(|(w, _)| hint_impl(w, &answer) == *hint).collect();
+        if row.is_empty() { return Ok(vec![]); } // prune early if any row is impossible
+        candidates.push(row);
+    }
+
+    fn search<'a>(candidates: &[Vec<(Word<'a>, &'a str)>], grid: &[Vec<Hint>], hard_mode: bool, limit: usize, path: &mut Vec<(Word<'a>, &'a str)>, res: &mut Vec<Vec<&'a str>>) {
+        let row = path.len();
+        if row == candidates.len() {
+            res.push(path.iter().map(|x| x.1).collect());
+            return;
+        }
+        for &(word, text) in candidates[row].iter() {
+            if res.len() >= limit { return; }
+            if path.iter().any(|x| x.0 == word) { continue; }
+            if hard_mode && !iter::zip(path.iter(), grid).all(|(prev, hint)| obeys_hard_mode(&word, &prev.0, hint)) { continue; }
+
+            path.push((word, text));
+            search(candidates, grid, hard_mode, limit, path, res);
+            path.pop();
+        }
+    }
+
+    let mut res = vec![];
+    search(&candidates, grid, hard_mode, limit, &mut Vec::with_capacity(grid.len()), &mut res);
+    Ok(res)
+}
+
+#[test]
+fn test_parse_share_grid() {
+    use Hint::{Absent as A, Correct as C, Present as P};
+
+    let grid = parse_share_grid("Wordle 254 3/6\n\n⬛🟨⬛⬛⬛\n⬜️🟩🟨⬜️⬜️\n🟩🟩🟩🟩🟩\n");
+    assert_eq!(grid, vec![vec![A, P, A, A, A], vec![A, C, P, A, A], vec![C, C, C, C, C]]);
+    assert_eq!(parse_share_grid("🟧🟦⬛\nnot a row 🟩\n"), vec![vec![C, P, A]]);
+    assert_eq!(parse_share_grid(""), Vec::<Vec<Hint>>::new());
+}
+
+#[test]
+fn test_infer_guesses() {
+    use Hint::{Absent as A, Correct as C, Present as P};
+
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "match", "chimp", "champ", "lower"]).unwrap();
+    let grid = [vec![A, C, C, C, C], vec![C, C, C, C, C]];
+    assert_eq!(infer_guesses(&grid, "hatch", &dict, false, 100).unwrap(), vec![
+        vec!["batch", "hatch"], vec!["catch", "hatch"], vec!["latch", "hatch"], vec!["match", "hatch"],
+    ]);
+    assert_eq!(infer_guesses(&grid, "hatch", &dict, false, 2).unwrap().len(), 2);
+
+    let grid = [vec![A, A, A, A, A], vec![A, C, C, C, C], vec![A, C, C, C, C], vec![C, C, C, C, C]];
+    let normal = infer_guesses(&grid, "hatch", &dict, false, 100).unwrap();
+    assert_eq!(normal.len(), 12);
+    assert!(normal.iter().all(|x| x[0] == "lower" && x[1] != x[2]));
+    let hard = infer_guesses(&grid, "hatch", &dict, true, 100).unwrap();
+    assert_eq!(hard, normal);
+
+    let grid: Vec<_> = ["hatch", "chimp", "champ"].iter().map(|g| get_hint(g, "champ").unwrap()).collect();
+    assert_eq!(grid, vec![vec![P, P, A, P, A], vec![C, C, A, C, C], vec![C, C, C, C, C]]);
+    assert_eq!(infer_guesses(&grid, "champ", &dict, false, 100).unwrap(), vec![vec!["hatch", "chimp", "champ"]]);
+    assert_eq!(infer_guesses(&grid, "champ", &dict, true, 100).unwrap(), Vec::<Vec<&str>>::new()); // chimp drops the revealed 'a'
+
+    assert_eq!(infer_guesses(&[vec![C, C, C, C, C], vec![C, C, C, C, C]], "champ", &dict, false, 100).unwrap(), Vec::<Vec<&str>>::new());
+    assert!(matches!(infer_guesses(&[vec![C, C, C, C]], "champ", &dict, false, 100), Err(InferError::WrongHintLen { row: 0, expected_len: 5 })));
+    assert!(matches!(infer_guesses(&[vec![C, C, C, C, C]], "champs", &dict, false, 100), Err(InferError::InvalidAnswer(_))));
+}
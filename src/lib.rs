@@ -1,14 +1,154 @@
-use std::collections::BTreeSet;
 use std::{iter, fmt};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Instant;
 use std::ops::Deref;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::cmp::Reverse;
 
 use itertools::Itertools;
-use float_ord::FloatOrd;
 
 mod bit_set;
 use bit_set::BitSet32;
 
+mod alphabet;
+pub use alphabet::*;
+
+mod average;
+pub use average::*;
+
+mod rng;
+pub use rng::*;
+
+mod graphviz;
+
+mod infer;
+pub use infer::*;
+
+mod game;
+pub use game::*;
+
+mod hint_matrix;
+pub use hint_matrix::*;
+
+mod swap;
+pub use swap::*;
+
+mod pattern;
+pub use pattern::*;
+
+mod prior;
+pub use prior::*;
+
+mod trace;
+pub use trace::*;
+
+mod probe;
+pub use probe::*;
+
+mod endgame;
+
+mod threads;
+pub use threads::*;
+
+mod stats;
+pub use stats::*;
+
+mod nearest;
+
+mod filter;
+
+mod profile;
+pub use profile::*;
+
+mod progress;
+pub use progress::*;
+
+mod cache;
+pub use cache::*;
+
+mod plot;
+pub use plot::*;
+
+mod risk;
+pub use risk::*;
+
+mod meta;
+pub use meta::*;
+
+mod define;
+pub use define::*;
+
+mod certificate;
+pub use certificate::*;
+
+mod listing;
+pub use listing::*;
+
+mod opening;
+
+mod answers;
+pub use answers::*;
+
+mod options;
+pub use options::*;
+
+mod preset;
+pub use preset::*;
+
+mod entropy;
+pub use entropy::*;
+
+mod scorer;
+pub use scorer::*;
+
+mod lookahead;
+pub use lookahead::*;
+
+mod exact;
+pub use exact::*;
+
+mod decision_tree;
+pub use decision_tree::*;
+
+mod weighted;
+pub use weighted::*;
+
+mod bench;
+pub use bench::*;
+
+mod dictionary_set;
+pub use dictionary_set::*;
+
+mod setter;
+pub use setter::*;
+
+mod source;
+pub use source::*;
+
+#[cfg(feature = "yaml")]
+mod yaml;
+#[cfg(feature = "yaml")]
+pub use yaml::*;
+
+#[cfg(feature = "gpu")]
+mod gpu;
+#[cfg(feature = "gpu")]
+pub use gpu::*;
+
+#[cfg(feature = "scripting")]
+mod script;
+#[cfg(feature = "scripting")]
+pub use script::*;
+
+#[cfg(feature = "sqlite")]
+mod sqlite;
+#[cfg(feature = "sqlite")]
+pub use sqlite::*;
+
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
+
 #[cfg(test)]
 use proptest::prelude::*;
 
@@ -16,67 +156,361 @@ use proptest::prelude::*;
 pub enum GuessError<'a> {
     WrongHintLen { hint: &'a [Hint], expected_len: usize },
     WrongWordLen { word: &'a str, expected_len: usize },
-    NotLowerAlpha { word: &'a str },
+    NotInAlphabet { word: &'a str },
+    /// No answer could produce the hint for the word, so it was probably mistyped.
+    /// `slot` is a [`Hint::Present`] which can't hold: either an earlier copy of the same letter is [`Hint::Absent`]
+    /// (extra copies are marked present from left to right), or there are not enough other slots for the present copies of the letter to be in.
+    ContradictoryHint { word: &'a str, hint: &'a [Hint], slot: usize },
+}
+/// An invalid entry in a history of guesses, as reported by [`Dictionary::validate_history`].
+#[derive(Debug)]
+pub struct HistoryError<'a> {
+    /// The position of the entry in the history (from `0`).
+    pub turn: usize,
+    /// Why the entry is invalid, as reported by [`Dictionary::validate_guess`].
+    pub error: GuessError<'a>,
+}
+impl fmt::Display for HistoryError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "guess {}: ", self.turn + 1)?;
+        match &self.error {
+            GuessError::WrongHintLen { hint, expected_len } => write!(f, "the hint has {} letters, but words have {}", hint.len(), expected_len),
+            GuessError::WrongWordLen { word, expected_len } => write!(f, "'{}' has {} letters, but words have {}", word, word.chars().count(), expected_len),
+            GuessError::NotInAlphabet { word } => write!(f, "'{}' has letters outside the alphabet", word),
+            GuessError::ContradictoryHint { word, slot, .. } => write!(f, "no answer could give this hint for '{}' (letter {} can't be present)", word, slot + 1),
+        }
+    }
+}
+/// A problem with a history entry which doesn't prevent it from being applied, as reported by [`Puzzle::apply_history_checked`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryWarning<'a> {
+    /// The guess at the given position in the history is not in the dictionary (e.g., it came from a clone with a bigger word list).
+    /// Its hint is still applied, since the constraints it reveals don't depend on the guess being a listed word.
+    UnknownWord { index: usize, word: &'a str },
 }
+
 impl<'a> From<WordError<'a>> for GuessError<'a> {
     fn from(e: WordError<'a>) -> Self {
         match e {
             WordError::WrongWordLen { word, expected_len } => GuessError::WrongWordLen { word, expected_len },
-            WordError::NotLowerAlpha { word } => GuessError::NotLowerAlpha { word },
+            WordError::NotInAlphabet { word } => GuessError::NotInAlphabet { word },
         }
     }
 }
 
+#[derive(Debug)]
+pub enum ConstraintError {
+    NotInAlphabet { letter: char },
+    SlotOutOfRange { slot: usize, word_len: usize },
+}
+
 #[derive(Debug)]
 pub enum WordError<'a> {
     WrongWordLen { word: &'a str, expected_len: usize },
-    NotLowerAlpha { word: &'a str },
+    NotInAlphabet { word: &'a str },
 }
 
 #[derive(Debug)]
 pub enum SolveErr {
-    Inconsistent
+    Inconsistent,
+    NoAdmissibleGuess,
+    /// An extra guess given to [`Puzzle::best_guess_with_extra`] is not a valid word for the dictionary.
+    InvalidExtraGuess(String),
+    /// Too many words could be the answer for [`Puzzle::best_guess_exact`], which is limited to [`Puzzle::MAX_EXACT_ANSWERS`].
+    TooManyAnswers { answers: usize, max: usize },
 }
 
-macro_rules! normalized_chars {
-    ($word:expr) => { $word.as_bytes().iter().map(|&x| x - 97) }
+/// The guess chosen by [`Puzzle::best_guess`], along with its scores.
+/// Equality ignores [`BestGuess::counters`], which depend on how the search was split among threads.
+#[derive(Debug, Clone)]
+pub struct BestGuess {
+    /// The word to guess.
+    pub word: String,
+    /// The worst-case number of remaining words after making the guess.
+    pub worst_case: u64,
+    /// The average-case number of remaining words after making the guess.
+    pub average: Average,
+    /// Whether the guess could itself be the answer.
+    pub feasible: bool,
+    /// Whether the guess was chosen by the exact endgame solver rather than by minimax.
+    pub endgame: bool,
+    /// The exact average number of guesses needed to solve the puzzle (including this one) when playing optimally, if computed by the endgame solver.
+    pub expected_guesses: Option<Average>,
+    /// The work done by the minimax search to find the guess (all zero if it was not needed).
+    pub counters: SearchCounters,
+    /// Whether the search was cut short by its [`ComputeLimits`], so the guess is only the best of those scored in time
+    /// (or, if none were, the best by letter coverage) rather than the best overall. The score is still exact for the chosen word.
+    pub approximate: bool,
+    /// The number of remaining words for each hint the guess could receive, largest first (ties in order of [`HintPattern::code`]).
+    /// Hints which no remaining word would produce are omitted, so the sizes sum to the number of remaining words.
+    pub partition: Vec<(HintPattern, u64)>,
 }
 
-fn check_word(expected_len: usize, word: &str) -> Result<(), WordError> {
-    if word.chars().any(|c| !('a'..='z').contains(&c)) {
-        return Err(WordError::NotLowerAlpha { word })
+impl PartialEq for BestGuess {
+    fn eq(&self, other: &Self) -> bool {
+        let Self { word, worst_case, average, feasible, endgame, expected_guesses, counters: _, approximate, partition } = self;
+        (word, worst_case, average, feasible, endgame, expected_guesses, approximate, partition)
+            == (&other.word, &other.worst_case, &other.average, &other.feasible, &other.endgame, &other.expected_guesses, &other.approximate, &other.partition)
     }
-    if word.len() != expected_len {
-        return Err(WordError::WrongWordLen { word, expected_len })
+}
+impl Eq for BestGuess {}
+
+/// Counts of the work done by [`Puzzle::best_guess`], summed over all threads.
+/// These depend on how the work was divided among threads (which affects pruning), so they are only comparable for a fixed thread count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchCounters {
+    /// The number of guesses that were scored (including those cut short).
+    pub evaluated: u64,
+    /// The number of hint patterns expanded while scoring, each of which clones and reduces the puzzle.
+    pub patterns: u64,
+    /// The number of guesses whose scoring was cut short because they could not beat the best so far.
+    pub prunes: u64,
+}
+impl std::ops::AddAssign for SearchCounters {
+    fn add_assign(&mut self, other: Self) {
+        self.evaluated += other.evaluated;
+        self.patterns += other.patterns;
+        self.prunes += other.prunes;
+    }
+}
+
+/// A guess ranked by [`Puzzle::rank_guesses`] (or scored by [`Puzzle::evaluate_guess`]), along with its scores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedGuess {
+    /// The word to guess.
+    pub word: String,
+    /// The worst-case number of remaining words after making the guess.
+    pub worst_case: u64,
+    /// The average-case number of remaining words after making the guess.
+    pub average: Average,
+    /// Whether the guess could itself be the answer.
+    pub feasible: bool,
+}
+
+/// The ranking of a guess by [`Puzzle::rank_guesses`], where smaller is better.
+type RankKey<'a> = ((u64, Average), bool, Word<'a>);
+
+/// A predicate restricting which words [`Puzzle::best_guess`] is allowed to suggest.
+/// Filters only affect the suggestions themselves; scoring is always performed over the true set of possible answers.
+/// Any `Fn(&str) -> bool` closure can be used as a filter, and [`AnyWord`] admits every word.
+pub trait CandidateFilter: Sync {
+    /// Returns `true` if `word` may be suggested as a guess.
+    fn admits(&self, word: &str) -> bool;
+}
+impl<F: Fn(&str) -> bool + Sync> CandidateFilter for F {
+    fn admits(&self, word: &str) -> bool {
+        self(word)
+    }
+}
+
+/// A [`CandidateFilter`] which admits every word in the dictionary.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnyWord;
+impl CandidateFilter for AnyWord {
+    fn admits(&self, _: &str) -> bool {
+        true
+    }
+}
+
+/// How far along a [`Puzzle`] is, as given by [`Puzzle::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleStatus {
+    /// More than one word could be the answer.
+    Unsolved,
+    /// Exactly one word could be the answer (see [`Puzzle::solution`]), though it may not have been guessed yet.
+    Solved,
+    /// No word is consistent with the hints.
+    Inconsistent,
+}
+
+/// A rule for choosing the next guess, as used by [`Puzzle::suggest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Strategy {
+    /// Always guess the given word (e.g., a fixed opener).
+    Fixed(String),
+    /// Use [`Puzzle::best_guess`] over the entire dictionary.
+    Minimax,
+    /// Use [`Puzzle::best_guess`], but only suggest words which could still be the answer (see [`Puzzle::feasible_filter`]).
+    FeasibleOnly,
+    /// Use [`Puzzle::best_guess_prob_left`] with a uniform prior, maximizing the probability that at most `k` words remain.
+    ProbTwoLeft { k: usize },
+    /// Use [`Puzzle::best_guess_entropy`], maximizing the expected information gained from the hint.
+    Entropy,
+    /// Use [`Puzzle::best_guess_lookahead`], minimizing the words left after `depth` guesses (considering the best `breadth` guesses at each step).
+    Lookahead { depth: usize, breadth: usize },
+    /// Use [`Puzzle::best_guess_exact`], minimizing the worst-case number of guesses needed to solve the puzzle (only practical once few words could be the answer).
+    Exact,
+    /// Use [`Puzzle::best_guess_weighted`] with a uniform prior, minimizing the expected number of words left after the guess.
+    ExpectedRemaining,
+}
+
+/// Selects the [`Strategy`] to use for each turn of a game played by [`play`].
+/// Any `Fn(usize, usize) -> Strategy` closure taking `(turn_index, feasible_count)` can be used as a policy.
+pub trait TurnPolicy {
+    /// Gets the strategy for the (zero-based) `turn` given the number of words which could still be the answer.
+    fn strategy(&self, turn: usize, feasible: usize) -> Strategy;
+}
+impl<F: Fn(usize, usize) -> Strategy> TurnPolicy for F {
+    fn strategy(&self, turn: usize, feasible: usize) -> Strategy {
+        self(turn, feasible)
+    }
+}
+
+#[derive(Debug)]
+pub enum PlayError<'a> {
+    InvalidAnswer(WordError<'a>),
+    InvalidGuess { turn: usize, word: String },
+    Stalled { turn: usize, word: String },
+    Solve(SolveErr),
+}
+impl From<SolveErr> for PlayError<'_> {
+    fn from(e: SolveErr) -> Self {
+        PlayError::Solve(e)
     }
-    Ok(())
 }
 
 /// A set of valid, uniform-length words for a [`Puzzle`].
 #[derive(Clone)]
 pub struct Dictionary {
     data: Vec<u8>,
+    words: Vec<String>,
     word_len: usize,
+    alphabet: Alphabet,
+    source: Option<DictionarySource>,
 }
 impl Dictionary {
     /// Creates a new dictionary of words where each word is the specified `word_len`.
     /// If a word is invalid (incorrect length or not lowercase alphabetic), returns [`Err`].
     /// Panics if `word_len` is zero.
     pub fn with_words<'a, T: IntoIterator<Item = &'a str>>(word_len: usize, words: T) -> Result<Self, WordError<'a>> {
+        Self::with_alphabet(Alphabet::english(), word_len, words)
+    }
+    /// Creates a new dictionary of words over a custom [`Alphabet`] where each word is the specified `word_len` characters.
+    /// If a word is invalid (incorrect length or contains characters outside the alphabet), returns [`Err`].
+    /// Panics if `word_len` is zero.
+    pub fn with_alphabet<'a, T: IntoIterator<Item = &'a str>>(alphabet: Alphabet, word_len: usize, words: T) -> Result<Self, WordError<'a>> {
         assert!(word_len > 0);
 
-        let words: BTreeSet<_> = words.into_iter().collect(); // sort and dedupe
-
-        let mut data = vec![];
-        for word in words {
-            check_word(word_len, word)?;
-            data.extend(normalized_chars!(word));
-        }
+        let mut words = words.into_iter().map(|w| alphabet.encode(word_len, w)).collect::<Result<Vec<_>, _>>()?;
+        words.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        words.dedup_by(|a, b| a.0 == b.0);
 
+        let data: Vec<u8> = words.into_iter().flat_map(|w| w.0).collect();
         assert_eq!(data.len() % word_len, 0);
-        Ok(Dictionary { data, word_len })
+        let words = data.chunks_exact(word_len).map(|w| alphabet.decode(w)).collect();
+        Ok(Dictionary { data, words, word_len, alphabet, source: None })
+    }
+    /// Creates a new dictionary of multi-word phrases, where words are separated by the given `delimiter`
+    /// and each phrase (including delimiters) is `word_len` characters long.
+    /// If all phrases share the same delimiter positions, the delimiter slots of a [`Puzzle`] are always correct,
+    /// and otherwise the delimiter is given hints just like any other character.
+    /// If a phrase is invalid (incorrect length or not lowercase alphabetic apart from delimiters), returns [`Err`].
+    /// Panics if `word_len` is zero or `delimiter` is a lowercase letter.
+    pub fn with_phrases<'a, T: IntoIterator<Item = &'a str>>(word_len: usize, delimiter: char, phrases: T) -> Result<Self, WordError<'a>> {
+        assert!(!delimiter.is_ascii_lowercase());
+        Self::with_alphabet(Alphabet::new(('a'..='z').chain(iter::once(delimiter))).unwrap(), word_len, phrases)
+    }
+    /// Iterates over the words in the dictionary, in lexicographic order.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
     }
-    fn to_words(&self) -> Vec<Word> {
+    /// Gets the number of letters in each word of the dictionary.
+    pub fn word_len(&self) -> usize {
+        self.word_len
+    }
+    /// Gets the number of words in the dictionary.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+    /// Checks if the dictionary has no words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+    /// Checks if the given word is in the dictionary.
+    pub fn contains(&self, word: &str) -> bool {
+        match self.alphabet.encode(self.word_len, word) {
+            Ok(word) => self.index_of(&word).is_some(),
+            Err(_) => false,
+        }
+    }
+    /// Checks that a guess and the hint received for it are valid for this dictionary, as [`Puzzle::guess`] does:
+    /// the word must have the right length and letters, the hint must be as long as the word, and some answer must be able to give the hint.
+    /// The word need not be in the dictionary.
+    pub fn validate_guess<'b>(&self, word: &'b str, hint: &'b [Hint]) -> Result<(), GuessError<'b>> {
+        self.encode_guess(word, hint).map(|_| ())
+    }
+    /// Checks every entry of a history with [`Dictionary::validate_guess`], reporting the first invalid one along with its position,
+    /// e.g., to reject bad input (from the command line or a saved game) before applying any of it.
+    pub fn validate_history<'b, W: AsRef<str>, H: AsRef<[Hint]>>(&self, history: &'b [(W, H)]) -> Result<(), HistoryError<'b>> {
+        for (turn, (word, hint)) in history.iter().enumerate() {
+            self.validate_guess(word.as_ref(), hint.as_ref()).map_err(|error| HistoryError { turn, error })?;
+        }
+        Ok(())
+    }
+    fn encode_guess<'b>(&self, word: &'b str, hint: &'b [Hint]) -> Result<OwnedWord, GuessError<'b>> {
+        let encoded = self.alphabet.encode(self.word_len, word)?;
+        if encoded.len() != hint.len() { return Err(GuessError::WrongHintLen { hint, expected_len: self.word_len }); }
+        match contradictory_slot(&encoded, hint) {
+            Some(slot) => Err(GuessError::ContradictoryHint { word, hint, slot }),
+            None => Ok(encoded),
+        }
+    }
+    /// Iterates over the words in the dictionary which start with the given prefix, in lexicographic order.
+    /// Because the words are stored in sorted order, this only takes a binary search to find the range of matching words.
+    pub fn words_with_prefix(&self, prefix: &str) -> impl Iterator<Item = &str> {
+        let prefix: Option<Vec<u8>> = prefix.chars().map(|ch| self.alphabet.index_of(ch)).collect();
+        let range = match prefix {
+            Some(prefix) if prefix.len() <= self.word_len => {
+                let start = |i: usize| &self.data[i * self.word_len..i * self.word_len + prefix.len()];
+                let partition_point = |pred: &dyn Fn(&[u8]) -> bool| {
+                    let (mut lo, mut hi) = (0, self.words.len());
+                    while lo < hi {
+                        let mid = (lo + hi) / 2;
+                        if pred(start(mid)) { lo = mid + 1 } else { hi = mid }
+                    }
+                    lo
+                };
+                partition_point(&|x| x < prefix.as_slice())..partition_point(&|x| x <= prefix.as_slice())
+            }
+            _ => 0..0,
+        };
+        self.words[range].iter().map(String::as_str)
+    }
+    fn index_of(&self, word: &[u8]) -> Option<usize> {
+        let (mut lo, mut hi) = (0, self.words.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            match self.data[mid * self.word_len..(mid + 1) * self.word_len].cmp(word) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => return Some(mid),
+            }
+        }
+        None
+    }
+    /// Computes a 64-bit fingerprint of the dictionary's contents (alphabet, word length, and words),
+    /// which is stable across runs and platforms and can be used to detect when cached data is stale.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash: u64 = 0xcbf29ce484222325; // FNV-1a
+        let mut feed = |bytes: &[u8]| for &b in bytes {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        };
+        feed(&(self.word_len as u64).to_le_bytes());
+        for ch in self.alphabet.chars() {
+            feed(&(*ch as u32).to_le_bytes());
+        }
+        feed(&self.data);
+        hash
+    }
+    /// Gets the [`Alphabet`] that words in this dictionary are composed of.
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+    fn word_str(&self, word: &[u8]) -> &str {
+        &self.words[self.index_of(word).expect("word not in dictionary")]
+    }
+    fn to_words(&self) -> Vec<Word<'_>> {
         self.data.chunks_exact(self.word_len).map(Word).collect()
     }
 }
@@ -91,24 +525,35 @@ proptest! {
         let dict = Dictionary::with_words(word_len, words.clone()).unwrap();
         let dict_words = dict.to_words();
         assert!(dict_words.len() <= num_words);
+        assert_eq!(dict_words.len(), dict.len());
 
         for word in words {
-            let clean = OwnedWord::new(word_len, word).unwrap();
+            let clean = Alphabet::english().encode(word_len, word).unwrap();
             let pos = dict_words.iter().enumerate().find(|x| x.1.0 == clean.0).unwrap().0;
             assert!(!dict_words[pos+1..].iter().any(|x| x.0 == clean.0));
+            assert!(dict.contains(word));
+            assert_eq!(dict.index_of(&clean), Some(pos));
         }
     }
 }
 
-struct OwnedWord(Vec<u8>);
-impl OwnedWord {
-    fn new(expected_len: usize, word: &str) -> Result<Self, WordError> {
-        check_word(expected_len, word)?;
-        Ok(OwnedWord(normalized_chars!(word).collect()))
-    }
+#[test]
+fn test_words_with_prefix() {
+    let dict = Dictionary::with_words(5, ["batch", "catch", "chimp", "champ", "cater", "lower"]).unwrap();
+    assert_eq!(dict.words_with_prefix("c").collect::<Vec<_>>(), &["catch", "cater", "champ", "chimp"]);
+    assert_eq!(dict.words_with_prefix("cat").collect::<Vec<_>>(), &["catch", "cater"]);
+    assert_eq!(dict.words_with_prefix("").count(), dict.len());
+    assert_eq!(dict.words_with_prefix("lower").collect::<Vec<_>>(), &["lower"]);
+    assert_eq!(dict.words_with_prefix("d").count(), 0);
+    assert_eq!(dict.words_with_prefix("zzz").count(), 0);
+    assert_eq!(dict.words_with_prefix("lowers").count(), 0);
+    assert_eq!(dict.words_with_prefix("C").count(), 0);
 }
+
+#[derive(Clone)]
+struct OwnedWord(Vec<u8>);
 impl OwnedWord {
-    fn as_ref(&self) -> Word {
+    fn as_ref(&self) -> Word<'_> {
         Word(self.0.as_slice())
     }
 }
@@ -128,24 +573,95 @@ impl<'a> Deref for Word<'a> {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Hint { Correct, Present, Absent }
+impl Hint {
+    /// Gets the single-character code for this hint: `'c'` (correct), `'p'` (present), or `'a'` (absent).
+    pub fn to_char(self) -> char {
+        match self {
+            Hint::Correct => 'c',
+            Hint::Present => 'p',
+            Hint::Absent => 'a',
+        }
+    }
+    /// Parses a single-character hint code as produced by [`Hint::to_char`].
+    pub fn from_char(ch: char) -> Option<Hint> {
+        match ch {
+            'c' => Some(Hint::Correct),
+            'p' => Some(Hint::Present),
+            'a' => Some(Hint::Absent),
+            _ => None,
+        }
+    }
+    /// Gets the share-grid emoji for this hint (green, yellow, or black square).
+    pub fn to_emoji(self) -> char {
+        match self {
+            Hint::Correct => '🟩',
+            Hint::Present => '🟨',
+            Hint::Absent => '⬛',
+        }
+    }
+    /// Parses a share-grid emoji, including the white square used in light mode and the orange/blue squares used in high contrast mode.
+    pub fn from_emoji(ch: char) -> Option<Hint> {
+        match ch {
+            '🟩' | '🟧' => Some(Hint::Correct),
+            '🟨' | '🟦' => Some(Hint::Present),
+            '⬛' | '⬜' => Some(Hint::Absent),
+            _ => None,
+        }
+    }
+}
 
 /// Generate the hint that would be provided after making the given guess.
 /// Note that this requires we know the answer (e.g, for implementing a wordle game).
+/// Both words must be lowercase alphabetic (`a..=z`), as for the default dictionary; for words from a custom [`Alphabet`]
+/// (e.g., accented letters or the delimiters of [`Dictionary::with_phrases`]), use [`Alphabet::get_hint`] instead.
+/// Whether the words are actually in a dictionary is left to the caller (e.g., with [`Dictionary::contains`]).
+/// Fails with [`WordError::NotInAlphabet`] if either word has other characters (checking the answer first),
+/// or with [`WordError::WrongWordLen`] if the words have different lengths.
 pub fn get_hint<'a>(guess: &'a str, answer: &'a str) -> Result<Vec<Hint>, WordError<'a>> {
-    let len = answer.len();
-    check_word(len, answer)?;
-    check_word(len, guess)?;
-    let norm_answer = normalized_chars!(answer);
-    let norm_guess = normalized_chars!(guess);
-
-    let mut counts = [0u8; 26];
-    for ch in norm_answer.clone() {
+    for word in [answer, guess] {
+        if !word.bytes().all(|x| x.is_ascii_lowercase()) {
+            return Err(WordError::NotInAlphabet { word });
+        }
+    }
+    if guess.len() != answer.len() {
+        return Err(WordError::WrongWordLen { word: guess, expected_len: answer.len() });
+    }
+    let answer: Vec<u8> = answer.bytes().map(|x| x - b'a').collect();
+    let guess: Vec<u8> = guess.bytes().map(|x| x - b'a').collect();
+    Ok(hint_impl(&guess, &answer))
+}
+/// Like [`get_hint`], but the words may consist of any characters, which are compared exactly.
+/// This is for words which are known to come from a [`Dictionary`] without access to its alphabet.
+/// Panics if the words contain more than 256 distinct characters.
+pub(crate) fn get_hint_chars<'a>(guess: &'a str, answer: &'a str) -> Result<Vec<Hint>, WordError<'a>> {
+    let len = answer.chars().count();
+    if guess.chars().count() != len {
+        return Err(WordError::WrongWordLen { word: guess, expected_len: len });
+    }
+
+    let mut seen = vec![];
+    let mut normalize = |ch: char| match seen.iter().position(|&x| x == ch) {
+        Some(x) => x as u8,
+        None => {
+            seen.push(ch);
+            u8::try_from(seen.len() - 1).expect("too many distinct characters")
+        }
+    };
+    let answer: Vec<u8> = answer.chars().map(&mut normalize).collect();
+    let guess: Vec<u8> = guess.chars().map(&mut normalize).collect();
+    Ok(hint_impl(&guess, &answer))
+}
+pub(crate) fn hint_impl(guess: &[u8], answer: &[u8]) -> Vec<Hint> {
+    debug_assert_eq!(guess.len(), answer.len());
+
+    let mut counts = [0u8; 256];
+    for &ch in answer {
         counts[ch as usize] += 1;
     }
 
-    let mut res = vec![Hint::Correct; len];
+    let mut res = vec![Hint::Correct; answer.len()];
     macro_rules! drop_count {
         ($g:ident) => {{
             let count = counts[$g as usize];
@@ -154,7 +670,7 @@ pub fn get_hint<'a>(guess: &'a str, answer: &'a str) -> Result<Vec<Hint>, WordEr
         }}
     }
 
-    let char_pairs = iter::zip(norm_guess.clone(), norm_answer.clone());
+    let char_pairs = iter::zip(guess.iter().copied(), answer.iter().copied());
     for (g, a) in char_pairs.clone() {
         if g == a { drop_count!(g); } // mark corrects first
     }
@@ -163,7 +679,107 @@ pub fn get_hint<'a>(guess: &'a str, answer: &'a str) -> Result<Vec<Hint>, WordEr
         *h = if drop_count!(g) > 0 { Hint::Present } else { Hint::Absent }
     }
 
-    Ok(res)
+    res
+}
+/// Finds a [`Hint::Present`] slot in the hint which no answer could produce for the given (alphabet-normalized) guess,
+/// as described by [`GuessError::ContradictoryHint`].
+fn contradictory_slot(guess: &[u8], hint: &[Hint]) -> Option<usize> {
+    debug_assert_eq!(guess.len(), hint.len());
+
+    let mut absent = [false; Alphabet::MAX_LEN];
+    let mut open = [0usize; Alphabet::MAX_LEN]; // slots which are not correct, by guess letter
+    let mut present = [0usize; Alphabet::MAX_LEN];
+    for (slot, (&g, &h)) in iter::zip(guess, hint).enumerate() {
+        match h {
+            Hint::Correct => continue,
+            Hint::Absent => absent[g as usize] = true,
+            Hint::Present if absent[g as usize] => return Some(slot),
+            Hint::Present => present[g as usize] += 1,
+        }
+        open[g as usize] += 1;
+    }
+    // the present copies of a letter must be in open slots which didn't guess it (different letters can always share the rest)
+    let total: usize = open.iter().sum();
+    let letter = (0..Alphabet::MAX_LEN).find(|&x| present[x] > total - open[x])?;
+    (0..guess.len()).rev().find(|&i| guess[i] as usize == letter && hint[i] == Hint::Present)
+}
+/// Computes the hint for the given alphabet-normalized words as a base-3 code, which is the same as [`HintPattern::code`]
+/// (absent is `0`, present is `1`, correct is `2`, and the first slot is the least significant digit).
+fn hint_code(guess: &[u8], answer: &[u8]) -> u32 {
+    debug_assert!(guess.len() == answer.len() && answer.len() <= 20);
+
+    let mut counts = [0u8; Alphabet::MAX_LEN];
+    for (&g, &a) in iter::zip(guess, answer) {
+        if g != a { counts[a as usize] += 1; }
+    }
+
+    let mut code = 0;
+    let mut place = 1;
+    for (&g, &a) in iter::zip(guess, answer) {
+        let digit = if g == a {
+            2
+        } else if counts[g as usize] > 0 {
+            counts[g as usize] -= 1;
+            1
+        } else {
+            0
+        };
+        code += digit * place;
+        place *= 3;
+    }
+    code
+}
+
+/// Checks if making the given guess against the given answer would produce exactly the given hint.
+/// If the guess and answer are not the same length or not lowercase alphabetic (see [`get_hint`]), returns `false`.
+pub fn is_consistent(guess: &str, hint: &[Hint], answer: &str) -> bool {
+    matches!(get_hint(guess, answer), Ok(x) if x == hint)
+}
+
+/// Finds all words in the dictionary which, as the answer, would produce exactly the given hint after making the given guess.
+/// If the guess is not a valid word for the dictionary or the hint is the wrong length, no answers are consistent.
+pub fn consistent_answers<'a>(guess: &str, hint: &[Hint], dictionary: &'a Dictionary) -> Vec<&'a str> {
+    let guess = match dictionary.alphabet.encode(dictionary.word_len, guess) {
+        Ok(x) if hint.len() == x.len() => x,
+        _ => return vec![],
+    };
+    iter::zip(dictionary.to_words(), dictionary.words()).filter(|(w, _)| hint_impl(&guess, w) == hint).map(|x| x.1).collect()
+}
+
+/// Finds all words in the dictionary which, as a guess, would produce exactly the given hint against the given answer.
+/// If the answer is not a valid word for the dictionary or the hint is the wrong length, no guesses are consistent.
+pub fn consistent_guesses<'a>(hint: &[Hint], answer: &str, dictionary: &'a Dictionary) -> Vec<&'a str> {
+    let answer = match dictionary.alphabet.encode(dictionary.word_len, answer) {
+        Ok(x) if hint.len() == x.len() => x,
+        _ => return vec![],
+    };
+    iter::zip(dictionary.to_words(), dictionary.words()).filter(|(w, _)| hint_impl(w, &answer) == hint).map(|x| x.1).collect()
+}
+
+#[test]
+fn test_consistency() {
+    use Hint::{Absent as A, Correct as C, Present as P};
+
+    assert!(is_consistent("speed", &[A, A, P, A, P], "abide"));
+    assert!(!is_consistent("speed", &[A, A, P, P, P], "abide"));
+    assert!(!is_consistent("speed", &[A, A, P, A], "abide"));
+    assert!(!is_consistent("speed", &[A, A, P, A, P], "abides"));
+
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "match", "patch", "watch", "chimp", "lower"]).unwrap();
+    assert_eq!(consistent_answers("hatch", &[A, C, C, C, C], &dict), &["batch", "catch", "latch", "match", "patch", "watch"]);
+    assert_eq!(consistent_answers("watch", &[P, A, A, A, A], &dict), &["lower"]);
+    assert_eq!(consistent_answers("chimp", &[C, C, C, C, C], &dict), &["chimp"]);
+    assert_eq!(consistent_answers("zzzzz", &[C, C, C, C, C], &dict), Vec::<&str>::new());
+    assert_eq!(consistent_answers("chimp", &[C, C, C, C], &dict), Vec::<&str>::new());
+    assert_eq!(consistent_answers("chim", &[C, C, C, C], &dict), Vec::<&str>::new());
+    for word in dict.words() {
+        assert_eq!(consistent_answers(word, &get_hint(word, "lower").unwrap(), &dict), &["lower"]);
+    }
+
+    assert_eq!(consistent_guesses(&[A, C, C, C, C], "hatch", &dict), &["batch", "catch", "latch", "match", "patch", "watch"]);
+    assert_eq!(consistent_guesses(&[P, A, A, P, A], "chimp", &dict), &["hatch"]);
+    assert_eq!(consistent_guesses(&[C, C, C, C, C], "lower", &dict), &["lower"]);
+    assert_eq!(consistent_guesses(&[C, C, C, C, C], "lowers", &dict), Vec::<&str>::new());
 }
 
 #[test]
@@ -184,58 +800,198 @@ fn test_get_hint() {
 
     assert_eq!(&get_hint("oogaa", "hloop").unwrap(), &[Hint::Present, Hint::Present, Hint::Absent, Hint::Absent, Hint::Absent]);
     assert_eq!(&get_hint("oogaa", "hollp").unwrap(), &[Hint::Absent, Hint::Correct, Hint::Absent, Hint::Absent, Hint::Absent]);
+
+    assert!(matches!(get_hint("hatch", "batches"), Err(WordError::WrongWordLen { word: "hatch", expected_len: 7 })));
+    assert!(matches!(get_hint("", "a"), Err(WordError::WrongWordLen { word: "", expected_len: 1 })));
+    assert!(matches!(get_hint("Hatch", "hatch"), Err(WordError::NotInAlphabet { word: "Hatch" })));
+    assert!(matches!(get_hint("hatch", "hat h"), Err(WordError::NotInAlphabet { word: "hat h" })));
+    assert!(matches!(get_hint("ñandú", "dúñan"), Err(WordError::NotInAlphabet { word: "dúñan" })));
+    assert!(matches!(get_hint("h4tch", "bat"), Err(WordError::NotInAlphabet { word: "h4tch" })));
+    assert_eq!(get_hint("", "").unwrap(), &[]);
+
+    // other characters are only compared as they are where the alphabet isn't needed
+    assert_eq!(&get_hint_chars("Hatch", "hatch").unwrap(), &[Hint::Absent, Hint::Correct, Hint::Correct, Hint::Correct, Hint::Correct]);
+    assert_eq!(&get_hint_chars("ñandú", "dúñan").unwrap(), &[Hint::Present; 5]);
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Constraints {
+    slots: Vec<BitSet32>,
+    letter_counts: [(usize, usize); Alphabet::MAX_LEN],
+}
+impl Constraints {
+    /// Checks if the word satisfies both the slot and letter count constraints.
+    fn admits(&self, word: Word) -> bool {
+        iter::zip(&self.slots, word.iter()).all(|(slot, &letter)| slot.contains(letter)) && fits_counts(&self.letter_counts, word)
+    }
+}
+
+/// Checks if the word satisfies the letter count constraints (ignoring the slots).
+fn fits_counts(letter_counts: &[(usize, usize); Alphabet::MAX_LEN], word: Word) -> bool {
+    let mut occurrences = [0; Alphabet::MAX_LEN];
+    for &letter in word.iter() {
+        occurrences[letter as usize] += 1;
+    }
+    iter::zip(letter_counts, occurrences).all(|(counts, occ)| (counts.0..=counts.1).contains(&occ))
 }
 
 /// A wordle-like puzzle.
+///
+/// The dictionary is borrowed and the encoded word lists are reference counted, so clones (and puzzles made with [`Puzzle::restart`])
+/// share them and only copy the small per-puzzle solve state. Every query takes `&self` and never mutates the puzzle
+/// (deferred reductions are performed on a private copy), so a single puzzle is [`Send`] and [`Sync`]
+/// and may be queried (e.g., by [`Puzzle::best_guess`]) from any number of threads at once.
+/// To update a puzzle while it is being read elsewhere (e.g., rendered by a UI thread), use [`Puzzle::guessed`],
+/// which makes the new state as a separate version and leaves the old one untouched.
 #[derive(Clone)]
 pub struct Puzzle<'a> {
+    dictionary: &'a Dictionary,
+    /// The encoded words of the dictionary, which are shared by all puzzles derived from the same one.
     all_words: Arc<Vec<Word<'a>>>,
     feasible_words: Arc<Vec<Word<'a>>>,
 
     slots: Vec<BitSet32>,
-    letter_counts: [(usize, usize); 26],
+    letter_counts: [(usize, usize); Alphabet::MAX_LEN],
+    /// The letters whose maximum number of occurrences is zero, which are removed from every slot when reduced.
+    banned_letters: BitSet32,
+
+    /// The constraints directly implied by hints or edits, before any reductions (which can depend on the dictionary).
+    given: Arc<Constraints>,
+    /// The guesses made so far and the hints received for them.
+    history: Arc<Vec<(String, Vec<Hint>)>>,
+    /// The given constraints from before each guess in the history, so that guesses can be undone (see [`Puzzle::undo`]).
+    given_before: Arc<Vec<Arc<Constraints>>>,
+    /// Whether hints have been applied to the solve state without reducing it yet (see [`Puzzle::force_reduce`]).
+    dirty: bool,
+    /// A reduced copy of the puzzle made by the first query while dirty, so later queries can share it (see [`Puzzle::reduced`]).
+    /// This must be cleared by anything which changes the puzzle.
+    reduced_cache: OnceLock<Arc<Puzzle<'a>>>,
+
+    turn: usize,
+    max_guesses: usize,
+    endgame_threshold: usize,
+
+    #[cfg(feature = "debug-invariants")]
+    known_answer: Option<Arc<OwnedWord>>,
 }
 impl<'a> Puzzle<'a> {
+    /// The default maximum number of feasible words for which [`Puzzle::best_guess`] switches to the exact endgame solver.
+    pub const ENDGAME_THRESHOLD: usize = 20;
+    /// The largest threshold for the exact endgame solver (see [`Puzzle::set_endgame_threshold`]).
+    pub const MAX_ENDGAME_THRESHOLD: usize = endgame::Endgame::MAX_ANSWERS;
+
     /// Creates a new puzzle from a [`Dictionary`] of acceptable words to guess.
     /// This object does not store the answer to the puzzle, and is instead used as a solver state.
     /// The number of letters in the puzzle is defined by the supplied dictionary.
     pub fn new(dictionary: &'a Dictionary) -> Self {
-        let all_words = Arc::new(dictionary.to_words());
+        Self::with_words(dictionary, Arc::new(dictionary.to_words()))
+    }
+    /// Creates a new puzzle with no hints for the same dictionary, sharing its word data rather than encoding the dictionary again.
+    /// Settings such as [`Puzzle::set_max_guesses`] are not carried over.
+    pub fn restart(&self) -> Self {
+        Self::with_words(self.dictionary, self.all_words.clone())
+    }
+    /// Clears all hints (and the history) in place, as if the puzzle was just created, e.g., to reuse it for another game.
+    /// Like [`Puzzle::restart`], the word data is shared rather than encoded again, but settings such as [`Puzzle::set_max_guesses`] are kept.
+    pub fn reset(&mut self) {
+        let mut fresh = self.restart();
+        fresh.max_guesses = self.max_guesses;
+        fresh.endgame_threshold = self.endgame_threshold;
+        *self = fresh;
+    }
+    /// Creates a new puzzle which starts from partially known letters rather than from guesses, e.g., to join a game midway.
+    /// Each character of the pattern is either a letter known to be in that slot or `_` for an unknown slot (so `"_a__e"` means
+    /// the second letter is `a` and the last is `e`). No guesses are counted. Letters known to be absent can be added with [`Puzzle::ban_letter`].
+    pub fn with_pattern<'b>(dictionary: &'a Dictionary, pattern: &'b str) -> Result<Self, WordError<'b>> {
+        let word_len = dictionary.word_len;
+        let mut known = Vec::with_capacity(word_len);
+        for ch in pattern.chars() {
+            known.push(match ch {
+                '_' => None,
+                ch => Some(dictionary.alphabet.index_of(ch).ok_or(WordError::NotInAlphabet { word: pattern })?),
+            });
+        }
+        if known.len() != word_len { return Err(WordError::WrongWordLen { word: pattern, expected_len: word_len }); }
+
+        let mut res = Self::new(dictionary);
+        let given = Arc::make_mut(&mut res.given);
+        for (slot, &letter) in known.iter().enumerate() {
+            let Some(letter) = letter else { continue };
+            given.slots[slot] = BitSet32::new();
+            given.slots[slot].insert(letter);
+            let counts = &mut given.letter_counts[letter as usize];
+            counts.0 = counts.0.max(known.iter().filter(|&&x| x == Some(letter)).count());
+        }
+        res.rederive();
+        Ok(res)
+    }
+    fn with_words(dictionary: &'a Dictionary, all_words: Arc<Vec<Word<'a>>>) -> Self {
         let feasible_words = all_words.clone();
 
         let mut allowed = BitSet32::new();
-        for i in 0..26 { allowed.insert(i); }
+        for i in 0..dictionary.alphabet.chars().len() { allowed.insert(i as u8); }
 
+        let slots = vec![allowed; dictionary.word_len];
+        let letter_counts = [(0, dictionary.word_len); Alphabet::MAX_LEN];
         let mut res = Puzzle {
-            all_words, feasible_words,
-            slots: vec![allowed; dictionary.word_len],
-            letter_counts: [(0, dictionary.word_len); 26],
+            dictionary, all_words, feasible_words,
+            given: Arc::new(Constraints { slots: slots.clone(), letter_counts }),
+            slots, letter_counts,
+            banned_letters: BitSet32::new(),
+            history: Default::default(),
+            given_before: Default::default(),
+            dirty: false,
+            reduced_cache: OnceLock::new(),
+            turn: 0,
+            max_guesses: Game::DEFAULT_MAX_GUESSES,
+            endgame_threshold: Self::ENDGAME_THRESHOLD,
+            #[cfg(feature = "debug-invariants")]
+            known_answer: None,
         };
 
         res.reduce();
         res
     }
+    fn encode<'b>(&self, word: &'b str) -> Result<OwnedWord, WordError<'b>> {
+        self.dictionary.alphabet.encode(self.slots.len(), word)
+    }
+    fn decode(&self, word: &[u8]) -> String {
+        self.dictionary.alphabet.decode(word)
+    }
     fn could_be(&self, word: Word) -> bool {
         debug_assert!(word.len() == self.slots.len());
 
-        let mut occurrences = [0; 26];
-        for (slot, &letter) in iter::zip(&self.slots, word.iter()) {
-            if !slot.contains(letter) { return false }
-            occurrences[letter as usize] += 1;
-        }
-        for (counts, occ) in iter::zip(&self.letter_counts, occurrences) {
-            if occ != 0 && !(counts.0..=counts.1).contains(&occ) { return false }
-        }
-        true
+        if word.iter().any(|&letter| self.banned_letters.contains(letter)) { return false }
+
+        if iter::zip(&self.slots, word.iter()).any(|(slot, &letter)| !slot.contains(letter)) { return false }
+        self.fits_counts(word)
+    }
+    /// Checks if the word is one of the feasible words, which (unlike [`Puzzle::could_be`]) is never true for words outside the dictionary.
+    /// This is only exact for a reduced puzzle.
+    fn is_feasible(&self, word: Word) -> bool {
+        self.feasible_words.binary_search(&word).is_ok()
+    }
+    /// Checks if the word satisfies the letter count constraints (ignoring the slots).
+    fn fits_counts(&self, word: Word) -> bool {
+        fits_counts(&self.letter_counts, word)
     }
     fn reduce(&mut self) {
+        self.reduce_traced(&mut |_| ());
+    }
+    fn reduce_traced<T: FnMut(TraceStep)>(&mut self, trace: &mut T) {
+        self.dirty = false;
+        self.reduced_cache.take();
+        let letters = self.dictionary.alphabet.chars();
         let mut masks = vec![BitSet32::new(); self.slots.len()];
         let mut slot_idxs = Vec::with_capacity(self.slots.len());
 
         loop {
-            let mut did_something = false;
+            let mut did_something = self.propagate_counts(trace);
 
-            let new_feasible: Vec<_> = self.feasible_words.iter().copied().filter(|&x| self.could_be(x)).collect();
+            let new_feasible = self.filter_feasible(&self.feasible_words);
+            if new_feasible.len() != self.feasible_words.len() {
+                trace(TraceStep::Feasible { before: self.feasible_words.len(), after: new_feasible.len() });
+            }
             self.feasible_words = Arc::new(new_feasible);
 
             // do slot-wise letter elimination by intersect with union over feasible words
@@ -245,10 +1001,15 @@ impl<'a> Puzzle<'a> {
                     mask.insert(letter);
                 }
             }
-            for (slot, mask) in iter::zip(&mut self.slots, &masks) {
+            for (i, (slot, mask)) in iter::zip(&mut self.slots, &masks).enumerate() {
                 let prev = *slot;
                 slot.intersect_with(mask);
-                if *slot != prev { did_something = true; }
+                if *slot != prev {
+                    did_something = true;
+                    for letter in prev.into_iter().filter(|&x| !slot.contains(x)) {
+                        trace(TraceStep::Unused { slot: i, letter: letters[letter as usize] });
+                    }
+                }
             }
 
             // do occurrence-based eliminations for slots with known occurrences
@@ -264,15 +1025,69 @@ impl<'a> Puzzle<'a> {
                     let prev = *slot;
                     slot.clear();
                     slot.insert(letter);
-                    if *slot != prev { did_something = true; }
+                    if *slot != prev {
+                        did_something = true;
+                        trace(TraceStep::Forced { slot: idx, letter: letters[letter as usize] });
+                    }
                 }
             }
 
             if !did_something { return }
         }
     }
-    fn guess_impl(&mut self, word: Word, response: &[Hint]) {
-        debug_assert!(word.len() == response.len() && word.len() == self.slots.len());
+    /// Eliminates letters from slots using only the letter counts (i.e., without the dictionary), returning true if anything changed.
+    /// A letter which is already fixed in as many slots as its maximum allows can't be in any other slot (or any slot at all, if its maximum is zero),
+    /// and if the letters which need more occurrences than they have fixed slots need every unfixed slot, no other letter can be in them.
+    fn propagate_counts<T: FnMut(TraceStep)>(&mut self, trace: &mut T) -> bool {
+        let letters = self.dictionary.alphabet.chars();
+        let mut did_something = false;
+
+        let count_fixed = |slots: &[BitSet32]| {
+            let mut fixed = [0; Alphabet::MAX_LEN];
+            for slot in slots.iter().filter(|s| s.len() == 1) {
+                fixed[slot.into_iter().next().unwrap() as usize] += 1;
+            }
+            fixed
+        };
+        let fixed = count_fixed(&self.slots);
+        self.banned_letters.clear();
+        for (letter, &(_, max)) in self.letter_counts.iter().enumerate() {
+            if max == 0 { self.banned_letters.insert(letter as u8); }
+            if fixed[letter] < max { continue }
+            for (i, slot) in self.slots.iter_mut().enumerate() {
+                // a banned letter is removed even from a slot where it's fixed, which leaves the puzzle inconsistent
+                if (slot.len() != 1 || max == 0) && slot.contains(letter as u8) {
+                    slot.remove(letter as u8);
+                    did_something = true;
+                    trace(TraceStep::MaxReached { slot: i, letter: letters[letter] });
+                }
+            }
+        }
+
+        let fixed = count_fixed(&self.slots); // the eliminations above may have fixed more slots
+        let mut needed = BitSet32::new();
+        let mut total_needed = 0;
+        for (letter, &(min, _)) in self.letter_counts.iter().enumerate() {
+            if min > fixed[letter] {
+                needed.insert(letter as u8);
+                total_needed += min - fixed[letter];
+            }
+        }
+        if total_needed == self.slots.iter().filter(|s| s.len() != 1).count() {
+            for (i, slot) in self.slots.iter_mut().enumerate().filter(|x| x.1.len() != 1) {
+                let prev = *slot;
+                slot.intersect_with(&needed);
+                for letter in prev.into_iter().filter(|&x| !slot.contains(x)) {
+                    did_something = true;
+                    trace(TraceStep::Reserved { slot: i, letter: letters[letter as usize] });
+                }
+            }
+        }
+        did_something
+    }
+    /// Applies the constraints implied by a hint to the given slot and letter count constraints.
+    fn apply_hint<T: FnMut(TraceStep)>(slots: &mut [BitSet32], letter_counts: &mut [(usize, usize); Alphabet::MAX_LEN], word: Word, response: &[Hint], letters: &[char], trace: &mut T) {
+        debug_assert!(word.len() == response.len() && word.len() == slots.len());
 
         // (slot, (letter, hint)) -- sorted by letter, then by hint, then by slot
         let mut word: Vec<(usize, (u8, Hint))> = iter::zip(word.iter().copied(), response.iter().copied()).enumerate().collect();
@@ -283,134 +1098,1299 @@ impl<'a> Puzzle<'a> {
         for (i, (ch, hint)) in word.iter().copied() {
             if ch != prev_char { occ_idx = 0; }
 
-            let mut letter_counts = &mut self.letter_counts[ch as usize];
-            let slot = &mut self.slots[i];
+            let letter = letters[ch as usize];
+            let letter_counts = &mut letter_counts[ch as usize];
+            let prev_counts = *letter_counts;
+            let slot = &mut slots[i];
             match hint {
                 Hint::Correct => {
                     letter_counts.0 = letter_counts.0.max(occ_idx + 1);
-                    slot.clear();
-                    slot.insert(ch);
+                    if *slot != BitSet32::single(ch) { trace(TraceStep::Correct { slot: i, letter }); }
+                    *slot = BitSet32::single(ch);
                 }
                 Hint::Present => {
                     letter_counts.0 = letter_counts.0.max(occ_idx + 1);
+                    if slot.contains(ch) { trace(TraceStep::NotInSlot { slot: i, letter }); }
                     slot.remove(ch);
                 }
                 Hint::Absent => {
                     letter_counts.1 = letter_counts.1.min(occ_idx);
                     if occ_idx == 0 {
-                        for slot in self.slots.iter_mut() {
+                        if slots.iter().any(|s| s.contains(ch)) { trace(TraceStep::Absent { letter }); }
+                        for slot in slots.iter_mut() {
                             slot.remove(ch);
                         }
+                    } else {
+                        // an extra copy is absent rather than correct, so the letter isn't here either
+                        if slot.contains(ch) { trace(TraceStep::NotInSlot { slot: i, letter }); }
+                        slot.remove(ch);
                     }
                 }
             }
+            if letter_counts.0 != prev_counts.0 { trace(TraceStep::MinCount { letter, min: letter_counts.0 }); }
+            if letter_counts.1 != prev_counts.1 { trace(TraceStep::MaxCount { letter, max: letter_counts.1 }); }
 
             prev_char = ch;
             occ_idx += 1;
         }
-
-        self.reduce();
     }
+    /// Runs any reductions deferred by [`Puzzle::guess`] or [`Puzzle::apply_history`].
+    /// This happens automatically before any query, but queries on a shared reference must reduce a temporary copy each time,
+    /// so callers making several queries after a guess can call this once up front instead.
+    pub fn force_reduce(&mut self) {
+        if self.dirty { self.reduce(); }
+    }
+    /// Gets a fully-reduced view of the puzzle. If there are deferred reductions, they are done on a copy,
+    /// which is kept until the puzzle is changed so that later queries don't have to copy and reduce it again.
+    fn reduced(&self) -> &Self {
+        if !self.dirty { return self }
+        self.reduced_cache.get_or_init(|| {
+            let mut res = self.clone();
+            res.reduce();
+            Arc::new(res)
+        })
+    }
+    /// Copies the puzzle with a shorter lifetime, e.g., to search it along with words which don't live as long as the dictionary.
+    /// The cache of [`Puzzle::reduced`] makes the puzzle invariant over its lifetime, so this can't be done by coercion.
+    fn shortened<'b>(&self) -> Puzzle<'b> where 'a: 'b {
+        Puzzle {
+            dictionary: self.dictionary,
+            all_words: self.all_words.clone(),
+            feasible_words: self.feasible_words.clone(),
+            slots: self.slots.clone(),
+            letter_counts: self.letter_counts,
+            banned_letters: self.banned_letters,
+            given: self.given.clone(),
+            history: self.history.clone(),
+            given_before: self.given_before.clone(),
+            dirty: self.dirty,
+            reduced_cache: OnceLock::new(),
+            turn: self.turn,
+            max_guesses: self.max_guesses,
+            endgame_threshold: self.endgame_threshold,
+            #[cfg(feature = "debug-invariants")]
+            known_answer: self.known_answer.clone(),
+        }
+    }
+    /// Discards the reduced copy made by [`Puzzle::reduced`], which must be done whenever the puzzle changes.
+    fn invalidate_reduced(&mut self) {
+        self.reduced_cache.take();
+    }
+    /// Applies a hint to the given constraints and the solve state, deferring reductions until needed.
+    fn guess_lazy(&mut self, word: Word, response: &[Hint]) {
+        let letters = self.dictionary.alphabet.chars();
+        Arc::make_mut(&mut self.given_before).push(self.given.clone());
+        let given = Arc::make_mut(&mut self.given);
+        Self::apply_hint(&mut given.slots, &mut given.letter_counts, word, response, letters, &mut |_| ());
+        Self::apply_hint(&mut self.slots, &mut self.letter_counts, word, response, letters, &mut |_| ());
+        let decoded = self.decode(&word);
+        Arc::make_mut(&mut self.history).push((decoded, response.to_vec()));
+        self.dirty = true;
+        self.invalidate_reduced();
+        self.turn += 1;
+    }
+    /// Applies a hint to the derived solve state only, which is all that is needed for temporary puzzles during search.
+    fn assume_impl(&mut self, word: Word, response: &[Hint]) {
+        self.assume_traced(word, response, &mut |_| ());
+    }
+    fn assume_traced<T: FnMut(TraceStep)>(&mut self, word: Word, response: &[Hint], trace: &mut T) {
+        Self::apply_hint(&mut self.slots, &mut self.letter_counts, word, response, self.dictionary.alphabet.chars(), trace);
+        self.reduce_traced(trace);
+    }
+    /// Applies a hint to both the given constraints (which can later be relaxed) and the derived solve state.
+    fn guess_impl(&mut self, word: Word, response: &[Hint]) {
+        self.guess_impl_traced(word, response, &mut |_| ());
+    }
+    fn guess_impl_traced<T: FnMut(TraceStep)>(&mut self, word: Word, response: &[Hint], trace: &mut T) {
+        Arc::make_mut(&mut self.given_before).push(self.given.clone());
+        let given = Arc::make_mut(&mut self.given);
+        Self::apply_hint(&mut given.slots, &mut given.letter_counts, word, response, self.dictionary.alphabet.chars(), &mut |_| ());
+        self.assume_traced(word, response, trace);
+        let decoded = self.decode(&word);
+        Arc::make_mut(&mut self.history).push((decoded, response.to_vec()));
+        self.turn += 1;
+        self.check_invariants();
+    }
+    /// Reverts the most recent guess (e.g., one with a mistyped hint), returning it along with its hint, or [`None`] if there were no guesses.
+    /// The constraints go back to exactly what they were before the guess, so edits made since then (like [`Puzzle::relax_letter`]) are also reverted,
+    /// and the guess count goes down by one.
+    pub fn undo(&mut self) -> Option<(String, Vec<Hint>)> {
+        let given = Arc::make_mut(&mut self.given_before).pop()?;
+        let entry = Arc::make_mut(&mut self.history).pop().expect("every guess is in the history");
+        self.given = given;
+        self.turn = self.turn.saturating_sub(1);
+        self.rederive();
+        Some(entry)
+    }
+    /// Gets the guesses made so far with [`Puzzle::guess`] (or similar) and the hints received for them, in order.
+    pub fn history(&self) -> &[(String, Vec<Hint>)] {
+        &self.history
+    }
+    /// Gets the number of letters in each word, which is also the length of every hint.
+    pub fn word_len(&self) -> usize {
+        self.slots.len()
+    }
+    /// Gets the [`Dictionary`] the puzzle was created from.
+    pub fn dictionary(&self) -> &'a Dictionary {
+        self.dictionary
+    }
+    /// Gets the number of guesses made so far, which counts the calls to [`Puzzle::guess`] (or similar) unless changed by [`Puzzle::set_turn`].
+    pub fn guess_count(&self) -> usize {
+        self.turn
+    }
+    /// Sets the maximum number of guesses allowed in the game (default [`Game::DEFAULT_MAX_GUESSES`]), which is respected by [`Puzzle::best_guess`].
+    /// Guesses already made with [`Puzzle::guess`] count towards this limit.
+    pub fn set_max_guesses(&mut self, max_guesses: usize) {
+        self.max_guesses = max_guesses;
+        self.invalidate_reduced();
+    }
+    /// Sets the number of guesses already made in the game, which otherwise counts the calls to [`Puzzle::guess`].
+    /// This is useful when the puzzle was reconstructed from a partial history.
+    pub fn set_turn(&mut self, turn: usize) {
+        self.turn = turn;
+        self.invalidate_reduced();
+    }
+    /// Gets the number of guesses remaining in the game (see [`Puzzle::set_max_guesses`] and [`Puzzle::set_turn`]).
+    pub fn turns_left(&self) -> usize {
+        self.max_guesses.saturating_sub(self.turn)
+    }
+    /// Sets the maximum number of feasible words for which [`Puzzle::best_guess`] uses the exact endgame solver (default [`Puzzle::ENDGAME_THRESHOLD`]).
+    /// A threshold of zero always uses minimax, and thresholds above [`Puzzle::MAX_ENDGAME_THRESHOLD`] are treated as that maximum.
+    pub fn set_endgame_threshold(&mut self, threshold: usize) {
+        self.endgame_threshold = threshold.min(Self::MAX_ENDGAME_THRESHOLD);
+        self.invalidate_reduced();
+    }
+    /// Sets the true answer to the puzzle (e.g., in a simulation), which is asserted to remain feasible after every guess or edit.
+    /// Answers which are not in the dictionary are ignored.
+    #[cfg(feature = "debug-invariants")]
+    pub fn set_known_answer(&mut self, answer: &str) {
+        self.known_answer = self.encode(answer).ok().filter(|x| self.dictionary.index_of(x).is_some()).map(Arc::new);
+        self.invalidate_reduced();
+        self.check_invariants();
+    }
+    #[cfg(feature = "debug-invariants")]
+    fn check_invariants(&self) {
+        if let Some(answer) = &self.known_answer {
+            let answer = Word(&answer.0);
+            assert!(self.could_be(answer), "answer '{}' violates the constraints:\n{}", self.decode(&answer), self);
+            assert!(self.feasible_words.binary_search(&answer).is_ok(), "answer '{}' was eliminated from the feasible words", self.decode(&answer));
+        }
+    }
+    #[cfg(not(feature = "debug-invariants"))]
+    fn check_invariants(&self) {}
     /// Performs the solve state reductions corresponding to guessing the given word and receiving the supplied hint from the game.
     /// The `word` is assumed to be a valid word from the dictionary, but this is not enforced.
     /// If the `word` is invalid (not lower alphabetic or wrong length), or if the hint is the wrong length, returns [`Err`].
+    /// The same goes for hints which no answer could produce (see [`GuessError::ContradictoryHint`]), since they are almost always typos.
+    ///
+    /// The (costly) reductions are deferred until the next query, so consecutive guesses only pay for them once (see [`Puzzle::force_reduce`]).
     pub fn guess<'b>(&mut self, word: &'b str, hint: &'b [Hint]) -> Result<(), GuessError<'b>> {
-        let word = OwnedWord::new(self.slots.len(), word)?;
-        if word.len() != hint.len() { return Err(GuessError::WrongHintLen { hint, expected_len: self.slots.len() }); }
-        self.guess_impl(word.as_ref(), hint);
+        let encoded = self.dictionary.encode_guess(word, hint)?;
+        self.guess_lazy(encoded.as_ref(), hint);
+        self.check_invariants();
         Ok(())
     }
-    /// From the set of all valid words in the dictionary used to construct the object,
+    /// Records the constraints implied by a hint without counting it as a guess: it isn't added to [`Puzzle::history`], the guess count is unchanged,
+    /// and contradictory hints are allowed (they just leave no feasible words). Nothing is filtered or propagated until the next query
+    /// (or [`Puzzle::force_reduce`]), so many hypothetical constraints can be batched and reduced once.
+    /// Since it isn't a guess, it can't be undone by itself, and [`Puzzle::undo`] of an earlier guess also discards it.
+    /// Fails only if the word is invalid or the hint is the wrong length.
+    pub fn apply_hint_raw<'b>(&mut self, word: &'b str, hint: &'b [Hint]) -> Result<(), GuessError<'b>> {
+        let encoded = self.encode(word)?;
+        if encoded.len() != hint.len() { return Err(GuessError::WrongHintLen { hint, expected_len: self.slots.len() }); }
+        let letters = self.dictionary.alphabet.chars();
+        let given = Arc::make_mut(&mut self.given);
+        Self::apply_hint(&mut given.slots, &mut given.letter_counts, encoded.as_ref(), hint, letters, &mut |_| ());
+        Self::apply_hint(&mut self.slots, &mut self.letter_counts, encoded.as_ref(), hint, letters, &mut |_| ());
+        self.dirty = true;
+        self.invalidate_reduced();
+        Ok(())
+    }
+    /// Equivalent to [`Puzzle::guess`], but returns the result as a new puzzle and leaves this one unchanged.
+    /// The new puzzle shares the word lists with this one until it is reduced, so making it is cheap,
+    /// and readers of this puzzle never see a partially updated state.
+    pub fn guessed<'b>(&self, word: &'b str, hint: &'b [Hint]) -> Result<Self, GuessError<'b>> {
+        let mut res = self.clone();
+        res.guess(word, hint)?;
+        Ok(res)
+    }
+    /// Equivalent to calling [`Puzzle::guess`] for each `(word, hint)` pair in order.
+    /// Every entry is validated before any are applied, so if any is invalid, returns [`Err`] and leaves the puzzle unchanged.
+    pub fn apply_history<'b, W: AsRef<str>, H: AsRef<[Hint]>>(&mut self, history: &'b [(W, H)]) -> Result<(), GuessError<'b>> {
+        self.apply_history_checked(history).map(|_| ())
+    }
+    /// Equivalent to [`Puzzle::apply_history`], but also checks each guess against the dictionary,
+    /// returning a warning for each one which is not in it rather than rejecting the history.
+    pub fn apply_history_checked<'b, W: AsRef<str>, H: AsRef<[Hint]>>(&mut self, history: &'b [(W, H)]) -> Result<Vec<HistoryWarning<'b>>, GuessError<'b>> {
+        let mut warnings = vec![];
+        let mut encoded = Vec::with_capacity(history.len());
+        for (word, hint) in history {
+            let (raw, hint) = (word.as_ref(), hint.as_ref());
+            let word = self.dictionary.encode_guess(raw, hint)?;
+            if self.dictionary.index_of(&word).is_none() {
+                warnings.push(HistoryWarning::UnknownWord { index: encoded.len(), word: history[encoded.len()].0.as_ref() });
+            }
+            encoded.push((word, hint));
+        }
+
+        for (word, hint) in encoded.iter() {
+            self.guess_lazy(word.as_ref(), hint);
+        }
+        self.check_invariants();
+        Ok(warnings)
+    }
+    /// Equivalent to [`Puzzle::guess`], but reports each step of reasoning to `trace` as it happens (e.g., for teaching).
+    pub fn guess_traced<'b, T: FnMut(TraceStep)>(&mut self, word: &'b str, hint: &'b [Hint], mut trace: T) -> Result<(), GuessError<'b>> {
+        let encoded = self.dictionary.encode_guess(word, hint)?;
+        self.guess_impl_traced(encoded.as_ref(), hint, &mut trace);
+        Ok(())
+    }
+    fn letter_index(&self, letter: char) -> Result<u8, ConstraintError> {
+        self.dictionary.alphabet.index_of(letter).ok_or(ConstraintError::NotInAlphabet { letter })
+    }
+    /// Recomputes the solve state from the given constraints after some of them were loosened.
+    fn rederive(&mut self) {
+        self.slots = self.given.slots.clone();
+        self.letter_counts = self.given.letter_counts;
+        self.feasible_words = self.all_words.clone();
+        self.reduce();
+        self.check_invariants();
+    }
+    /// Removes all constraints on the given letter, e.g., to recover from a mistyped hint.
+    /// The letter is allowed in every slot again (slots where it was marked correct are reopened to every letter)
+    /// and may occur any number of times.
+    pub fn relax_letter(&mut self, letter: char) -> Result<(), ConstraintError> {
+        let ch = self.letter_index(letter)?;
+        let given = Arc::make_mut(&mut self.given);
+        for slot in given.slots.iter_mut() {
+            if slot.len() == 1 && slot.contains(ch) {
+                for i in 0..self.dictionary.alphabet.chars().len() { slot.insert(i as u8); }
+            }
+            slot.insert(ch);
+        }
+        given.letter_counts[ch as usize] = (0, self.slots.len());
+        self.rederive();
+        Ok(())
+    }
+    /// Removes the upper bound on the number of occurrences of the given letter, without re-allowing it in any slots.
+    pub fn relax_letter_max(&mut self, letter: char) -> Result<(), ConstraintError> {
+        let ch = self.letter_index(letter)?;
+        Arc::make_mut(&mut self.given).letter_counts[ch as usize].1 = self.slots.len();
+        self.rederive();
+        Ok(())
+    }
+    /// Removes the lower bound on the number of occurrences of the given letter.
+    pub fn relax_letter_min(&mut self, letter: char) -> Result<(), ConstraintError> {
+        let ch = self.letter_index(letter)?;
+        Arc::make_mut(&mut self.given).letter_counts[ch as usize].0 = 0;
+        self.rederive();
+        Ok(())
+    }
+    /// Marks the given letter as not occurring in the answer at all, as if a guess had received [`Hint::Absent`] for it.
+    /// This is the opposite of [`Puzzle::relax_letter`], and no guess is counted.
+    pub fn ban_letter(&mut self, letter: char) -> Result<(), ConstraintError> {
+        let ch = self.letter_index(letter)?;
+        Arc::make_mut(&mut self.given).letter_counts[ch as usize].1 = 0;
+        self.rederive();
+        Ok(())
+    }
+    /// Allows the given letter in the given slot again (in addition to any letters already allowed there).
+    pub fn allow_letter(&mut self, letter: char, slot: usize) -> Result<(), ConstraintError> {
+        let ch = self.letter_index(letter)?;
+        if slot >= self.slots.len() { return Err(ConstraintError::SlotOutOfRange { slot, word_len: self.slots.len() }); }
+        Arc::make_mut(&mut self.given).slots[slot].insert(ch);
+        self.rederive();
+        Ok(())
+    }
+    /// Iterates over the letters which could still be in the given slot, in alphabet order (the same letters as the [`Display`](fmt::Display) output).
+    /// This reflects everything deduced from the hints so far, so it may be narrower than the hints alone imply.
+    pub fn allowed_letters(&self, slot: usize) -> Result<impl Iterator<Item = char> + 'a, ConstraintError> {
+        if slot >= self.slots.len() { return Err(ConstraintError::SlotOutOfRange { slot, word_len: self.slots.len() }); }
+        let letters = self.dictionary.alphabet.chars();
+        Ok(self.reduced().slots[slot].into_iter().map(move |x| letters[x as usize]))
+    }
+    /// Gets the inclusive `(min, max)` bounds on the number of occurrences of the given letter in the answer (the same bounds as the [`Display`](fmt::Display) output).
+    /// Unlike [`Puzzle::allowed_letters`], these only come from the hints, not from which words remain.
+    pub fn letter_bounds(&self, letter: char) -> Result<(usize, usize), ConstraintError> {
+        let ch = self.letter_index(letter)?;
+        Ok(self.reduced().letter_counts[ch as usize])
+    }
+    /// Checks if the word is consistent with all hints so far (and any edits, like [`Puzzle::relax_letter`]), e.g., to validate a guess in hard mode.
+    /// Unlike [`Puzzle::candidates`], the word need not be in the dictionary, so this only fails if the word is invalid.
+    pub fn is_candidate<'b>(&self, word: &'b str) -> Result<bool, WordError<'b>> {
+        Ok(self.given.admits(self.encode(word)?.as_ref()))
+    }
+    /// Gets the words from the dictionary which are consistent with all hints so far (i.e., could be the answer), in lexicographic order.
+    pub fn candidates(&self) -> Vec<&'a str> {
+        self.reduced().feasible_words.iter().map(|word| self.dictionary.word_str(word)).collect()
+    }
+    /// Iterates over the words from the dictionary which could still be the answer, in lexicographic order (the same words as [`Puzzle::candidates`]).
+    /// The words borrow from the dictionary rather than the puzzle, so the puzzle may be updated while they are still in use.
+    pub fn feasible_words(&self) -> impl Iterator<Item = &'a str> {
+        let (dictionary, words) = (self.dictionary, self.reduced().feasible_words.clone());
+        (0..words.len()).map(move |i| dictionary.word_str(&words[i]))
+    }
+    /// Gets whether the answer is known, still unknown, or impossible, based on the number of words which could be the answer.
+    pub fn status(&self) -> PuzzleStatus {
+        match self.reduced().feasible_words.len() {
+            0 => PuzzleStatus::Inconsistent,
+            1 => PuzzleStatus::Solved,
+            _ => PuzzleStatus::Unsolved,
+        }
+    }
+    /// Checks if exactly one word could be the answer (see [`Puzzle::status`]).
+    pub fn is_solved(&self) -> bool {
+        self.status() == PuzzleStatus::Solved
+    }
+    /// Checks if no word could be the answer (see [`Puzzle::status`]).
+    pub fn is_inconsistent(&self) -> bool {
+        self.status() == PuzzleStatus::Inconsistent
+    }
+    /// Gets the answer if exactly one word could be the answer, otherwise [`None`].
+    pub fn solution(&self) -> Option<&'a str> {
+        match self.reduced().feasible_words.as_slice() {
+            [word] => Some(self.dictionary.word_str(word)),
+            _ => None,
+        }
+    }
+    /// Computes the `(worst_case_remaining, avg_case_remaining)` score for making the given guess.
+    /// If the worst case exceeds `bound` (when given), the search is cut short and [`None`] is returned.
+    /// Also returns [`None`] if no hint is possible (an inconsistent puzzle).
+    fn score_guess(&self, guess: Word, bound: Option<u64>) -> Option<(u64, Average)> {
+        self.score_guess_counted(guess, bound, &mut SearchCounters::default())
+    }
+    /// Equivalent to [`Puzzle::score_guess`], but adds the work done to `counters`.
+    fn score_guess_counted(&self, guess: Word, bound: Option<u64>, counters: &mut SearchCounters) -> Option<(u64, Average)> {
+        counters.evaluated += 1;
+        let mut worst: u64 = 0;
+        let mut worst_avg: (u64, u64) = (0, 0);
+
+        let hint_order = [Hint::Present, Hint::Absent, Hint::Correct]; // experimentally fastest expansion order with pruning
+
+        for response in iter::once(hint_order).cycle().take(self.slots.len()).multi_cartesian_product() {
+            if contradictory_slot(&guess, &response).is_some() { continue; } // no answer gives this hint, but the constraints alone may not rule it out
+            counters.patterns += 1;
+            let mut cpy = self.clone();
+            cpy.assume_impl(guess, &response);
+            let possible = cpy.feasible_words.len() as u64;
+            if possible == 0 { continue; }
+
+            worst = worst.max(possible);
+            worst_avg.0 += possible;
+            worst_avg.1 += 1;
+
+            if let Some(bound) = bound {
+                if worst > bound {
+                    counters.prunes += 1;
+                    return None;
+                }
+            }
+        }
+        if worst == 0 { return None; }
+        debug_assert_ne!(worst_avg.1, 0);
+
+        Some((worst, Average::new(worst_avg.0, worst_avg.1)))
+    }
+    /// Counts the feasible words which would produce each hint for the given guess, as in [`BestGuess::partition`].
+    fn partition(&self, guess: Word) -> Vec<(HintPattern, u64)> {
+        let mut counts: HashMap<u32, u64> = HashMap::new();
+        for answer in self.feasible_words.iter() {
+            *counts.entry(hint_code(&guess, answer)).or_insert(0) += 1;
+        }
+        let mut res: Vec<_> = counts.into_iter().map(|(code, n)| (HintPattern::from_code_unchecked(code, guess.len()), n)).collect();
+        res.sort_by_key(|&(pattern, n)| (Reverse(n), pattern.code()));
+        res
+    }
+    /// From the set of all valid words in the dictionary used to construct the object which are admitted by `filter`,
     /// finds the word which has the best worst-case (over the set of consistent hints) number of possible solutions after using it as a guess.
-    /// In the event of ties, the word with the best average-case is selected, and further ties are broken by taking the first word in the lexicographic ordering.
+    /// In the event of ties, the word with the best average-case is selected, then words which could be the answer are preferred,
+    /// and further ties are broken by taking the first word in the lexicographic ordering (so the result does not depend on `threads`).
     /// If there are no possible solutions (an inconsistent puzzle), returns [`Err`].
-    /// Returns a tuple `(word, worst_case_remaining, avg_case_remaining)`.
+    /// If `filter` rejects every word in the dictionary, returns [`SolveErr::NoAdmissibleGuess`].
+    /// The worst and average cases in the result are computed exactly (see [`Average`]).
+    ///
+    /// When few enough words remain (see [`Puzzle::set_endgame_threshold`]), the guess is instead chosen by an exact search
+    /// which minimizes the average number of guesses needed within the remaining turns (see [`Puzzle::set_max_guesses`]),
+    /// in which case [`BestGuess::endgame`] is set. The filter only restricts the guess made this turn.
+    /// With only one guess left, only words which could be the answer are suggested (unless the filter rejects all of them).
     /// 
     /// Because this logic can be slow, it is performed in parallel over all the words in the dictionary.
     /// The `threads` input specifies the number of threads to use.
     /// If `threads` is zero, it is defaulted to `1`.
-    pub fn best_guess(&self, mut threads: usize) -> Result<(String, u64, f64), SolveErr> {
+    /// For more settings, see [`Puzzle::best_guess_opts`].
+    pub fn best_guess<F: CandidateFilter>(&self, threads: usize, filter: F) -> Result<BestGuess, SolveErr> {
+        self.best_guess_opts(&SolveOptions::new().with_threads(threads).with_filter(&filter))
+    }
+    /// Equivalent to [`Puzzle::best_guess`], but also considers the given extra guesses, which need not be in the dictionary
+    /// (e.g., words a wordle clone accepts as guesses but which are missing from the word list).
+    /// Extra guesses are trimmed and lowercased before use, must still be valid words for the dictionary (or [`SolveErr::InvalidExtraGuess`] is returned),
+    /// and are subject to `filter` like any other guess. They are never treated as possible answers.
+    pub fn best_guess_with_extra<F: CandidateFilter>(&self, threads: usize, filter: F, extra_guesses: &[&str]) -> Result<BestGuess, SolveErr> {
+        self.best_guess_limited(threads, filter, extra_guesses, &ComputeLimits::default())
+    }
+    pub(crate) fn best_guess_limited<F: CandidateFilter>(&self, threads: usize, filter: F, extra_guesses: &[&str], limits: &ComputeLimits) -> Result<BestGuess, SolveErr> {
+        let mut extra = Vec::with_capacity(extra_guesses.len());
+        for word in extra_guesses {
+            let normalized = word.trim().to_lowercase();
+            match self.encode(&normalized) {
+                Ok(x) => extra.push(x),
+                Err(_) => return Err(SolveErr::InvalidExtraGuess(word.to_string())),
+            }
+        }
+        let mut extra: Vec<Word> = extra.iter().map(OwnedWord::as_ref).filter(|w| self.all_words.binary_search(w).is_err()).collect();
+        extra.sort_unstable();
+        extra.dedup();
+
+        self.reduced().shortened().best_guess_impl(threads, filter, &extra, limits, &mut |_| ())
+    }
+    /// Equivalent to [`Puzzle::best_guess`], but reports the progress of the search to `progress` as it happens (at most once per percent).
+    /// Progress is only reported when a search is actually performed (i.e., not when the answer is known or the endgame solver is used),
+    /// and the last update has every guess done.
+    pub fn best_guess_progress<F: CandidateFilter, P: FnMut(&Progress) + Send>(&self, threads: usize, filter: F, mut progress: P) -> Result<BestGuess, SolveErr> {
+        self.reduced().best_guess_impl(threads, filter, &[], &ComputeLimits::default(), &mut progress)
+    }
+    fn best_guess_impl<F: CandidateFilter>(&self, threads: usize, filter: F, extra: &[Word<'a>], limits: &ComputeLimits, progress: &mut (dyn FnMut(&Progress) + Send)) -> Result<BestGuess, SolveErr> {
         if self.slots.iter().any(BitSet32::is_empty) {
             return Err(SolveErr::Inconsistent);
         }
         if self.slots.iter().all(|s| s.len() == 1) {
-            return Ok((self.slots.iter().map(|&s| char::from_u32(s.into_iter().next().unwrap() as u32 + 97).unwrap()).collect(), 0, 0.0));
+            let answer: Vec<u8> = self.slots.iter().map(|&s| s.into_iter().next().unwrap()).collect();
+            let word = self.decode(&answer);
+            if filter.admits(&word) {
+                let partition = vec![(HintPattern::from_hints(&vec![Hint::Correct; answer.len()]), 1)];
+                return Ok(BestGuess { word, worst_case: 0, average: Average::ZERO, feasible: true, endgame: false, expected_guesses: None, counters: SearchCounters::default(), approximate: false, partition });
+            }
         }
-        threads = threads.max(1);
+        let threads = threads.max(1);
 
-        let best = crossbeam::scope(|scope| {
-            let guesses = Arc::new(Mutex::new(self.all_words.iter().copied().fuse())); // a guess doesn't have to be a feasible solution
+        let mut admissible: Vec<_> = self.all_words.iter().chain(extra).copied().filter(|w| filter.admits(&self.decode(w))).collect();
+        if admissible.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+        if self.turns_left() == 1 && admissible.iter().any(|&w| self.is_feasible(w)) {
+            admissible.retain(|&w| self.is_feasible(w)); // an information-gathering guess can't win on the last turn
+        }
+
+        if let Some((guess, expected)) = self.endgame_guess(&admissible) {
+            let (worst_case, average) = self.score_guess(guess, None).ok_or(SolveErr::Inconsistent)?;
+            return Ok(BestGuess { word: self.decode(&guess), worst_case, average, feasible: self.is_feasible(guess), endgame: true, expected_guesses: Some(expected), counters: SearchCounters::default(), approximate: false, partition: self.partition(guess) });
+        }
+
+        self.drop_equivalent_guesses(&mut admissible);
+        let (ranked, counters, truncated) = self.rank_admissible_limited(threads, &admissible, 1, limits, progress);
+        match ranked.first() {
+            Some(&((worst_case, average), infeasible, guess)) => Ok(BestGuess { word: self.decode(&guess), worst_case, average, feasible: !infeasible, endgame: false, expected_guesses: None, counters, approximate: truncated, partition: self.partition(guess) }),
+            None if truncated => {
+                // out of time before scoring anything, so fall back to the letter coverage heuristic
+                let guess = self.coverage_ranked_among(&admissible, 1)[0];
+                let (worst_case, average) = self.score_guess(guess, None).ok_or(SolveErr::Inconsistent)?;
+                Ok(BestGuess { word: self.decode(&guess), worst_case, average, feasible: self.is_feasible(guess), endgame: false, expected_guesses: None, counters, approximate: true, partition: self.partition(guess) })
+            }
+            None => Err(SolveErr::Inconsistent),
+        }
+    }
+    /// Removes guesses which are equivalent to an earlier guess in the list, and so can never be strictly better.
+    /// A letter which occurs in no feasible word is always marked absent and doesn't affect the hints for other letters,
+    /// so two guesses which differ only by swapping such letters (e.g., anagrams over the dead letters) give the same hint for every answer.
+    /// Such guesses also can't be the answer, so keeping the first one preserves the tie-breaking of [`Puzzle::best_guess`].
+    fn drop_equivalent_guesses(&self, guesses: &mut Vec<Word<'a>>) {
+        let mut live = BitSet32::new();
+        for word in self.feasible_words.iter() {
+            for &ch in word.iter() { live.insert(ch); }
+        }
+        if self.dictionary.alphabet.chars().len() - (live.len() as usize) < 2 { return } // need at least two dead letters to swap
+
+        let mut seen = HashSet::new();
+        guesses.retain(|word| {
+            let key: Vec<u8> = word.iter().map(|&ch| if live.contains(ch) { ch } else { u8::MAX }).collect();
+            seen.insert(key)
+        });
+    }
+    /// Ranks the words in the dictionary which are admitted by `filter` by the same criteria as [`Puzzle::best_guess`] (without the endgame solver),
+    /// returning the best `n` from best to worst. Fewer than `n` words are returned if fewer are admitted.
+    /// Only the best `n` guesses seen so far are kept during the search, so asking for all of them (e.g., with [`usize::MAX`]) is fine.
+    /// The errors and meaning of `threads` are the same as for [`Puzzle::best_guess`].
+    pub fn rank_guesses<F: CandidateFilter>(&self, threads: usize, filter: F, n: usize) -> Result<Vec<RankedGuess>, SolveErr> {
+        let this = self.reduced();
+        if this.slots.iter().any(BitSet32::is_empty) {
+            return Err(SolveErr::Inconsistent);
+        }
+        let admissible: Vec<_> = this.all_words.iter().copied().filter(|w| filter.admits(&this.decode(w))).collect();
+        if admissible.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+        Ok(this.rank_admissible(threads.max(1), &admissible, n, &mut |_| ()).0.into_iter().map(|((worst_case, average), infeasible, guess)| {
+            RankedGuess { word: this.decode(&guess), worst_case, average, feasible: !infeasible }
+        }).collect())
+    }
+    /// Scores a single guess by the same criteria as [`Puzzle::best_guess`] (without the endgame solver), e.g., to compare the guess a user
+    /// intends to make with the suggestions. The guess may be any valid word, even one outside the dictionary (which can't be the answer).
+    /// Returns [`Err`] if the word is invalid, or [`None`] if no word could be the answer (an inconsistent puzzle).
+    pub fn evaluate_guess<'b>(&self, word: &'b str) -> Result<Option<RankedGuess>, WordError<'b>> {
+        let guess = self.encode(word)?;
+        let this = self.reduced();
+        Ok(this.score_guess(guess.as_ref(), None).map(|(worst_case, average)| {
+            RankedGuess { word: this.decode(&guess), worst_case, average, feasible: this.is_feasible(guess.as_ref()) }
+        }))
+    }
+    /// Gets the best `n` guesses from the whole dictionary with their worst and average case scores, from best to worst,
+    /// so that a user can pick among good alternatives (e.g., a word they actually know).
+    /// This is [`Puzzle::rank_guesses`] with [`AnyWord`], and fails under the same conditions.
+    pub fn top_guesses(&self, n: usize, threads: usize) -> Result<Vec<RankedGuess>, SolveErr> {
+        self.rank_guesses(threads, AnyWord, n)
+    }
+    /// Scores the admissible guesses in parallel and returns the best `n` in order, ranked by `((worst case remaining, avg case remaining), can't be answer flag, guess)`.
+    /// This is a total order, so the result doesn't depend on how the guesses were divided among threads.
+    /// Also returns the work done, summed over all threads.
+    /// Each thread only keeps its best `n` guesses so far (see [`push_bounded`]), so memory use doesn't grow with the number of guesses scored.
+    fn rank_admissible(&self, threads: usize, admissible: &[Word<'a>], n: usize, progress: &mut (dyn FnMut(&Progress) + Send)) -> (Vec<RankKey<'a>>, SearchCounters) {
+        let (ranked, counters, _) = self.rank_admissible_limited(threads, admissible, n, &ComputeLimits::default(), progress);
+        (ranked, counters)
+    }
+    /// Equivalent to [`Puzzle::rank_admissible`], but stops scoring guesses once a limit is reached, in which case the last output is `true`.
+    fn rank_admissible_limited(&self, threads: usize, admissible: &[Word<'a>], n: usize, limits: &ComputeLimits, progress: &mut (dyn FnMut(&Progress) + Send)) -> (Vec<RankKey<'a>>, SearchCounters, bool) {
+        if n == 0 { return (vec![], SearchCounters::default(), false) }
+        let start = Instant::now();
+        let started = AtomicU64::new(0);
+        let truncated = AtomicBool::new(false);
+        let mut counters = SearchCounters::default();
+        let progress = Mutex::new(SearchProgress::new(admissible.len(), progress));
+        let capacity = n.min(admissible.len());
+        // with limits, the search may stop early, so the most promising guesses (by letter coverage) are scored first
+        let ordered = if *limits != ComputeLimits::default() { self.coverage_ranked_among(admissible, admissible.len()) } else { admissible.to_vec() };
+        let ranked = crossbeam::scope(|scope| {
+            let guesses = Arc::new(Mutex::new(ordered.into_iter().fuse())); // a guess doesn't have to be a feasible solution
             let threads: Vec<_> = (0..threads).map(|_| {
                 let guesses = guesses.clone();
+                let progress = &progress;
+                let (started, truncated) = (&started, &truncated);
                 let this = self.clone();
                 scope.spawn(move |_| {
-                    let mut best: Option<(Word, (u64, FloatOrd<f64>), bool)> = None; // (guess, (worst case remaining, avg case remaining), could be answer flag)
-                    'next_word: loop {
+                    let mut best = BinaryHeap::with_capacity(capacity);
+                    let mut counters = SearchCounters::default();
+                    loop {
                         let guess = match guesses.lock().unwrap().next() {
                             Some(x) => x,
                             None => break,
                         };
-
-                        let mut worst: u64 = 0;
-                        let mut worst_avg: (u64, u64) = (0, 0);
-
-                        let hint_order = [Hint::Present, Hint::Absent, Hint::Correct]; // experimentally fastest expansion order with pruning
-
-                        'next_response: for response in iter::once(hint_order).cycle().take(this.slots.len()).multi_cartesian_product() {
-                            let mut cpy = this.clone();
-                            cpy.guess_impl(guess, &response);
-                            let possible = cpy.feasible_words.len() as u64;
-                            if possible == 0 { continue 'next_response; }
-
-                            worst = worst.max(possible);
-                            worst_avg.0 += possible;
-                            worst_avg.1 += 1;
-
-                            if let Some(prev) = best {
-                                if worst > prev.1.0 { continue 'next_word; }
-                            }
+                        if limits.reached(start.elapsed(), started.fetch_add(1, Ordering::Relaxed)) {
+                            truncated.store(true, Ordering::Relaxed);
+                            break;
                         }
-                        if worst == 0 { continue 'next_word; }
-                        debug_assert_ne!(worst_avg.1, 0);
 
-                        let score = (worst, FloatOrd(worst_avg.0 as f64 / worst_avg.1 as f64));
-                        let replace = match best {
-                            None => true,
-                            Some(prev) => score < prev.1 || (score == prev.1 && !prev.2),
+                        let bound = if best.len() == n { best.peek().map(|x: &RankKey| x.0.0) } else { None };
+                        let score = match this.score_guess_counted(guess, bound, &mut counters) {
+                            Some(x) => x,
+                            None => { progress.lock().unwrap().finish_one(&this, None); continue }
                         };
-                        if replace { best = Some((guess, score, this.could_be(guess))); }
+                        let key = (score, !this.is_feasible(guess), guess);
+                        progress.lock().unwrap().finish_one(&this, Some(key));
+                        push_bounded(&mut best, key, n);
                     }
-                    best
+                    (best, counters)
                 })
             }).collect();
 
-            threads.into_iter().filter_map(|t| t.join().unwrap()).min_by_key(|&(guess, score, cbf)| (score, if cbf { 0 } else { 1 }, guess))
+            let mut ranked = BinaryHeap::with_capacity(capacity);
+            for thread in threads {
+                let (best, thread_counters) = thread.join().unwrap();
+                for key in best { push_bounded(&mut ranked, key, n); }
+                counters += thread_counters;
+            }
+            ranked
         }).unwrap();
-
-        match best {
-            Some(x) => Ok((x.0.iter().map(|&c| char::from_u32(c as u32 + 97).unwrap()).collect(), x.1.0, x.1.1.0)),
-            None => Err(SolveErr::Inconsistent),
+        (ranked.into_sorted_vec(), counters, truncated.into_inner())
+    }
+    /// Gets the next guess to make according to the given [`Strategy`].
+    /// The `threads` input is forwarded to [`Puzzle::best_guess`] for strategies which perform a search.
+    pub fn suggest(&self, strategy: &Strategy, threads: usize) -> Result<String, SolveErr> {
+        match strategy {
+            Strategy::Fixed(word) => Ok(word.clone()),
+            Strategy::Minimax => Ok(self.best_guess(threads, AnyWord)?.word),
+            Strategy::FeasibleOnly => Ok(self.best_guess(threads, self.feasible_filter())?.word),
+            Strategy::ProbTwoLeft { k } => Ok(self.best_guess_prob_left(threads, AnyWord, *k, &Prior::uniform(self.dictionary))?.word),
+            Strategy::Entropy => Ok(self.best_guess_entropy(threads, AnyWord)?.word),
+            Strategy::Lookahead { depth, breadth } => Ok(self.best_guess_lookahead(threads, AnyWord, *depth, *breadth)?.word),
+            Strategy::Exact => Ok(self.best_guess_exact(threads, AnyWord)?.word),
+            Strategy::ExpectedRemaining => Ok(self.best_guess_weighted(threads, AnyWord, &Prior::uniform(self.dictionary))?.word),
         }
     }
 }
 impl fmt::Display for Puzzle<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let letters = "abcdefghijklmnopqrstuvwxyz";
-        let mut mapped = BTreeSet::new();
+        let this = self.reduced();
+        let letters = this.dictionary.alphabet.chars();
 
-        for (i, &slot) in self.slots.iter().enumerate() {
-            mapped.clear();
-            for v in slot { mapped.insert(&letters[v as usize..v as usize + 1]); }
-            let txt = mapped.iter().fold(String::new(), |acc, v| acc + v);
+        for (i, &slot) in this.slots.iter().enumerate() {
+            let txt: String = slot.into_iter().map(|v| letters[v as usize]).collect();
             writeln!(f, "{}: {}", i, txt)?;
         }
 
         write!(f, "{{ ").unwrap();
-        for (counts, letter) in iter::zip(&self.letter_counts, letters.chars()) {
+        for (counts, &letter) in iter::zip(&this.letter_counts, letters) {
             write!(f, "{}: {}..={}, ", letter, counts.0, counts.1).unwrap();
         }
         writeln!(f, "}}").unwrap();
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Adds `key` to a max-heap which holds the smallest (best) `n` keys seen so far, dropping the largest key if it would grow past `n`.
+fn push_bounded<T: Ord>(heap: &mut BinaryHeap<T>, key: T, n: usize) {
+    if heap.len() < n {
+        heap.push(key);
+    } else if let Some(mut worst) = heap.peek_mut() {
+        if key < *worst { *worst = key; }
+    }
+}
+
+/// Plays a full game against the given `answer`, choosing a [`Strategy`] for each turn from `policy`.
+/// Returns the sequence of guesses made, the last of which is the answer.
+/// If the policy repeats a guess which is not the answer, the game can never finish and [`PlayError::Stalled`] is returned.
+pub fn play<'a, P: TurnPolicy>(dictionary: &Dictionary, answer: &'a str, policy: P, threads: usize) -> Result<Vec<String>, PlayError<'a>> {
+    dictionary.alphabet.encode(dictionary.word_len, answer).map_err(PlayError::InvalidAnswer)?;
+
+    let mut puzzle = Puzzle::new(dictionary);
+    #[cfg(feature = "debug-invariants")]
+    puzzle.set_known_answer(answer);
+    let mut guesses: Vec<String> = vec![];
+    loop {
+        let turn = guesses.len();
+        let guess = puzzle.suggest(&policy.strategy(turn, puzzle.feasible_words.len()), threads)?;
+        if guess == answer {
+            guesses.push(guess);
+            return Ok(guesses);
+        }
+        if guesses.contains(&guess) {
+            return Err(PlayError::Stalled { turn, word: guess });
+        }
+
+        let hint = match dictionary.alphabet.get_hint(&guess, answer) {
+            Ok(x) => x,
+            Err(_) => return Err(PlayError::InvalidGuess { turn, word: guess }),
+        };
+        if puzzle.guess(&guess, &hint).is_err() {
+            return Err(PlayError::InvalidGuess { turn, word: guess });
+        }
+        puzzle.force_reduce();
+        guesses.push(guess);
+    }
+}
+#[test]
+fn test_best_guess_filter() {
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "match", "patch", "watch", "chimp", "blimp", "lower"]).unwrap();
+    let puzzle = Puzzle::new(&dict);
+
+    let BestGuess { word: unfiltered, worst_case: worst, .. } = puzzle.best_guess(2, AnyWord).unwrap();
+    let BestGuess { word: filtered, worst_case: filtered_worst, .. } = puzzle.best_guess(2, |w: &str| w != unfiltered && w.ends_with("atch")).unwrap();
+    assert!(filtered.ends_with("atch") && filtered != unfiltered);
+    assert!(filtered_worst >= worst);
+
+    let mut solved = puzzle.clone();
+    solved.guess("watch", &get_hint("watch", "lower").unwrap()).unwrap();
+    assert_eq!(solved.best_guess(1, AnyWord).unwrap().word, "lower");
+    assert_eq!(solved.best_guess(1, |w: &str| w == "chimp").unwrap().word, "chimp");
+
+    assert!(matches!(puzzle.best_guess(1, |_: &str| false), Err(SolveErr::NoAdmissibleGuess)));
+}
+
+#[test]
+fn test_best_guess_partition() {
+    let dict = testkit::dictionary(testkit::DUPLICATE_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    for threshold in [0, usize::MAX] {
+        puzzle.set_endgame_threshold(threshold);
+        let best = puzzle.best_guess(2, AnyWord).unwrap();
+        let candidates = puzzle.candidates();
+        let mut expected: Vec<_> = partition_counts(&best.word, &candidates).unwrap().into_iter().map(|(p, n)| (p, n as u64)).collect();
+        expected.sort_by_key(|&(p, n)| (Reverse(n), p.code()));
+        assert_eq!(best.partition, expected);
+        assert_eq!(best.partition.iter().map(|x| x.1).sum::<u64>(), candidates.len() as u64);
+    }
+
+    let answer = dict.words().next().unwrap();
+    puzzle.guess(answer, &[Hint::Correct; 5]).unwrap();
+    assert_eq!(puzzle.best_guess(1, AnyWord).unwrap().partition, &[(HintPattern::from_hints(&[Hint::Correct; 5]), 1)]);
+}
+
+#[test]
+fn test_best_guess_with_extra() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["wield"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("wield", &get_hint("wield", "catch").unwrap()).unwrap();
+    assert_eq!(puzzle.candidates(), &["batch", "catch", "hatch", "match", "patch"]);
+
+    for threshold in [0, usize::MAX] {
+        puzzle.set_endgame_threshold(threshold);
+        let best = puzzle.best_guess_with_extra(1, AnyWord, &[" BLIMP ", "blimp", "zatch"]).unwrap();
+        assert_eq!((best.word.as_str(), best.worst_case, best.endgame), ("blimp", 2, threshold != 0));
+        assert_eq!(puzzle.best_guess_with_extra(1, AnyWord, &["batch"]).unwrap(), puzzle.best_guess(1, AnyWord).unwrap());
+        assert_eq!(puzzle.best_guess_with_extra(2, AnyWord, &["batch"]).unwrap().word, puzzle.best_guess(2, AnyWord).unwrap().word);
+        assert_eq!(puzzle.best_guess_with_extra(1, |w: &str| w != "blimp", &["blimp"]).unwrap(), puzzle.best_guess(1, AnyWord).unwrap());
+    }
+    assert_eq!(puzzle.candidates().len(), 5);
+
+    // "zatch" fits the hints, but it can't be the answer, so it's no use on the last turn
+    let mut last = puzzle.clone();
+    last.set_max_guesses(2);
+    assert_eq!(last.best_guess_with_extra(1, |w: &str| w == "zatch" || w == "batch", &["zatch"]).unwrap().word, "batch");
+    assert_eq!(last.best_guess_with_extra(1, |w: &str| w == "zatch", &["zatch"]).unwrap().word, "zatch");
+
+    assert!(matches!(puzzle.best_guess_with_extra(1, AnyWord, &["blimp", "hat"]), Err(SolveErr::InvalidExtraGuess(x)) if x == "hat"));
+    assert!(matches!(puzzle.best_guess_with_extra(1, AnyWord, &["h4tch"]), Err(SolveErr::InvalidExtraGuess(_))));
+}
+
+#[test]
+fn test_apply_history() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield"])).unwrap();
+    let history: Vec<_> = ["wield", "blimp"].into_iter().map(|w| (w, get_hint(w, "hatch").unwrap())).collect();
+
+    let mut sequential = Puzzle::new(&dict);
+    for (word, hint) in history.iter() {
+        sequential.guess(word, hint).unwrap();
+    }
+    let mut batch = Puzzle::new(&dict);
+    batch.apply_history(&history).unwrap();
+    assert_eq!(batch.candidates(), sequential.candidates());
+    assert_eq!(batch.candidates(), &["catch", "hatch"]);
+    assert_eq!(batch.turns_left(), sequential.turns_left());
+    assert_eq!(batch.to_string(), sequential.to_string());
+
+    let mut bad = history.clone();
+    bad.push(("hat", get_hint("latch", "hatch").unwrap()));
+    let mut puzzle = Puzzle::new(&dict);
+    assert!(matches!(puzzle.apply_history(&bad), Err(GuessError::WrongWordLen { word: "hat", .. })));
+    assert_eq!(puzzle.candidates().len(), dict.len());
+    assert_eq!(puzzle.turns_left(), Game::DEFAULT_MAX_GUESSES);
+
+    // guesses from a clone with a bigger word list are applied with a warning
+    let history: Vec<_> = ["wield", "bloat", "aahed"].into_iter().map(|w| (w, get_hint(w, "hatch").unwrap())).collect();
+    let mut puzzle = Puzzle::new(&dict);
+    assert_eq!(puzzle.apply_history_checked(&history).unwrap(), &[
+        HistoryWarning::UnknownWord { index: 1, word: "bloat" },
+        HistoryWarning::UnknownWord { index: 2, word: "aahed" },
+    ]);
+    assert_eq!(puzzle.candidates(), &["catch", "hatch", "match", "patch"]);
+    assert_eq!(puzzle.turns_left(), Game::DEFAULT_MAX_GUESSES - 3);
+    assert!(matches!(Puzzle::new(&dict).apply_history_checked(&bad), Err(GuessError::WrongWordLen { .. })));
+}
+
+#[test]
+fn test_feasible_words() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    assert!(puzzle.feasible_words().eq(puzzle.candidates()));
+    puzzle.guess("batch", &get_hint("batch", "watch").unwrap()).unwrap();
+    let feasible = puzzle.feasible_words();
+    assert!(puzzle.feasible_words().eq(puzzle.candidates()));
+
+    // the iterator outlives changes to the puzzle
+    puzzle.guess("latch", &get_hint("latch", "watch").unwrap()).unwrap();
+    puzzle.force_reduce();
+    assert!(feasible.eq(["catch", "hatch", "latch", "match", "patch", "watch"]));
+    assert!(puzzle.feasible_words().eq(["catch", "hatch", "match", "patch", "watch"]));
+}
+
+#[test]
+fn test_reduced_cache() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("batch", &get_hint("batch", "watch").unwrap()).unwrap();
+
+    // queries on a dirty puzzle share one reduced copy
+    let reduced: *const Puzzle = puzzle.reduced();
+    assert!(std::ptr::eq(reduced, puzzle.reduced()) && !std::ptr::eq(reduced, &puzzle));
+    assert_eq!(puzzle.candidates(), &["catch", "hatch", "latch", "match", "patch", "watch"]);
+
+    // but any change discards it
+    puzzle.set_max_guesses(2);
+    assert_eq!(puzzle.reduced().turns_left(), 1);
+    puzzle.guess("latch", &get_hint("latch", "watch").unwrap()).unwrap();
+    assert_eq!(puzzle.candidates(), &["catch", "hatch", "match", "patch", "watch"]);
+    assert_eq!(puzzle.clone().candidates(), puzzle.candidates());
+    puzzle.force_reduce();
+    assert!(std::ptr::eq(puzzle.reduced(), &puzzle));
+}
+
+#[test]
+fn test_validate_history() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let hint = get_hint("latch", "hatch").unwrap();
+    assert!(dict.validate_history(&[("latch", &hint[..]), ("aahed", &hint[..])]).is_ok());
+    assert!(dict.validate_history::<&str, &[Hint]>(&[]).is_ok());
+
+    let history = [("latch", &hint[..]), ("hat", &hint[..])];
+    let e = dict.validate_history(&history).unwrap_err();
+    assert!(matches!(e, HistoryError { turn: 1, error: GuessError::WrongWordLen { word: "hat", expected_len: 5 } }));
+    assert_eq!(e.to_string(), "guess 2: 'hat' has 3 letters, but words have 5");
+    let history = [("latch", &hint[..3])];
+    let e = dict.validate_history(&history).unwrap_err();
+    assert!(matches!(e.error, GuessError::WrongHintLen { expected_len: 5, .. }));
+    assert_eq!(e.to_string(), "guess 1: the hint has 3 letters, but words have 5");
+    let history = [("latch", &hint[..]), ("latch", &hint[..]), ("LATCH", &hint[..])];
+    let e = dict.validate_history(&history).unwrap_err();
+    assert!(matches!(e, HistoryError { turn: 2, error: GuessError::NotInAlphabet { .. } }));
+    let contradictory = [Hint::Absent, Hint::Absent, Hint::Absent, Hint::Absent, Hint::Present];
+    assert!(matches!(dict.validate_guess("aaaaa", &contradictory), Err(GuessError::ContradictoryHint { slot: 4, .. })));
+    assert!(dict.validate_guess("zzzzz", &[Hint::Absent; 5]).is_ok());
+}
+
+#[test]
+fn test_contradictory_hint() {
+    use Hint::*;
+    let dict = Dictionary::with_words(5, testkit::DUPLICATE_WORDS.iter().copied().chain(["geese", "eerie", "aaaaa"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    // extra copies of a letter are marked present from left to right, so a present copy can't follow an absent one
+    assert!(matches!(puzzle.guess("geese", &[Absent, Absent, Present, Absent, Absent]), Err(GuessError::ContradictoryHint { word: "geese", slot: 2, .. })));
+    assert!(matches!(puzzle.guess_traced("eerie", &[Correct, Absent, Absent, Absent, Present], |_| ()), Err(GuessError::ContradictoryHint { slot: 4, .. })));
+    // a present letter has to be somewhere else, but every other slot guessed it
+    assert!(matches!(puzzle.guess("aaaaa", &[Absent, Present, Absent, Absent, Absent]), Err(GuessError::ContradictoryHint { slot: 1, .. })));
+    let history = [("geese", get_hint("geese", "eerie").unwrap()), ("eerie", vec![Present, Absent, Absent, Absent, Present])];
+    assert!(matches!(puzzle.apply_history(&history), Err(GuessError::ContradictoryHint { word: "eerie", slot: 4, .. })));
+    assert_eq!((puzzle.guess_count(), puzzle.candidates().len()), (0, dict.len()));
+
+    // every hint the game could give is accepted
+    for guess in dict.words() {
+        for answer in dict.words() {
+            let hint = get_hint(guess, answer).unwrap();
+            assert_eq!(contradictory_slot(&dict.alphabet.encode(5, guess).unwrap().0, &hint), None, "{} {}", guess, answer);
+        }
+    }
+    assert_eq!(contradictory_slot(&dict.alphabet.encode(5, "geese").unwrap().0, &[Present; 5]), Some(4));
+    puzzle.guess("geese", &[Present, Present, Absent, Absent, Absent]).unwrap();
+}
+
+#[test]
+fn test_best_guess_thread_independent() {
+    let dict = testkit::dictionary(testkit::DUPLICATE_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_endgame_threshold(0);
+    let mut states = vec![puzzle.clone()];
+    for answer in ["geese", "otter", "babes"] {
+        let mut state = puzzle.clone();
+        state.guess("there", &get_hint("there", answer).unwrap()).unwrap();
+        states.push(state);
+    }
+    let counters = puzzle.best_guess(1, AnyWord).unwrap().counters;
+    assert_eq!(counters.evaluated, dict.len() as u64);
+    assert!(counters.patterns >= counters.evaluated && counters.prunes < counters.evaluated);
+    let best = puzzle.best_guess(1, AnyWord).unwrap();
+    assert_eq!(BestGuess { counters: SearchCounters::default(), ..best.clone() }, best); // counters are left out of equality
+    for state in states.iter() {
+        let expected = state.best_guess(1, AnyWord).unwrap();
+        for threads in 2..=8 {
+            let best = state.best_guess(threads, AnyWord).unwrap();
+            assert_eq!((best.word, best.worst_case, best.average), (expected.word.clone(), expected.worst_case, expected.average), "threads = {}", threads);
+        }
+    }
+}
+
+#[test]
+fn test_propagate_counts() {
+    let dict = Dictionary::with_words(5, ["sissy", "sushi", "sassy", "missy", "asses"]).unwrap();
+    let mut steps = vec![];
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess_traced("sissy", &get_hint("sissy", "sushi").unwrap(), |x| steps.push(x)).unwrap();
+    assert_eq!(puzzle.candidates(), &["sushi"]);
+    // an absent later occurrence can't be in its own slot (or it would be correct), which also caps the count at the two fixed 's'
+    assert!(steps.contains(&TraceStep::NotInSlot { slot: 3, letter: 's' }));
+    assert!(steps.contains(&TraceStep::MaxReached { slot: 4, letter: 's' }));
+
+    // capping 'a' fixes slot 1 to 'd', which must count as placed before reserving the last open slot for needed letters
+    let dict = Dictionary::with_words(5, ["bdabb", "ddabb", "dabbb", "badbb", "bbabb"]).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    for guess in ["ccdbb", "abaac"] {
+        puzzle.guess(guess, &get_hint(guess, "bdabb").unwrap()).unwrap();
+    }
+    assert_eq!(puzzle.candidates(), &["bdabb", "ddabb"]);
+
+    // every slot is needed for a present letter, so no other letter fits, even though the dictionary doesn't rule it out
+    let dict = Dictionary::with_words(5, ["abcde", "bcdea", "abzzz", "zzzzz"]).unwrap();
+    let mut steps = vec![];
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess_traced("abcde", &[Hint::Present; 5], |x| steps.push(x)).unwrap();
+    assert_eq!(puzzle.candidates(), &["bcdea"]);
+    assert_eq!(steps.iter().filter(|x| matches!(x, TraceStep::Reserved { letter: 'z', .. })).count(), 5);
+
+    // a word missing a letter which must occur is not feasible
+    let dict = Dictionary::with_words(5, ["slate", "moody"]).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("crane", &get_hint("crane", "slate").unwrap()).unwrap();
+    assert_eq!(puzzle.candidates(), &["slate"]);
+}
+
+#[test]
+fn test_banned_letters() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    let (b, w) = (dict.alphabet.index_of('b').unwrap(), dict.alphabet.index_of('w').unwrap());
+    puzzle.guess("batch", &get_hint("batch", "watch").unwrap()).unwrap();
+    puzzle.force_reduce();
+    assert!(puzzle.banned_letters.contains(b) && !puzzle.banned_letters.contains(w));
+    assert!(puzzle.slots.iter().all(|s| !s.contains(b)));
+
+    let watch = puzzle.encode("watch").unwrap();
+    assert!(puzzle.could_be(watch.as_ref()));
+    puzzle.letter_counts[w as usize] = (0, 0);
+    puzzle.reduce();
+    assert!(puzzle.banned_letters.contains(w) && !puzzle.could_be(watch.as_ref()));
+    assert_eq!(puzzle.candidates(), &["catch", "hatch", "latch", "match", "patch"]);
+
+    // a letter banned by an edited count is removed even where it was fixed
+    let mut fixed = Puzzle::new(&dict);
+    fixed.guess("watch", &[Hint::Correct; 5]).unwrap();
+    fixed.force_reduce();
+    fixed.letter_counts[w as usize] = (0, 0);
+    fixed.reduce();
+    assert!(fixed.slots[0].is_empty() && fixed.candidates().is_empty());
+}
+
+#[test]
+fn test_drop_equivalent_guesses() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_endgame_threshold(0);
+    for guess in ["watch", "latch"] {
+        puzzle.guess(guess, &get_hint(guess, "catch").unwrap()).unwrap();
+    }
+    assert_eq!(puzzle.candidates(), &["batch", "catch", "hatch", "match", "patch"]);
+
+    // "l" and "w" are dead, so "watch" gives the same hints as "latch" and needn't be scored
+    let best = puzzle.best_guess(1, AnyWord).unwrap();
+    assert_eq!(best.counters.evaluated, dict.len() as u64 - 1);
+    let ranked = puzzle.rank_guesses(1, AnyWord, 1).unwrap();
+    assert_eq!((best.word, best.worst_case, best.average), (ranked[0].word.clone(), ranked[0].worst_case, ranked[0].average));
+}
+
+#[test]
+fn test_rank_guesses() {
+    let dict = testkit::dictionary(testkit::ANAGRAM_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_endgame_threshold(0);
+    let ranked = puzzle.rank_guesses(1, AnyWord, 5).unwrap();
+    assert_eq!(ranked.len(), 5);
+    assert_eq!(ranked[0].word, puzzle.best_guess(1, AnyWord).unwrap().word);
+    assert!(ranked.windows(2).all(|x| (x[0].worst_case, x[0].average, !x[0].feasible) <= (x[1].worst_case, x[1].average, !x[1].feasible)));
+    assert_eq!(puzzle.rank_guesses(4, AnyWord, 5).unwrap(), ranked);
+    assert_eq!(puzzle.top_guesses(5, 2).unwrap(), ranked);
+    for guess in ranked.iter() {
+        assert_eq!(puzzle.evaluate_guess(&guess.word).unwrap().as_ref(), Some(guess));
+    }
+    let outside = puzzle.evaluate_guess("xxxxx").unwrap().unwrap();
+    assert_eq!((outside.worst_case, outside.feasible), (dict.len() as u64, false));
+    assert!(matches!(puzzle.evaluate_guess("xxx"), Err(WordError::WrongWordLen { .. })));
+    let mut inconsistent = puzzle.clone();
+    inconsistent.guess("crane", &[Hint::Correct; 5]).unwrap();
+    inconsistent.guess("slate", &[Hint::Correct; 5]).unwrap();
+    assert_eq!(inconsistent.evaluate_guess("crane").unwrap(), None);
+
+    assert_eq!(puzzle.rank_guesses(1, AnyWord, 100).unwrap().len(), dict.len());
+    let all = puzzle.rank_guesses(3, AnyWord, usize::MAX).unwrap();
+    assert_eq!((all.len(), &all[..5]), (dict.len(), ranked.as_slice()));
+    assert!(puzzle.rank_guesses(1, AnyWord, 0).unwrap().is_empty());
+    assert!(matches!(puzzle.rank_guesses(1, |_: &str| false, 5), Err(SolveErr::NoAdmissibleGuess)));
+}
+
+#[test]
+fn test_concurrent_queries() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Puzzle>();
+
+    let dict = testkit::dictionary(testkit::DUPLICATE_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_endgame_threshold(0);
+    puzzle.guess("there", &get_hint("there", "geese").unwrap()).unwrap(); // left unreduced on purpose
+    let expected = puzzle.clone().best_guess(1, AnyWord).unwrap().word;
+
+    let shared = &puzzle;
+    let results: Vec<_> = crossbeam::scope(|scope| {
+        let handles: Vec<_> = (0..4).map(|_| scope.spawn(move |_| (shared.best_guess(2, AnyWord).unwrap().word, shared.candidates()))).collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    }).unwrap();
+    for (word, candidates) in results {
+        assert_eq!(word, expected);
+        assert_eq!(candidates, puzzle.candidates());
+    }
+
+    // a new version can be made while the old one is still being read, without locking
+    let old = Arc::new(puzzle.clone());
+    let reader = old.clone();
+    let (next, seen) = crossbeam::scope(|scope| {
+        let handle = scope.spawn(move |_| reader.candidates());
+        let next = old.guessed("geese", &get_hint("geese", "geese").unwrap()).unwrap();
+        (next, handle.join().unwrap())
+    }).unwrap();
+    assert_eq!(seen, puzzle.candidates());
+    assert_eq!((old.history().len(), next.history().len()), (1, 2));
+    assert_eq!(next.candidates(), &["geese"]);
+    assert!(Arc::ptr_eq(&next.all_words, &old.all_words));
+    assert!(matches!(old.guessed("hat", &[]), Err(GuessError::WrongWordLen { .. })));
+
+    let fresh = puzzle.restart();
+    assert!(Arc::ptr_eq(&fresh.all_words, &puzzle.all_words));
+    assert_eq!(fresh.candidates().len(), dict.len());
+    assert!(fresh.history().is_empty());
+}
+
+#[test]
+fn test_last_turn() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_endgame_threshold(0);
+    puzzle.guess("wield", &get_hint("wield", "catch").unwrap()).unwrap();
+    assert_eq!(puzzle.turns_left(), Game::DEFAULT_MAX_GUESSES - 1);
+    assert_eq!((puzzle.guess_count(), puzzle.word_len(), puzzle.dictionary().word_len()), (1, 5, 5));
+    assert!(std::ptr::eq(puzzle.dictionary(), &dict));
+    assert!(!puzzle.candidates().contains(&puzzle.best_guess(1, AnyWord).unwrap().word.as_str()));
+
+    puzzle.set_turn(Game::DEFAULT_MAX_GUESSES - 1);
+    assert_eq!((puzzle.turns_left(), puzzle.guess_count()), (1, Game::DEFAULT_MAX_GUESSES - 1));
+    assert!(puzzle.candidates().contains(&puzzle.best_guess(1, AnyWord).unwrap().word.as_str()));
+    assert_eq!(puzzle.best_guess(1, |w: &str| w == "chimp").unwrap().word, "chimp");
+}
+
+#[test]
+fn test_relax_constraints() {
+    use Hint::*;
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "match", "chimp", "champ", "lower", "geese", "eerie"]).unwrap();
+
+    // a mistyped hint for "lower" (the l should have been correct) rules out the answer
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &[Absent; 5]).unwrap();
+    assert_eq!(puzzle.candidates(), &["batch", "catch", "champ", "chimp", "hatch", "match"]);
+    puzzle.relax_letter('l').unwrap();
+    assert!(puzzle.candidates().contains(&"latch"));
+    puzzle.guess("lower", &[Correct, Absent, Absent, Absent, Absent]).unwrap();
+    assert_eq!(puzzle.candidates(), &["latch"]);
+
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("geese", &get_hint("geese", "batch").unwrap()).unwrap();
+    assert!(!puzzle.candidates().contains(&"eerie"));
+    puzzle.relax_letter_max('e').unwrap();
+    assert!(!puzzle.candidates().contains(&"eerie")); // e is still excluded from every slot
+    for slot in [0, 1, 4] { puzzle.allow_letter('e', slot).unwrap(); }
+    assert!(puzzle.candidates().contains(&"eerie"));
+    assert!(matches!(puzzle.allow_letter('e', 5), Err(ConstraintError::SlotOutOfRange { slot: 5, word_len: 5 })));
+    assert!(matches!(puzzle.relax_letter('E'), Err(ConstraintError::NotInAlphabet { letter: 'E' })));
+
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("catch", &[Present, Absent, Absent, Absent, Absent]).unwrap();
+    assert!(puzzle.to_string().contains("c: 1..=1,"));
+    puzzle.relax_letter_min('c').unwrap();
+    assert!(puzzle.to_string().contains("c: 0..=1,"));
+    puzzle.relax_letter('c').unwrap();
+    assert!(puzzle.to_string().contains("c: 0..=5,"));
+    assert_eq!(puzzle.candidates(), &["eerie", "geese", "lower"]);
+}
+
+#[test]
+fn test_undo() {
+    use Hint::*;
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "match", "chimp", "champ", "lower", "geese", "eerie"]).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    assert_eq!(puzzle.undo(), None);
+
+    puzzle.guess("geese", &get_hint("geese", "latch").unwrap()).unwrap();
+    let before = (puzzle.candidates(), puzzle.to_string());
+    puzzle.guess("lower", &[Absent; 5]).unwrap(); // mistyped, so the answer is ruled out
+    assert!(!puzzle.candidates().contains(&"latch"));
+    assert_eq!(puzzle.undo(), Some(("lower".into(), vec![Absent; 5])));
+    assert_eq!((puzzle.candidates(), puzzle.to_string()), before);
+    assert_eq!((puzzle.history().len(), puzzle.guess_count()), (1, 1));
+
+    // edits made after the guess are reverted along with it
+    puzzle.guess("lower", &[Absent; 5]).unwrap();
+    puzzle.relax_letter('l').unwrap();
+    assert!(puzzle.candidates().contains(&"latch"));
+    puzzle.undo().unwrap();
+    assert_eq!((puzzle.candidates(), puzzle.to_string()), before);
+
+    puzzle.guess_traced("lower", &[Correct, Absent, Absent, Absent, Absent], |_| ()).unwrap();
+    assert_eq!(puzzle.candidates(), &["latch"]);
+    puzzle.undo().unwrap();
+    puzzle.undo().unwrap();
+    assert_eq!((puzzle.candidates().len(), puzzle.guess_count(), puzzle.undo()), (dict.len(), 0, None));
+}
+
+#[cfg(feature = "debug-invariants")]
+#[test]
+#[should_panic(expected = "answer 'latch' violates the constraints")]
+fn test_known_answer_invariant() {
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "lower"]).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_known_answer("latch");
+    puzzle.guess("batch", &get_hint("batch", "latch").unwrap()).unwrap();
+    puzzle.guess("lower", &get_hint("lower", "catch").unwrap()).unwrap(); // hint for the wrong answer
+}
+
+#[test]
+fn test_phrase_puzzle() {
+    let dict = Dictionary::with_phrases(7, '-', ["hot-dog", "top-dog", "hot-tub", "lap-dog", "pop-art", "hip-hop"]).unwrap();
+    assert!(matches!(Dictionary::with_phrases(7, '-', ["hot dog"]), Err(WordError::NotInAlphabet { .. })));
+    assert!(matches!(get_hint("hot-dog", "pop-art"), Err(WordError::NotInAlphabet { word: "pop-art" })));
+    assert_eq!(dict.alphabet().get_hint("hot-dog", "pop-art").unwrap(), &[Hint::Absent, Hint::Correct, Hint::Present, Hint::Correct, Hint::Absent, Hint::Absent, Hint::Absent]);
+
+    let mut puzzle = Puzzle::new(&dict);
+    assert!(puzzle.to_string().starts_with("0: hlpt\n1: aio\n2: pt\n3: -\n"));
+
+    let guess = puzzle.best_guess(1, AnyWord).unwrap().word;
+    assert_eq!(guess.chars().nth(3), Some('-'));
+    puzzle.guess("top-dog", &dict.alphabet().get_hint("top-dog", "lap-dog").unwrap()).unwrap();
+    assert_eq!(puzzle.best_guess(1, AnyWord).unwrap().word, "lap-dog");
+}
+
+#[test]
+fn test_play_policy() {
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "match", "patch", "watch", "chimp", "blimp", "lower"]).unwrap();
+    let policy = |turn: usize, _: usize| if turn == 0 { Strategy::Fixed("lower".into()) } else { Strategy::FeasibleOnly };
+
+    for answer in ["batch", "watch", "lower", "blimp"] {
+        let guesses = play(&dict, answer, policy, 2).unwrap();
+        assert_eq!(guesses[0], "lower");
+        assert_eq!(guesses.last().unwrap(), answer);
+
+        let mut puzzle = Puzzle::new(&dict);
+        for guess in guesses.iter() {
+            assert!(guess == "lower" || puzzle.could_be(puzzle.encode(guess).unwrap().as_ref()));
+            puzzle.guess(guess, &get_hint(guess, answer).unwrap()).unwrap();
+        }
+    }
+
+    assert!(matches!(play(&dict, "batch", |_: usize, _: usize| Strategy::Fixed("lower".into()), 1), Err(PlayError::Stalled { turn: 1, .. })));
+    assert!(matches!(play(&dict, "bat", policy, 1), Err(PlayError::InvalidAnswer(_))));
+    assert!(matches!(play(&dict, "batch", |_: usize, _: usize| Strategy::Fixed("l0wer".into()), 1), Err(PlayError::InvalidGuess { turn: 0, word }) if word == "l0wer"));
+}
+
+#[test]
+fn test_status() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    assert_eq!((puzzle.status(), puzzle.is_solved(), puzzle.is_inconsistent(), puzzle.solution()), (PuzzleStatus::Unsolved, false, false, None));
+
+    for guess in ["watch", "patch", "batch", "latch", "catch"] {
+        puzzle.guess(guess, &get_hint(guess, "match").unwrap()).unwrap();
+    }
+    assert_eq!((puzzle.status(), puzzle.solution()), (PuzzleStatus::Unsolved, None)); // "hatch" or "match"
+    puzzle.guess("hatch", &get_hint("hatch", "match").unwrap()).unwrap();
+    assert!(puzzle.is_solved() && !puzzle.is_inconsistent());
+    assert_eq!(puzzle.solution(), Some("match"));
+
+    puzzle.guess("match", &get_hint("match", "hatch").unwrap()).unwrap();
+    assert_eq!((puzzle.status(), puzzle.is_solved(), puzzle.is_inconsistent(), puzzle.solution()), (PuzzleStatus::Inconsistent, false, true, None));
+}
+
+#[test]
+fn test_constraint_accessors() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    assert_eq!(puzzle.allowed_letters(0).unwrap().collect::<String>(), "bchlmpw");
+    assert_eq!(puzzle.allowed_letters(1).unwrap().collect::<String>(), "a");
+    assert_eq!(puzzle.letter_bounds('a').unwrap(), (0, 5)); // the bounds only come from hints, even though every word has one 'a'
+
+    puzzle.guess("chimp", &get_hint("chimp", "batch").unwrap()).unwrap();
+    assert_eq!(puzzle.allowed_letters(0).unwrap().collect::<String>(), "bhlw");
+    assert_eq!(puzzle.letter_bounds('m').unwrap(), (0, 0));
+    assert_eq!(puzzle.letter_bounds('c').unwrap(), (1, 5));
+    assert!(matches!(puzzle.allowed_letters(5), Err(ConstraintError::SlotOutOfRange { slot: 5, word_len: 5 })));
+    assert!(matches!(puzzle.letter_bounds('!'), Err(ConstraintError::NotInAlphabet { letter: '!' })));
+}
+
+#[test]
+fn test_reset() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_max_guesses(3);
+    puzzle.set_endgame_threshold(0);
+    puzzle.guess("watch", &get_hint("watch", "match").unwrap()).unwrap();
+    let words = puzzle.all_words.clone();
+
+    puzzle.reset();
+    assert!(Arc::ptr_eq(&puzzle.all_words, &words));
+    assert_eq!(puzzle.candidates(), Puzzle::new(&dict).candidates());
+    assert!(puzzle.history().is_empty() && puzzle.undo().is_none());
+    assert_eq!((puzzle.guess_count(), puzzle.turns_left(), puzzle.endgame_threshold), (0, 3, 0));
+}
+
+#[test]
+fn test_apply_hint_raw() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    let mut guessed = puzzle.clone();
+    for guess in ["watch", "patch"] {
+        let hint = get_hint(guess, "match").unwrap();
+        puzzle.apply_hint_raw(guess, &hint).unwrap();
+        guessed.guess(guess, &hint).unwrap();
+    }
+    assert!(puzzle.dirty);
+    assert_eq!(puzzle.candidates(), guessed.candidates());
+    assert!(puzzle.history().is_empty() && puzzle.guess_count() == 0);
+
+    // constraints survive rederiving, but not undoing an earlier guess
+    puzzle.guess("batch", &get_hint("batch", "match").unwrap()).unwrap();
+    puzzle.apply_hint_raw("latch", &get_hint("latch", "match").unwrap()).unwrap();
+    puzzle.relax_letter('z').unwrap();
+    assert_eq!(puzzle.candidates(), &["catch", "hatch", "match"]);
+    puzzle.undo();
+    assert_eq!(puzzle.candidates(), guessed.candidates());
+
+    puzzle.apply_hint_raw("geese", &[Hint::Present; 5]).unwrap();
+    assert!(puzzle.is_inconsistent());
+    assert!(matches!(puzzle.apply_hint_raw("watch", &[Hint::Absent; 4]), Err(GuessError::WrongHintLen { expected_len: 5, .. })));
+}
+
+#[test]
+fn test_is_candidate() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("watch", &get_hint("watch", "match").unwrap()).unwrap();
+    for &word in testkit::ATCH_WORDS {
+        assert_eq!(puzzle.is_candidate(word).unwrap(), puzzle.candidates().contains(&word), "{}", word);
+    }
+    assert!(puzzle.is_candidate("zatch").unwrap()); // consistent, though not in the dictionary
+    assert!(!puzzle.is_candidate("zatcw").unwrap());
+    assert!(puzzle.is_candidate("atch").is_err());
+
+    // an absent extra copy rules out its own slot
+    let dict = testkit::dictionary(testkit::DUPLICATE_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("ebbed", &get_hint("ebbed", "kebab").unwrap()).unwrap();
+    assert!(puzzle.is_candidate("kebab").unwrap() && !puzzle.is_candidate("abbey").unwrap());
+}
+
+#[test]
+fn test_best_guess_feasible() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "lower"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+    for filter in [|w: &str| w.ends_with("atch"), |w: &str| !w.ends_with("atch")] {
+        let best = puzzle.best_guess(1, filter).unwrap();
+        assert_eq!(best.feasible, puzzle.candidates().contains(&best.word.as_str()), "{}", best.word);
+        assert_eq!(best.feasible, best.partition.iter().any(|x| x.0.hints().iter().all(|&h| h == Hint::Correct)));
+    }
+    puzzle.guess("chimp", &get_hint("chimp", "hatch").unwrap()).unwrap();
+    puzzle.guess("batch", &get_hint("batch", "hatch").unwrap()).unwrap();
+    let best = puzzle.best_guess(1, AnyWord).unwrap();
+    assert!(best.feasible, "{:?}", best);
+}
+
+#[test]
+fn test_with_pattern() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["eaten", "lapse", "false", "tease"])).unwrap();
+    let mut puzzle = Puzzle::with_pattern(&dict, "_a__e").unwrap();
+    assert_eq!(puzzle.candidates(), &["false", "lapse"]);
+    assert_eq!((puzzle.guess_count(), puzzle.history().len()), (0, 0));
+    assert!(puzzle.is_candidate("baxxe").unwrap() && !puzzle.is_candidate("batch").unwrap());
+    puzzle.ban_letter('p').unwrap();
+    assert_eq!(puzzle.candidates(), &["false"]);
+    assert_eq!(puzzle.letter_bounds('p').unwrap(), (0, 0));
+
+    let puzzle = Puzzle::with_pattern(&dict, "_____").unwrap();
+    assert_eq!(puzzle.candidates(), Puzzle::new(&dict).candidates());
+    let puzzle = Puzzle::with_pattern(&dict, "e_te_").unwrap();
+    assert_eq!((puzzle.candidates(), puzzle.letter_bounds('e').unwrap().0), (vec!["eaten"], 2));
+
+    assert!(matches!(Puzzle::with_pattern(&dict, "_a_e"), Err(WordError::WrongWordLen { expected_len: 5, .. })));
+    assert!(matches!(Puzzle::with_pattern(&dict, "_A__e"), Err(WordError::NotInAlphabet { .. })));
+    assert!(matches!(Puzzle::new(&dict).ban_letter('?'), Err(ConstraintError::NotInAlphabet { letter: '?' })));
+}
@@ -0,0 +1,84 @@
+use std::cmp::Reverse;
+
+use crate::*;
+
+/// The order to list candidates in, as used by [`Puzzle::candidate_page`].
+#[derive(Clone, Copy)]
+pub enum CandidateOrder<'p> {
+    /// Lexicographic order.
+    Alphabetical,
+    /// From most to least likely according to the prior, which must be for the puzzle's dictionary (ties in lexicographic order).
+    Prior(&'p Prior<'p>),
+    /// From most to least likely by positional letter frequency: each word is scored by the sum, over its slots,
+    /// of the number of candidates with the same letter in that slot (ties in lexicographic order).
+    Positional,
+}
+
+/// A truncated list of candidates, as produced by [`Puzzle::candidate_page`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandidatePage<'a> {
+    /// The first candidates in the requested order.
+    pub words: Vec<&'a str>,
+    /// The total number of candidates, including those which were left out.
+    pub total: usize,
+}
+
+impl<'a> Puzzle<'a> {
+    /// Gets the first `limit` words which could be the answer in the given order, along with the total number of candidates.
+    /// Only the selected words are looked up as strings, and the ordering takes linear time plus `limit * log(limit)`,
+    /// so this is suitable for showing the top of a large candidate list on every turn.
+    pub fn candidate_page(&self, order: CandidateOrder, limit: usize) -> CandidatePage<'a> {
+        let this = self.reduced();
+        let words = &this.feasible_words;
+        let limit = limit.min(words.len());
+
+        let mut keyed: Vec<(u64, usize)> = match order {
+            CandidateOrder::Alphabetical => (0..limit).map(|i| (0, i)).collect(),
+            CandidateOrder::Prior(prior) => words.iter().enumerate().map(|(i, w)| (prior.weight_at(self.dictionary.index_of(w).unwrap()) as u64, i)).collect(),
+            CandidateOrder::Positional => {
+                let mut counts = vec![[0u64; Alphabet::MAX_LEN]; this.slots.len()];
+                for word in words.iter() {
+                    for (slot, &letter) in word.iter().enumerate() {
+                        counts[slot][letter as usize] += 1;
+                    }
+                }
+                words.iter().enumerate().map(|(i, w)| (w.iter().enumerate().map(|(slot, &letter)| counts[slot][letter as usize]).sum(), i)).collect()
+            }
+        };
+        // feasible words are in lexicographic order, so ties are broken by index
+        let key = |&(score, i): &(u64, usize)| (Reverse(score), i);
+        if limit < keyed.len() {
+            if limit > 0 { keyed.select_nth_unstable_by_key(limit - 1, key); }
+            keyed.truncate(limit);
+        }
+        keyed.sort_unstable_by_key(key);
+
+        CandidatePage { words: keyed.into_iter().map(|(_, i)| self.dictionary.word_str(&words[i])).collect(), total: words.len() }
+    }
+}
+
+#[test]
+fn test_candidate_page() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "champ", "cloth", "lower"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "catch").unwrap()).unwrap();
+    let candidates = puzzle.candidates();
+    assert_eq!(candidates, &["batch", "catch", "champ", "chimp", "hatch", "match", "patch"]);
+
+    let page = puzzle.candidate_page(CandidateOrder::Alphabetical, 3);
+    assert_eq!((page.words.as_slice(), page.total), (&candidates[..3], 7));
+    assert_eq!(puzzle.candidate_page(CandidateOrder::Alphabetical, 100).words, candidates);
+    assert_eq!(puzzle.candidate_page(CandidateOrder::Positional, 0), CandidatePage { words: vec![], total: 7 });
+
+    // the _atch words share 4 slots with most other candidates, and "champ" beats "chimp" on the 'a'
+    let positional = puzzle.candidate_page(CandidateOrder::Positional, 100).words;
+    assert_eq!(positional, &["catch", "batch", "hatch", "match", "patch", "champ", "chimp"]);
+    assert_eq!(puzzle.candidate_page(CandidateOrder::Positional, 2).words, &positional[..2]);
+
+    let prior = Prior::uniform(&dict).with_modifier(|w: &str, x| match w { "patch" => x * 3, "chimp" => x * 2, "batch" => x / 2, _ => x });
+    assert_eq!(puzzle.candidate_page(CandidateOrder::Prior(&prior), 100).words, &["patch", "chimp", "catch", "champ", "hatch", "match", "batch"]);
+    for limit in 0..=7 {
+        let page = puzzle.candidate_page(CandidateOrder::Prior(&prior), limit);
+        assert_eq!(page.words, prior.most_likely(candidates.iter().copied(), limit).into_iter().map(|x| x.0).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::*;
+
+/// A guess chosen by looking ahead at the follow-up guesses for each hint it could receive, as produced by [`Puzzle::best_guess_lookahead`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookaheadGuess {
+    /// The word to guess.
+    pub word: String,
+    /// The worst-case number of words which could still be the answer after making the guess and the follow-ups.
+    pub worst_case: u64,
+    /// The average (over the words which could be the answer) number of words which could still be the answer after making the guess and the follow-ups.
+    /// Guessing the answer leaves zero.
+    pub remaining: Average,
+    /// Whether the guess could itself be the answer.
+    pub feasible: bool,
+}
+
+impl<'a> Puzzle<'a> {
+    /// The default number of guesses considered at each step of [`Puzzle::best_guess_lookahead`] (e.g., by [`Strategy::Lookahead`] in [`parse_strategy`]).
+    pub const LOOKAHEAD_BREADTH: usize = 10;
+
+    /// Finds the guess which minimizes the worst-case number of words which could still be the answer after `depth` guesses
+    /// (this one and `depth - 1` follow-ups, each chosen the same way for the hint received), then the average number.
+    /// Unlike [`Puzzle::best_guess`], which only looks at the hint to this guess, this accounts for how well each hint can be followed up.
+    /// The lookahead stops early if the game would run out of guesses.
+    ///
+    /// To keep the search tractable, only the best `breadth` guesses by [`Puzzle::rank_guesses`] are considered at each step
+    /// (among the words admitted by `filter` for this guess, and among all words for the follow-ups, which are ranked like [`Puzzle::rank_guesses`] but ignoring the guess limit),
+    /// so the time taken grows with `breadth` to the power of `depth`. With a `breadth` of at least the size of the dictionary, the search is exact.
+    /// Ties are broken by preferring words which could be the answer, then lexicographically.
+    ///
+    /// The guesses for this turn are scored in parallel using the given number of threads (minimum `1`).
+    /// Fails under the same conditions as [`Puzzle::best_guess`].
+    pub fn best_guess_lookahead<F: CandidateFilter>(&self, threads: usize, filter: F, depth: usize, breadth: usize) -> Result<LookaheadGuess, SolveErr> {
+        let this = self.reduced();
+        let shortlist = this.rank_guesses(threads, filter, breadth.max(1))?;
+        let follow_ups = depth.min(this.turns_left()).saturating_sub(1); // no follow-ups once the game is out of guesses
+
+        let chunk_size = shortlist.len().div_ceil(threads.max(1)).max(1);
+        let ((worst_case, total), infeasible, word) = crossbeam::scope(|scope| {
+            let threads: Vec<_> = shortlist.chunks(chunk_size).map(|guesses| {
+                let this = &this;
+                scope.spawn(move |_| guesses.iter().map(|guess| {
+                    let encoded = this.encode(&guess.word).unwrap();
+                    (this.lookahead_value(encoded.as_ref(), follow_ups, breadth), !guess.feasible, guess.word.as_str())
+                }).min().unwrap())
+            }).collect();
+            threads.into_iter().map(|x| x.join().unwrap()).min().unwrap()
+        }).unwrap();
+
+        Ok(LookaheadGuess { word: word.into(), worst_case, remaining: Average::new(total, this.feasible_words.len() as u64), feasible: !infeasible })
+    }
+    /// Gets the worst case and the total (over the feasible words) number of words which could still be the answer
+    /// after making `guess` and then `depth` follow-ups, each chosen from the best `breadth` guesses to minimize the same.
+    fn lookahead_value(&self, guess: Word, depth: usize, breadth: usize) -> (u64, u64) {
+        let (mut worst_case, mut total) = (0, 0);
+        for (pattern, n) in self.partition(guess) {
+            let hints = pattern.hints();
+            if hints.iter().all(|&h| h == Hint::Correct) { continue } // guessed the answer, so nothing remains
+            let (worst, sum) = match (depth, n) {
+                (0, n) => (n, n * n),
+                (_, 1) => (0, 0), // the next guess is the answer
+                _ => {
+                    let mut next = self.clone();
+                    next.assume_impl(guess, &hints);
+                    next.turn += 1;
+                    next.lookahead_shortlist(breadth).into_iter().map(|x| next.lookahead_value(x, depth - 1, breadth)).min().unwrap()
+                }
+            };
+            worst_case = worst_case.max(worst);
+            total += sum;
+        }
+        (worst_case, total)
+    }
+    /// Gets the best `breadth` follow-up guesses, ranked like [`Puzzle::rank_guesses`] (but ignoring the guess limit).
+    /// The guesses are scored by counting the feasible words for each hint, which gives the same scores without the overhead of a search.
+    fn lookahead_shortlist(&self, breadth: usize) -> Vec<Word<'a>> {
+        let breadth = breadth.max(1);
+        if breadth >= self.all_words.len() { return self.all_words.to_vec() } // every word is considered, so ranking them is wasted work
+        let mut ranked: Vec<_> = self.all_words.iter().map(|&guess| {
+            let partition = self.partition(guess);
+            ((partition[0].1, Average::new(self.feasible_words.len() as u64, partition.len() as u64)), !self.could_be(guess), guess)
+        }).collect();
+        ranked.select_nth_unstable(breadth - 1);
+        ranked.truncate(breadth);
+        ranked.into_iter().map(|x| x.2).collect()
+    }
+}
+
+#[test]
+fn test_best_guess_lookahead() {
+    use std::collections::HashMap;
+
+    // scores every line from scratch with get_hint, trying every word at every step
+    fn reference(words: &[&str], answers: &[&str], guess: &str, depth: usize) -> (u64, u64) {
+        let mut groups: HashMap<Vec<Hint>, Vec<&str>> = HashMap::new();
+        for &answer in answers { groups.entry(get_hint(guess, answer).unwrap()).or_default().push(answer); }
+        let (mut worst_case, mut total) = (0, 0);
+        for (hint, group) in groups {
+            if hint.iter().all(|&h| h == Hint::Correct) { continue }
+            let n = group.len() as u64;
+            let (worst, sum) = match depth {
+                0 => (n, n * n),
+                _ => words.iter().map(|w| reference(words, &group, w, depth - 1)).min().unwrap(),
+            };
+            worst_case = worst_case.max(worst);
+            total += sum;
+        }
+        (worst_case, total)
+    }
+
+    for (words, max_depth) in [(testkit::ATCH_WORDS, 3), (testkit::MINI_WORDS, 2)] {
+        let dict = testkit::dictionary(words);
+        let mut puzzle = Puzzle::new(&dict);
+        let opener = words[words.len() - 1];
+        puzzle.guess(opener, &get_hint(opener, words[0]).unwrap()).unwrap();
+        let answers = puzzle.candidates();
+        for depth in 1..=max_depth {
+            let best = puzzle.best_guess_lookahead(2, AnyWord, depth, dict.len()).unwrap();
+            let ((worst_case, total), _, word) = words.iter().map(|&w| (reference(words, &answers, w, depth - 1), !answers.contains(&w), w)).min().unwrap();
+            assert_eq!((best.word.as_str(), best.worst_case, best.remaining), (word, worst_case, Average::new(total, answers.len() as u64)), "{:?} depth={}", words, depth);
+            assert_eq!(best.feasible, answers.contains(&word));
+        }
+    }
+
+    // looking further ahead never makes the guaranteed result worse, and a narrow search only considers the best few guesses
+    let dict = testkit::dictionary(testkit::MINI_WORDS);
+    let puzzle = Puzzle::new(&dict);
+    let (one, two) = (puzzle.best_guess_lookahead(4, AnyWord, 1, 5).unwrap(), puzzle.best_guess_lookahead(4, AnyWord, 2, 5).unwrap());
+    assert!((two.worst_case, two.remaining) <= (one.worst_case, one.remaining));
+    let shortlist: Vec<_> = puzzle.rank_guesses(1, AnyWord, 5).unwrap().into_iter().map(|x| x.word).collect();
+    assert!(shortlist.contains(&two.word));
+    let filtered = puzzle.best_guess_lookahead(1, |w: &str| w.starts_with('b'), 2, 5).unwrap();
+    assert!(filtered.word.starts_with('b'));
+
+    let mut limited = puzzle.clone();
+    limited.set_max_guesses(1);
+    let last = limited.best_guess_lookahead(1, AnyWord, 3, 5).unwrap();
+    assert!(last.feasible);
+    assert_eq!(last, limited.best_guess_lookahead(1, AnyWord, 1, 5).unwrap());
+    limited.guess("bread", &get_hint("bread", "chore").unwrap()).unwrap();
+    assert_eq!(limited.turns_left(), 0);
+    assert!(matches!(limited.best_guess(1, AnyWord), Err(SolveErr::Inconsistent)));
+    assert!(matches!(limited.best_guess_lookahead(1, AnyWord, 3, 5), Err(SolveErr::Inconsistent)));
+    assert!(matches!(puzzle.best_guess_lookahead(1, |_: &str| false, 2, 5), Err(SolveErr::NoAdmissibleGuess)));
+}
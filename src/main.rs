@@ -1,69 +1,615 @@
-use std::sync::Mutex;
-use clap::Parser;
+use std::iter;
+use std::sync::{Mutex, OnceLock};
+use std::path::Path;
+use std::collections::HashMap;
+use std::io::{self, Read, BufRead, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use clap::{Parser, Subcommand, Args as ClapArgs, CommandFactory, FromArgMatches, ErrorKind};
 use wordle_solver::*;
 
-const WORD_LEN: usize = 5;
-
-#[derive(Parser)]
-enum Args {
-    /// Solve a wordle puzzle by predicting the best guess to make next
-    Solve {
-        #[clap(short, long, default_value_t = num_cpus::get())]
-        threads: usize,
-
-        inputs: Vec<String>,
-    },
-    /// Benchmark the performance of the solver on all possible 5-letter english words
-    /// (includes words not used as answers by wordle itself)
-    Bench {
-        #[clap(short, long, default_value_t = num_cpus::get())]
-        threads: usize,
-        /// Also output the number of guesses needed for each tested word
-        /// (a consistent ordering of words in the output is not guaranteed)
-        #[clap(short, long)]
-        verbose: bool,
-    },
+mod messages;
+use messages::*;
+
+include!("cli.rs");
+
+const EXIT_INCONSISTENT: i32 = 2;
+const EXIT_INVALID_INPUT: i32 = 3;
+const EXIT_DICTIONARY: i32 = 4;
+const EXIT_REGRESSION: i32 = 5;
+
+/// The locale for user-facing messages, which is set once the arguments are parsed.
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Formats a user-facing message in the selected locale.
+fn tr(message: Message, args: &[&dyn std::fmt::Display]) -> String {
+    message.format(LOCALE.get().copied().unwrap_or(Locale::English), args)
+}
+
+/// Prints a usage error and exits with the given code.
+fn usage_error(code: i32, kind: ErrorKind, msg: impl std::fmt::Display) -> ! {
+    Cli::command().error(kind, msg).print().ok();
+    std::process::exit(code);
+}
+
+/// Prints an error message and exits with the given code.
+fn fail(code: i32, msg: impl std::fmt::Display) -> ! {
+    eprintln!("error: {}", msg);
+    std::process::exit(code);
+}
+
+/// Exits with the code for a failed search.
+fn solve_failed(e: SolveErr) -> ! {
+    match e {
+        SolveErr::Inconsistent => fail(EXIT_INCONSISTENT, tr(Message::Inconsistent, &[])),
+        SolveErr::NoAdmissibleGuess => fail(EXIT_DICTIONARY, tr(Message::NoAdmissibleGuess, &[])),
+        SolveErr::InvalidExtraGuess(word) => fail(EXIT_INVALID_INPUT, tr(Message::InvalidExtraGuess, &[&word])),
+        SolveErr::TooManyAnswers { answers, max } => fail(EXIT_INVALID_INPUT, tr(Message::TooManyAnswers, &[&answers, &max])),
+    }
+}
+
+fn load_definitions(paths: &[std::path::PathBuf]) -> Definitions {
+    let mut res = Definitions::new();
+    for path in paths {
+        let text = std::fs::read_to_string(path).unwrap_or_else(|e| usage_error(EXIT_INVALID_INPUT, ErrorKind::Io, tr(Message::FailedToRead, &[&path.display(), &e])));
+        match path.file_name().and_then(|x| x.to_str()) {
+            Some(name) if name.starts_with("data.") => res.add_wordnet(&text),
+            _ => res.add_tsv(&text),
+        }
+    }
+    res
+}
+
+/// Formats a suggested word along with its definition (if known), shortened to fit on a line.
+fn describe(word: &str, definer: &dyn Definer) -> String {
+    const MAX_DEFINITION_LEN: usize = 60;
+    match definer.define(word) {
+        Some(def) if def.chars().count() > MAX_DEFINITION_LEN => format!("{} ({}...)", word, def.chars().take(MAX_DEFINITION_LEN - 3).collect::<String>()),
+        Some(def) => format!("{} ({})", word, def),
+        None => word.into(),
+    }
+}
+
+/// Gets the per-user directory to cache results in by default, if there is one.
+fn default_cache_dir() -> Option<std::path::PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(x) if !x.is_empty() => x.into(),
+        _ => std::path::PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+    Some(base.join("wordle-solver"))
+}
+
+fn default_config_file() -> Option<std::path::PathBuf> {
+    let base = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(x) if !x.is_empty() => x.into(),
+        _ => std::path::PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("wordle-solver").join("config"))
+}
+
+fn load_presets(path: Option<&Path>) -> Vec<Preset> {
+    let (path, text) = match path {
+        Some(path) => (path.to_owned(), std::fs::read_to_string(path).unwrap_or_else(|e| usage_error(EXIT_INVALID_INPUT, ErrorKind::Io, tr(Message::FailedToRead, &[&path.display(), &e])))),
+        None => match default_config_file().and_then(|path| Some((std::fs::read_to_string(&path).ok()?, path))) {
+            Some((text, path)) => (path, text),
+            None => return vec![],
+        },
+    };
+    parse_presets(&text).unwrap_or_else(|e| usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, tr(Message::InvalidConfig, &[&path.display(), &format!("{:?}", e)])))
+}
+
+fn open_progress(fd: u32) -> Box<dyn Write + Send> {
+    match fd {
+        1 => Box::new(std::io::stdout()),
+        2 => Box::new(std::io::stderr()),
+        fd => Box::new(progress_file(fd).unwrap_or_else(|e| usage_error(EXIT_INVALID_INPUT, ErrorKind::Io, tr(Message::ProgressFdFailed, &[&fd, &e])))),
+    }
+}
+
+#[cfg(unix)]
+fn progress_file(fd: u32) -> io::Result<std::fs::File> {
+    use std::os::unix::io::{FromRawFd, IntoRawFd, RawFd};
+    let raw = RawFd::try_from(fd).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "not a valid file descriptor"))?;
+    // SAFETY: the descriptor was handed to us by the caller for writing progress, and nothing else in the process uses it
+    let file = unsafe { std::fs::File::from_raw_fd(raw) };
+    if let Err(e) = file.metadata() {
+        let _ = file.into_raw_fd(); // it isn't open, so it must not be closed either
+        return Err(e);
+    }
+    Ok(file)
+}
+#[cfg(not(unix))]
+fn progress_file(_: u32) -> io::Result<std::fs::File> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "only 1 (stdout) and 2 (stderr) are supported on this platform"))
+}
+
+/// An append-only record of the answers completed by a benchmark, so that an interrupted run can resume where it left off.
+/// The file is a header line with the fingerprint of the dictionary, followed by one `<answer> <guesses>` line per completed answer.
+struct Checkpoint {
+    file: io::LineWriter<std::fs::File>,
+}
+impl Checkpoint {
+    const HEADER: &'static str = "wordle-solver bench checkpoint";
+
+    /// Opens (or creates) a checkpoint for the dictionary and gets the results it already contains.
+    fn open(path: &Path, dictionary: &Dictionary) -> io::Result<(Self, Vec<(String, u8)>)> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(x) => x,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        let header = format!("{} {:016x}", Self::HEADER, dictionary.fingerprint());
+
+        let mut completed = vec![];
+        let mut lines = text.split_inclusive('\n');
+        if let Some(first) = lines.next() {
+            if first.trim_end() != header {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "not a checkpoint for this word list"));
+            }
+            for line in lines {
+                if !line.ends_with('\n') { break; } // cut off by an interruption, so the answer is redone
+                match line.trim_end().split_once(' ').and_then(|(word, guesses)| Some((word.to_owned(), guesses.parse().ok()?))) {
+                    Some(x) => completed.push(x),
+                    None => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("malformed line {:?}", line.trim_end()))),
+                }
+            }
+        }
+
+        // rewrite the valid part so that new results are not appended to a partial line
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        let mut f = io::BufWriter::new(std::fs::File::create(&tmp)?);
+        writeln!(f, "{}", header)?;
+        for (word, guesses) in completed.iter() {
+            writeln!(f, "{} {}", word, guesses)?;
+        }
+        f.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        std::fs::rename(&tmp, path)?;
+
+        let file = std::fs::OpenOptions::new().append(true).open(path)?;
+        Ok((Checkpoint { file: io::LineWriter::new(file) }, completed))
+    }
+    /// Records the number of guesses needed for an answer.
+    fn record(&mut self, answer: &str, guesses: u8) -> io::Result<()> {
+        writeln!(self.file, "{} {}", answer, guesses)
+    }
+}
+
+/// Finds the best guess by the score from a user-supplied script (see [`ScriptScorer`]), exiting if the script is invalid or fails.
+#[cfg(feature = "scripting")]
+fn best_guess_scripted<F: CandidateFilter>(puzzle: &Puzzle, threads: usize, filter: F, path: &Path) -> Result<BestGuess, SolveErr> {
+    let source = std::fs::read_to_string(path).unwrap_or_else(|e| usage_error(EXIT_INVALID_INPUT, ErrorKind::Io, tr(Message::FailedToRead, &[&path.display(), &e])));
+    let scorer = ScriptScorer::new(&source).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::InvalidScoreScript, &[&path.display(), &e])));
+    let best = puzzle.best_guess_by(threads, filter, &scorer);
+    if let Some(e) = scorer.take_error() {
+        fail(EXIT_INVALID_INPUT, tr(Message::ScoreScriptFailed, &[&path.display(), &e]));
+    }
+    best
+}
+#[cfg(not(feature = "scripting"))]
+fn best_guess_scripted<F: CandidateFilter>(_: &Puzzle, _: usize, _: F, _: &Path) -> Result<BestGuess, SolveErr> {
+    usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, tr(Message::ScriptingDisabled, &[]))
+}
+
+fn load_weights<'a>(dictionary: &'a Dictionary, path: &Path) -> Prior<'a> {
+    let content = std::fs::read_to_string(path).unwrap_or_else(|e| usage_error(EXIT_INVALID_INPUT, ErrorKind::Io, tr(Message::FailedToRead, &[&path.display(), &e])));
+    let weights: Vec<(&str, u32)> = content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).map(|(i, line)| {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next().and_then(|x| x.parse().ok()), parts.next()) {
+            (Some(word), Some(weight), None) => (word, weight),
+            _ => usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, tr(Message::WeightsSyntax, &[&path.display(), &(i + 1)])),
+        }
+    }).collect();
+    Prior::uniform(dictionary).with_weights(weights).unwrap_or_else(|word| {
+        usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, tr(Message::WeightsUnknownWord, &[&path.display(), &word]))
+    })
+}
+
+fn parse_input(input: &str) -> (&str, Vec<Hint>) {
+    let sep = match input.find(':') {
+        Some(x) => x,
+        None => usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, tr(Message::UnknownInput, &[&input])),
+    };
+    let guess = &input[..sep];
+    let response = parse_response(&input[sep+1..]).unwrap_or_else(|e| usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, e));
+    (guess, response)
+}
+
+fn parse_puzzle<'a>(dictionary: &'a Dictionary, inputs: &'a PuzzleInputs) -> Puzzle<'a> {
+    parse_puzzle_traced(dictionary, inputs, false)
+}
+
+fn parse_puzzle_traced<'a>(dictionary: &'a Dictionary, inputs: &'a PuzzleInputs, explain: bool) -> Puzzle<'a> {
+    if inputs.guesses.len() != inputs.hints.len() {
+        usage_error(EXIT_INVALID_INPUT, ErrorKind::WrongNumberOfValues, tr(Message::UnmatchedGuess, &[]));
+    }
+    let mut parsed_inputs: Vec<_> = inputs.inputs.iter().map(|x| parse_input(x)).collect();
+    parsed_inputs.extend(iter::zip(&inputs.guesses, &inputs.hints).map(|(g, h)| (g.as_str(), h.clone())));
+
+    dictionary.validate_history(&parsed_inputs).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::InvalidInput, &[&e])));
+    let mut puzzle = Puzzle::new(dictionary);
+    if !explain {
+        let warnings = puzzle.apply_history_checked(&parsed_inputs).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::InvalidInput, &[&format!("{:?}", e)])));
+        for HistoryWarning::UnknownWord { word, .. } in warnings {
+            eprintln!("{}", tr(Message::UnknownWordWarning, &[&word]));
+        }
+        puzzle.force_reduce();
+        return puzzle;
+    }
+    for (guess, response) in parsed_inputs.iter() {
+        println!("{}:", guess);
+        if !dictionary.contains(guess) {
+            eprintln!("{}", tr(Message::UnknownWordWarning, &[&guess]));
+        }
+        puzzle.guess_traced(guess, response, |step| println!("  {}", step)).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::InvalidInput, &[&format!("{:?}", e)])));
+    }
+    puzzle
 }
 
 fn main() {
-    let args = Args::parse();
-    let raw_words = include_str!("guess-list.txt").split_whitespace();
-    let dictionary = Dictionary::with_words(WORD_LEN, raw_words.clone()).unwrap();
-
-    match args {
-        Args::Solve { threads, inputs } => {
-            let mut parsed_inputs = vec![];
-
-            for input in inputs.iter() {
-                let sep = match input.find(':') {
-                    Some(x) => x,
-                    None => panic!("unknown input '{}' (expected <guess>:<response>, see -h for info)", input),
+    // --version also describes the bundled word list (the source subcommand describes the list in use)
+    let bundled = GameProfile::Wordle.words().unwrap();
+    let long_version = format!("{}\nword list: {}", env!("CARGO_PKG_VERSION"), GameProfile::Wordle.source().with_word_count(bundled.split_whitespace().count()));
+    let cli = Cli::command().long_version(long_version.as_str()).try_get_matches().and_then(|x| Cli::from_arg_matches(&x)).unwrap_or_else(|e| match e.use_stderr() {
+        true => { e.print().ok(); std::process::exit(EXIT_INVALID_INPUT) }
+        false => e.exit(), // help and version requests
+    });
+    // this only describes the interface, so it runs before any word list is loaded (the manual is generated by cargo xtask man)
+    if let Args::Completions { shell } = cli.command {
+        return clap_complete::generate(shell, &mut Cli::command(), env!("CARGO_BIN_NAME"), &mut io::stdout());
+    }
+    LOCALE.set(cli.lang.unwrap_or_else(Locale::from_env)).unwrap();
+    let profile = cli.game.unwrap_or(GameProfile::Wordle);
+    let words = match &cli.words {
+        Some(path) => std::fs::read_to_string(path).unwrap_or_else(|e| usage_error(EXIT_DICTIONARY, ErrorKind::Io, tr(Message::FailedToRead, &[&path.display(), &e]))).into(),
+        None => profile.words().unwrap_or_else(|| usage_error(EXIT_DICTIONARY, ErrorKind::MissingRequiredArgument, tr(Message::WordListNotBundled, &[&profile.name()]))),
+    };
+    let raw_words = words.split_whitespace();
+    let dictionary = profile.dictionary_with(&words).unwrap_or_else(|e| usage_error(EXIT_DICTIONARY, ErrorKind::InvalidValue, tr(Message::InvalidWordList, &[&format!("{:?}", e)])));
+    let dictionary = dictionary.with_source(match &cli.words {
+        Some(path) => DictionarySource::new(path.display().to_string()),
+        None => profile.source(),
+    });
+    let max_guesses = profile.max_guesses();
+    let mut progress_out = cli.progress_fd.map(open_progress);
+    let definitions = load_definitions(&cli.definitions);
+    let answer_words = cli.answers.as_ref().map(|path| std::fs::read_to_string(path).unwrap_or_else(|e| usage_error(EXIT_DICTIONARY, ErrorKind::Io, tr(Message::FailedToRead, &[&path.display(), &e]))));
+    let answers = answer_words.as_ref().map(|words| AnswerList::new(&dictionary, words.split_whitespace())
+        .unwrap_or_else(|word| usage_error(EXIT_DICTIONARY, ErrorKind::InvalidValue, tr(Message::AnswerNotInWordList, &[&word]))));
+    // with a separate answer list, the remaining legal guesses and possible answers differ, so both are shown
+    let answer_scores = |puzzle: &Puzzle, word: &str| match &answers {
+        Some(answers) => {
+            let score = puzzle.dual_score(word, answers).unwrap();
+            format!(" {}", tr(Message::AnswerScore, &[&tr(Message::Score, &[&score.answer_worst_case, &score.answer_average])]))
+        }
+        None => String::new(),
+    };
+
+    match cli.command {
+        Args::Solve { threads, explain, suggest_n, cache_dir, histogram, exact_opening, list, order, time_limit, preset, exact_depth, score_script, weights, score_weights, hard, feasible_only, answer_tolerance, inputs } => {
+            let preset = preset.map(|name| Preset::find(&name, &load_presets(cli.config.as_deref())).unwrap_or_else(|| {
+                usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, tr(Message::UnknownPreset, &[&name]))
+            }));
+            let puzzle = parse_puzzle_traced(&dictionary, &inputs, explain);
+            println!("{}\n{}", tr(Message::InputSummary, &[]), puzzle);
+            if let Some(answers) = &answers {
+                println!("{}\n", tr(Message::Remaining, &[&puzzle.candidates().len(), &puzzle.remaining_answers(answers)]));
+            }
+            if explain {
+                let report = puzzle.family_report();
+                for family in report.families.iter() {
+                    println!("{}", tr(Message::WordFamily, &[&family.join(" ")]));
+                }
+                if report.at_risk {
+                    print!("{}", tr(Message::FamilyWarning, &[&report.worst_chain, &puzzle.turns_left()]));
+                    match &report.probe {
+                        Some(probe) => println!("{}", tr(Message::FamilyProbe, &[&probe.word, &probe.groups])),
+                        None => println!(),
+                    }
+                }
+                if !report.families.is_empty() { println!(); }
+            }
+            if let Some(n) = list {
+                let order = if order == "positional" { CandidateOrder::Positional } else { CandidateOrder::Alphabetical };
+                let page = puzzle.candidate_page(order, n);
+                let more = if page.total > page.words.len() { format!(" ... ({} total)", page.total) } else { String::new() };
+                println!("{}{}\n", tr(Message::Candidates, &[&page.words.join(" ")]), more);
+            }
+            if answer_tolerance.is_nan() || answer_tolerance < 0.0 {
+                usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, tr(Message::InvalidAnswerTolerance, &[&answer_tolerance]));
+            }
+            let hard_mode = hard.then(|| puzzle.hard_mode_filter());
+            let admits = |word: &str| hard_mode.as_ref().map(|f| f.admits(word)).unwrap_or(true) && (!feasible_only || puzzle.feasible_filter().admits(word));
+            let time_limit = time_limit.map(|x| Duration::try_from_secs_f64(x).unwrap_or_else(|_| usage_error(EXIT_INVALID_INPUT, ErrorKind::InvalidValue, tr(Message::InvalidTimeLimit, &[&x]))));
+            let threads = threads.or(preset.as_ref().and_then(|x| x.threads)).unwrap_or_else(|| recommended_threads(dictionary.len(), puzzle.candidates().len()));
+            // the opening for a non-English alphabet is picked from a shortlist and cached, so it has no time limit or progress to report
+            let shortlist_opening = *dictionary.alphabet() != Alphabet::english() && puzzle.guess_count() == 0 && !exact_opening;
+            let conflict = |arg: &str, other: &str| -> ! {
+                usage_error(EXIT_INVALID_INPUT, ErrorKind::ArgumentConflict, tr(Message::ArgumentConflict, &[&arg, &other]))
+            };
+            for (given, arg) in [(time_limit.is_some(), "--time-limit"), (preset.is_some(), "--preset")] {
+                if given && shortlist_opening { conflict(arg, &tr(Message::ShortlistedOpeningConflict, &[])); }
+            }
+            if cli.progress_fd.is_some() {
+                if time_limit.is_some() { conflict("--progress-fd", "--time-limit"); }
+                if preset.is_some() { conflict("--progress-fd", "--preset"); }
+                if answer_tolerance > 0.0 { conflict("--progress-fd", "--answer-tolerance"); }
+                // these pick the guess without the minimax search, so there is no progress to report
+                for (given, arg) in [(suggest_n.is_some(), "--suggest-n"), (exact_depth, "--exact-depth"), (score_script.is_some(), "--score-script"), (weights.is_some(), "--weights"), (score_weights.is_some(), "--score-weights")] {
+                    if given { conflict("--progress-fd", arg); }
+                }
+                if shortlist_opening { conflict("--progress-fd", &tr(Message::ShortlistedOpeningConflict, &[])); }
+            }
+            if let Some(n) = suggest_n {
+                println!("{}", tr(Message::BestGuesses, &[]));
+                for (i, guess) in puzzle.rank_guesses(threads, admits, n).unwrap_or_else(|e| solve_failed(e)).iter().enumerate() {
+                    let feasible = if guess.feasible { format!(" {}", tr(Message::CouldBeAnswer, &[])) } else { String::new() };
+                    println!("{}. {}: {}{}{}", i + 1, describe(&guess.word, &definitions), tr(Message::Score, &[&guess.worst_case, &guess.average]), answer_scores(&puzzle, &guess.word), feasible);
+                }
+                return;
+            }
+            if exact_depth {
+                let best = puzzle.best_guess_exact(threads, admits).unwrap_or_else(|e| solve_failed(e));
+                let feasible = if best.feasible { format!(" {}", tr(Message::CouldBeAnswer, &[])) } else { String::new() };
+                println!("{}{}\n{}", tr(Message::BestGuess, &[&describe(&best.word, &definitions)]), feasible, tr(Message::ProvenDepth, &[&best.depth]));
+                return;
+            }
+            let open_cache = |dir: &std::path::Path| FileStore::open(dir).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::CacheFailed, &[&dir.display(), &e])));
+            let best = if let Some(preset) = &preset {
+                let mut opts = preset.apply(SolveOptions::new()).with_threads(threads).with_hard_mode(hard).with_feasible_only(feasible_only).with_answer_tolerance(answer_tolerance);
+                if let Some(time) = time_limit { opts = opts.with_limits(ComputeLimits { time: Some(time), ..preset.limits }); }
+                puzzle.best_guess_opts(&opts)
+            } else if let Some(path) = &score_script {
+                best_guess_scripted(&puzzle, threads, admits, path)
+            } else if let Some(scorer) = &score_weights {
+                puzzle.best_guess_by(threads, admits, scorer)
+            } else if let Some(path) = &weights {
+                let prior = load_weights(&dictionary, path);
+                puzzle.best_guess_opts(&SolveOptions::new().with_threads(threads).with_hard_mode(hard).with_feasible_only(feasible_only).with_strategy(Strategy::ExpectedRemaining).with_prior(&prior))
+            } else if shortlist_opening {
+                // the result is the same every time, so it's worth caching even if no cache was requested
+                let store: Box<dyn CacheStore> = match (&cache_dir, default_cache_dir().map(FileStore::open)) {
+                    (Some(dir), _) => Box::new(open_cache(dir)),
+                    (None, Some(Ok(store))) => Box::new(store),
+                    (None, _) => Box::new(MemoryStore::new()),
                 };
-                let guess = &input[..sep];
-                let response: Vec<_> = input[sep+1..].chars().map(|ch| match ch {
-                    'c' => Hint::Correct,
-                    'p' => Hint::Present,
-                    'a' => Hint::Absent,
-                    x => panic!("unknown response '{}' (expected 'c' (correct), 'p' (present), or 'a' (absent))", x),
-                }).collect();
-                parsed_inputs.push((guess, response));
+                println!("{}", tr(Message::ShortlistedOpening, &[&Puzzle::OPENING_SHORTLIST_LEN]));
+                puzzle.opening_guess_cached(threads, Puzzle::OPENING_SHORTLIST_LEN, &*store)
+            } else {
+                match (&cache_dir, progress_out.as_mut(), time_limit) {
+                    (Some(dir), _, _) => puzzle.best_guess_cached(threads, &open_cache(dir)),
+                    (None, _, Some(time)) => puzzle.best_guess_opts(&SolveOptions::new().with_threads(threads).with_hard_mode(hard).with_feasible_only(feasible_only)
+                        .with_answer_tolerance(answer_tolerance).with_limits(ComputeLimits { time: Some(time), ..Default::default() })),
+                    (None, _, None) if answer_tolerance > 0.0 => puzzle.best_guess_opts(&SolveOptions::new().with_threads(threads).with_hard_mode(hard).with_feasible_only(feasible_only).with_answer_tolerance(answer_tolerance)),
+                    (None, Some(out), None) => puzzle.best_guess_progress(threads, admits, |x: &Progress| writeln!(out, "{}", x.to_json()).unwrap()),
+                    (None, None, None) => puzzle.best_guess(threads, admits),
+                }
+            }.unwrap_or_else(|e| solve_failed(e));
+            println!("{}\n{}{}", tr(Message::BestGuess, &[&describe(&best.word, &definitions)]),
+                tr(Message::RemainingWords, &[&tr(Message::Score, &[&best.worst_case, &best.average])]), answer_scores(&puzzle, &best.word));
+            if let Some(expected) = best.expected_guesses {
+                println!("{}", tr(Message::EndgameSolved, &[&expected]));
+            }
+            if best.approximate {
+                println!("{}", tr(Message::Approximate, &[]));
             }
+            if histogram {
+                const HISTOGRAM_WIDTH: usize = 50;
+                print!("{}\n{}", tr(Message::HintHistogram, &[]), bar_chart(&best.partition, HISTOGRAM_WIDTH));
+            }
+        }
+        Args::Interactive { threads, penalty, inputs } => {
+            const TICKER_LEN: usize = 10;
+            const COMPLETIONS_LEN: usize = 20;
 
-            let mut puzzle = Puzzle::new(&dictionary);
-            for (guess, response) in parsed_inputs.iter() {
-                puzzle.guess(guess, response).unwrap();
+            let prior = Prior::uniform(&dictionary).with_modifier(Morphology::new(penalty));
+            let mut puzzle = parse_puzzle(&dictionary, &inputs);
+            let stdin = std::io::stdin();
+            let mut lines = stdin.lock().lines();
+            loop {
+                let candidates = puzzle.candidates();
+                let total: u64 = candidates.iter().map(|&w| prior.weight(w) as u64).sum();
+                println!("{}", tr(Message::CandidateCount, &[&candidates.len()]));
+                for (word, weight) in prior.most_likely(candidates.iter().copied(), TICKER_LEN) {
+                    println!("  {} {:>5.1}%", word, 100.0 * weight as f64 / total.max(1) as f64);
+                }
+                match puzzle.status() {
+                    PuzzleStatus::Inconsistent => solve_failed(SolveErr::Inconsistent),
+                    PuzzleStatus::Solved => return,
+                    PuzzleStatus::Unsolved => (),
+                }
+                if !inputs.inputs.is_empty() || !inputs.guesses.is_empty() || candidates.len() < dictionary.len() {
+                    let threads = threads.unwrap_or_else(|| recommended_threads(dictionary.len(), candidates.len()));
+                    println!("{}", tr(Message::BestGuess, &[&describe(&puzzle.best_guess(threads, AnyWord).unwrap_or_else(|e| solve_failed(e)).word, &definitions)]));
+                }
+
+                loop {
+                    print!("> ");
+                    std::io::stdout().flush().unwrap();
+                    let line = match lines.next() {
+                        Some(x) => x.unwrap(),
+                        None => { println!(); return; }
+                    };
+                    // a partial guess ending in tab or '?' lists the words it could be completed to
+                    if let Some(prefix) = line.trim_start().strip_suffix(|ch| ch == '\t' || ch == '?') {
+                        let completions: Vec<_> = dictionary.words_with_prefix(prefix.trim_end()).collect();
+                        match completions.len() {
+                            0 => println!("{}", tr(Message::NoCompletions, &[&prefix.trim_end()])),
+                            n if n > COMPLETIONS_LEN => println!("{}", tr(Message::Completions, &[&completions[..COMPLETIONS_LEN].join(" "), &n])),
+                            _ => println!("{}", completions.join(" ")),
+                        }
+                        continue;
+                    }
+                    if line.trim() == "undo" {
+                        match puzzle.undo() {
+                            Some((guess, _)) => { println!("{}", tr(Message::Undid, &[&guess])); puzzle.force_reduce(); break }
+                            None => { println!("{}", tr(Message::NothingToUndo, &[])); continue }
+                        }
+                    }
+                    let (guess, response) = match line.trim().split_once(':') {
+                        Some(x) => x,
+                        None => { println!("{}", tr(Message::InteractiveUsage, &[])); continue; }
+                    };
+                    if !dictionary.contains(guess) {
+                        let suggestions = dictionary.nearest(guess, Game::SUGGESTION_DISTANCE);
+                        match &suggestions[..suggestions.len().min(Game::MAX_SUGGESTIONS)] {
+                            [] => println!("{}", tr(Message::NotInWordList, &[&guess])),
+                            suggestions => println!("{}", tr(Message::NotInWordListSuggest, &[&guess, &suggestions.join(", ")])),
+                        }
+                        continue;
+                    }
+                    let response = match parse_response(response) {
+                        Ok(x) => x,
+                        Err(e) => { println!("{}", e); continue; }
+                    };
+                    match puzzle.guess(guess, &response) {
+                        Ok(()) => { puzzle.force_reduce(); break }
+                        Err(error) => println!("{}", tr(Message::InvalidInput, &[&HistoryError { turn: puzzle.guess_count(), error }])),
+                    }
+                }
             }
+        }
+        Args::Dot { threads, depth, inputs } => {
+            let puzzle = parse_puzzle(&dictionary, &inputs);
+            let threads = threads.unwrap_or_else(|| recommended_threads(dictionary.len(), puzzle.candidates().len()));
+            print!("{}", puzzle.to_dot(depth, threads).unwrap_or_else(|e| solve_failed(e)));
+        }
+        Args::Swap { input } => {
+            let (letters, response) = parse_input(&input);
+            let puzzle = SwapPuzzle::new(&dictionary, letters, &response).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::InvalidInput, &[&format!("{:?}", e)])));
+            println!("{}", tr(Message::Candidates, &[&puzzle.candidates().join(" ")]));
+            match puzzle.best_swaps() {
+                Ok((target, swaps)) => {
+                    println!("{}", tr(Message::SwapTarget, &[&target, &swaps.len()]));
+                    for (a, b) in swaps { println!("{}", tr(Message::SwapStep, &[&(a + 1), &(b + 1)])); }
+                }
+                Err(e) => solve_failed(e),
+            }
+        }
+        Args::Infer { answer, limit, hard } => {
+            let mut grid = String::new();
+            std::io::stdin().read_to_string(&mut grid).unwrap();
+            let grid = parse_share_grid(&grid);
+
+            let sequences = infer_guesses(&grid, &answer, &dictionary, hard, limit).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::InvalidInput, &[&format!("{:?}", e)])));
+            for sequence in sequences.iter() {
+                println!("{}", sequence.join(" "));
+            }
+            if sequences.len() >= limit { println!("{}", tr(Message::InferTruncated, &[&limit])); }
+            else if sequences.is_empty() { fail(EXIT_INCONSISTENT, tr(Message::NoInferredSequence, &[])); }
+        }
+        Args::Train { threads, seed, hard, alternatives } => {
+            let day = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() / 86400;
+            let mut game = match seed {
+                Some(seed) => Game::random(&dictionary, &mut SolverRng::new(seed)),
+                None => Game::daily(&dictionary, day),
+            }.with_max_guesses(max_guesses).with_hard_mode(hard);
+
+            let stdin = std::io::stdin();
+            let mut lines = stdin.lock().lines();
+            while game.status() == GameStatus::InProgress {
+                print!("{}", tr(Message::TrainPrompt, &[&(game.history().len() + 1), &game.max_guesses()]));
+                std::io::stdout().flush().unwrap();
+                let line = match lines.next() {
+                    Some(x) => x.unwrap(),
+                    None => {
+                        println!("\n{}", tr(Message::GaveUp, &[&game.answer()]));
+                        return;
+                    }
+                };
+                match game.guess(line.trim()) {
+                    Ok(hint) => println!("{} {}", hint.iter().map(|h| h.to_emoji()).collect::<String>(), line.trim()),
+                    Err(GameError::NotInDictionary { word, suggestions }) if suggestions.is_empty() => println!("{}", tr(Message::NotInWordList, &[&word])),
+                    Err(GameError::NotInDictionary { word, suggestions }) => println!("{}", tr(Message::NotInWordListSuggest, &[&word, &suggestions.join(", ")])),
+                    Err(GameError::HardModeViolation { word, previous }) => println!("{}", tr(Message::HardModeViolation, &[&word, &previous])),
+                    Err(e) => println!("{}", tr(Message::InvalidGuess, &[&format!("{:?}", e)])),
+                }
+            }
+
+            match game.status() {
+                GameStatus::Won => println!("\n{}", tr(Message::TrainWon, &[&game.history().len(), &game.max_guesses()])),
+                _ => println!("\n{}", tr(Message::TrainLost, &[&game.answer()])),
+            }
+            let name = profile.name();
+            let title = match seed {
+                Some(seed) => format!("{} (seed {})", name, seed),
+                None => format!("{} {}", name, day),
+            };
+            println!("\n{}\n", game.share_grid(&title).unwrap());
+
+            println!("{}", tr(Message::Grading, &[]));
+            let threads = threads.unwrap_or_else(|| recommended_threads(dictionary.len(), dictionary.len()));
+            for (i, grade) in game.grade(threads).unwrap_or_else(|e| solve_failed(e)).iter().enumerate() {
+                println!("{}. {}", i + 1, tr(Message::Grade, &[&grade.guess, &grade.candidates, &tr(Message::Score, &[&grade.worst_case, &grade.average]),
+                    &grade.best_guess, &tr(Message::Score, &[&grade.best_worst_case, &grade.best_average])]));
+            }
+            if alternatives {
+                const ALTERNATIVES: usize = 3;
+                println!("\n{}", tr(Message::Alternatives, &[]));
+                for (i, turn) in game.postmortem(threads, ALTERNATIVES).unwrap_or_else(|e| solve_failed(e)).iter().enumerate() {
+                    println!("{}. {}", i + 1, tr(Message::AlternativeTurn, &[&turn.guess, &turn.remaining]));
+                    for line in turn.alternatives.iter() {
+                        let steps: Vec<_> = line.steps.iter().map(|(word, left)| format!("{} ({})", word, left)).collect();
+                        let outcome = match line.won {
+                            true => tr(Message::AlternativeWon, &[&(i + line.steps.len())]),
+                            false => tr(Message::AlternativeLost, &[]),
+                        };
+                        println!("   {}: {} | {} | {}", line.guess, tr(Message::Score, &[&line.worst_case, &line.average]), steps.join(" -> "), outcome);
+                    }
+                }
+            }
+        }
+        Args::Source => println!("{}", dictionary.provenance_json()),
+        Args::DictStats => {
+            let stats = dictionary.stats();
+            println!("{}", tr(Message::WordCount, &[&dictionary.len()]));
+            println!("{}", tr(Message::RepeatedLetterWords, &[&stats.duplicate_letter_words]));
+            println!("{}", tr(Message::AnagramGroups, &[&stats.anagram_groups, &stats.anagram_words]));
+            println!();
 
-            println!("input summary:\n{}", puzzle);
-            let (best_guess, worst_rem, avg_rem) = puzzle.best_guess(threads).unwrap();
-            println!("best guess: {}\nremaining words: {} worst, {} avg.", best_guess, worst_rem, avg_rem);
+            let slots: String = (1..=stats.positional_counts.len()).map(|i| format!(" {:>6}", format!("#{}", i))).collect();
+            // the columns widen to fit their (translated) headings
+            let headings = [tr(Message::LetterColumn, &[]), tr(Message::TotalColumn, &[]), tr(Message::WordsColumn, &[])];
+            let [w0, w1, w2] = [0, 1, 2].map(|i| headings[i].chars().count().max([6, 7, 7][i]));
+            println!("{:<w0$} {:>w1$} {:>w2$}{}", headings[0], headings[1], headings[2], slots);
+            for (i, &letter) in stats.letters.iter().enumerate() {
+                if stats.letter_counts[i] == 0 { continue }
+                let slots: String = stats.positional_counts.iter().map(|counts| format!(" {:>6}", counts[i])).collect();
+                println!("{:>w0$} {:>w1$} {:>w2$}{}", letter, stats.letter_counts[i], stats.word_counts[i], slots);
+            }
         }
-        Args::Bench { mut threads, verbose } => {
-            threads = threads.max(1);
+        Args::Bench { threads, verbose, sample, seed, checkpoint, baseline, save, max_guess_regression, max_time_regression } => {
+            // read the baseline first, so that a bad path doesn't waste a whole run
+            let baseline = baseline.map(|path| {
+                let text = std::fs::read_to_string(&path).unwrap_or_else(|e| usage_error(EXIT_INVALID_INPUT, ErrorKind::Io, tr(Message::FailedToRead, &[&path.display(), &e])));
+                BenchReport::from_json(&text).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::InvalidBaseline, &[&path.display(), &format!("{:?}", e)])))
+            });
+            let threads = threads.unwrap_or_else(|| recommended_threads(dictionary.len(), dictionary.len())).max(1);
 
-            let init_guess = Puzzle::new(&dictionary).best_guess(threads).unwrap().0;
-            let words_iter = Mutex::new(raw_words.into_iter().fuse());
-            let results = Mutex::new(vec![]);
+            let mut answers: Vec<_> = raw_words.collect();
+            if let Some(sample) = sample {
+                SolverRng::new(seed).shuffle(&mut answers);
+                answers.truncate(sample);
+            }
+            let total = answers.len();
+
+            let mut resumed = vec![];
+            let checkpoint = checkpoint.map(|path| {
+                let (checkpoint, completed) = Checkpoint::open(&path, &dictionary)
+                    .unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::CheckpointFailed, &[&path.display(), &e])));
+                let completed: HashMap<_, _> = completed.into_iter().collect();
+                resumed.extend(answers.iter().filter_map(|&x| completed.get(x).copied()));
+                answers.retain(|&x| !completed.contains_key(x));
+                checkpoint
+            });
+            if !resumed.is_empty() { println!("{}\n", tr(Message::Resuming, &[&resumed.len(), &total])); }
+
+            let init_guess = Puzzle::new(&dictionary).best_guess(threads, AnyWord).unwrap().word;
+            let words_iter = Mutex::new(answers.into_iter().fuse());
+            let results = Mutex::new(resumed);
+            let times = Mutex::new(vec![]);
+            let progress_out = Mutex::new(progress_out);
+            let checkpoint = Mutex::new(checkpoint);
+            let start = Instant::now();
 
             crossbeam::scope(|s| {
                 for _ in 0..threads {
@@ -73,21 +619,28 @@ fn main() {
                                 Some(x) => x,
                                 None => break,
                             };
-                            let mut puzzle = Puzzle::new(&dictionary);
-                            let mut guesses = 0u8;
-
-                            loop {
-                                let guess = match guesses {
-                                    0 => init_guess.clone(),
-                                    _ => puzzle.best_guess(1).unwrap().0,
-                                };
-                                guesses += 1;
-                                puzzle.guess(&guess, &get_hint(&guess, answer).unwrap()).unwrap();
-                                if guess == answer { break }
-                            }
+                            let policy = |turn: usize, _: usize| match turn {
+                                0 => Strategy::Fixed(init_guess.clone()),
+                                _ => Strategy::Minimax,
+                            };
+                            let answer_start = Instant::now();
+                            let guesses = play(&dictionary, answer, policy, 1).unwrap().len() as u8;
+                            times.lock().unwrap().push(answer_start.elapsed());
 
-                            results.lock().unwrap().push(guesses);
-                            if verbose { println!("{} took {} guesses", answer, guesses); }
+                            let mut results = results.lock().unwrap();
+                            results.push(guesses);
+                            let done = results.len();
+                            if let Some(checkpoint) = checkpoint.lock().unwrap().as_mut() {
+                                checkpoint.record(answer, guesses).unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::CheckpointWriteFailed, &[&e])));
+                            }
+                            if let Some(out) = progress_out.lock().unwrap().as_mut() {
+                                if done * 100 / total > (done - 1) * 100 / total {
+                                    let progress = Progress { done, total, elapsed: start.elapsed(), best: None };
+                                    writeln!(out, "{}", progress.to_json()).unwrap();
+                                }
+                            }
+                            drop(results);
+                            if verbose { println!("{}", tr(Message::AnswerGuesses, &[&answer, &guesses])); }
                         }
                     });
                 }
@@ -95,30 +648,33 @@ fn main() {
 
             if verbose { println!(); }
             let results = results.into_inner().unwrap();
+            let report = BenchReport { source: dictionary.source().cloned(), ..BenchReport::new(dictionary.fingerprint(), &results, &times.into_inner().unwrap()) };
+
+            println!("{}", tr(Message::BenchResults, &[&report.words]));
+            println!("{}", tr(Message::BenchMin, &[&report.min_guesses]));
+            println!("{}", tr(Message::BenchMax, &[&report.max_guesses]));
+            println!("{}", tr(Message::BenchAvg, &[&format!("{:.04}", report.avg_guesses)]));
+            println!("{}", tr(Message::BenchStd, &[&format!("{:.04}", report.std_guesses)]));
+            println!("{}", tr(Message::BenchTime, &[&format!("{:.3}ms", report.p95_time.as_secs_f64() * 1000.0)]));
 
-            let mut min = u8::MAX;
-            let mut max = 0;
-            let mut avg = 0.0;
-            for &x in results.iter() {
-                min = min.min(x);
-                max = max.max(x);
-                avg += x as f64;
-            }
-            avg /= results.len() as f64;
-
-            let mut std = 0.0;
-            for &x in results.iter() {
-                let diff = x as f64 - avg;
-                std += diff * diff;
-            }
-            std /= results.len() as f64;
-            std = std.sqrt();
-
-            println!("results over {} words:", results.len());
-            println!("min: {}", min);
-            println!("max: {}", max);
-            println!("avg: {:.04}", avg);
-            println!("std: {:.04}", std);
+            if let Some(path) = &save {
+                std::fs::write(path, report.to_json() + "\n").unwrap_or_else(|e| fail(EXIT_INVALID_INPUT, tr(Message::FailedToWrite, &[&path.display(), &e])));
+            }
+            if let Some(baseline) = baseline {
+                let thresholds = BenchThresholds { avg_guesses: max_guess_regression, p95_time: max_time_regression / 100.0 };
+                let cmp = report.compare(&baseline, &thresholds);
+                let flag = |regressed: bool| if regressed { format!(" {}", tr(Message::Regression, &[])) } else { String::new() };
+                println!("\n{}", tr(Message::BaselineComparison, &[]));
+                if !cmp.same_words { println!("{}", tr(Message::BaselineWordsWarning, &[&baseline.words])); }
+                println!("{}", tr(Message::BenchAvg, &[&format!("{:.04} -> {:.04} ({:+.04}){}", baseline.avg_guesses, report.avg_guesses, cmp.avg_guesses, flag(cmp.guess_regression))]));
+                println!("{}", tr(Message::BenchMax, &[&format!("{} -> {} ({:+})", baseline.max_guesses, report.max_guesses, cmp.max_guesses)]));
+                println!("{}", tr(Message::BenchTime, &[&format!("{:.3}ms -> {:.3}ms ({:+.1}%){}", baseline.p95_time.as_secs_f64() * 1000.0, report.p95_time.as_secs_f64() * 1000.0,
+                    cmp.p95_time * 100.0, flag(cmp.time_regression))]));
+                if cmp.regressed() {
+                    fail(EXIT_REGRESSION, tr(Message::Regressed, &[]));
+                }
+            }
         }
+        Args::Completions { .. } => unreachable!("handled before loading the word list"),
     }
 }
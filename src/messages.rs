@@ -0,0 +1,462 @@
+use std::fmt;
+
+/// A language for user-facing messages, which is independent of the language of the word list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    English,
+    Spanish,
+}
+impl Locale {
+    /// All supported locales.
+    pub const ALL: [Locale; 2] = [Locale::English, Locale::Spanish];
+
+    /// Gets the language code of the locale (e.g., `es`).
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::Spanish => "es",
+        }
+    }
+    /// Finds the locale for a language code, with or without a region and encoding (e.g., `es`, `es-MX`, or `es_ES.UTF-8`).
+    /// If the language is not supported, returns [`None`].
+    pub fn parse(code: &str) -> Option<Self> {
+        let lang = code.split(['_', '-', '.']).next().unwrap_or("").to_lowercase();
+        Self::ALL.into_iter().find(|x| x.code() == lang)
+    }
+    /// Like [`Locale::parse`], but unsupported languages fall back to english.
+    pub fn from_code(code: &str) -> Self {
+        Self::parse(code).unwrap_or(Locale::English)
+    }
+    /// Picks the locale from the environment, checking `LC_ALL`, `LC_MESSAGES`, and `LANG` in that order (as POSIX does).
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter().filter_map(|x| std::env::var(x).ok()).find(|x| !x.is_empty())
+            .map(|x| Self::from_code(&x)).unwrap_or(Locale::English)
+    }
+}
+
+/// A user-facing message of the command line interface, which can be formatted in any [`Locale`].
+/// Each `{}` in a message template is replaced by the next argument (see [`Message::format`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Message {
+    /// `input summary:`
+    InputSummary,
+    /// `candidates: {words}`
+    Candidates,
+    /// `{count} candidates`
+    CandidateCount,
+    /// `best guess: {word}`
+    BestGuess,
+    /// `best guesses:`
+    BestGuesses,
+    /// `{worst} worst, {average} avg.`
+    Score,
+    /// `remaining words: {score}`
+    RemainingWords,
+    /// `(could be the answer)`
+    CouldBeAnswer,
+    /// `(answers: {score})`, for the score counting only possible answers
+    AnswerScore,
+    /// `endgame solved exactly: {expected} guesses expected`
+    EndgameSolved,
+    /// `solves every answer within {depth} guesses`, for a guess found by [`Puzzle::best_guess_exact`](wordle_solver::Puzzle::best_guess_exact)
+    ProvenDepth,
+    /// `(approximate: ...)`, for a guess found by a search cut short by its [`ComputeLimits`](wordle_solver::ComputeLimits)
+    Approximate,
+    /// The error for [`SolveErr::Inconsistent`](wordle_solver::SolveErr::Inconsistent).
+    Inconsistent,
+    /// The error for [`SolveErr::NoAdmissibleGuess`](wordle_solver::SolveErr::NoAdmissibleGuess).
+    NoAdmissibleGuess,
+    /// The error for [`SolveErr::InvalidExtraGuess`](wordle_solver::SolveErr::InvalidExtraGuess), given the word.
+    InvalidExtraGuess,
+    /// The error for [`SolveErr::TooManyAnswers`](wordle_solver::SolveErr::TooManyAnswers), given the number of words and the limit.
+    TooManyAnswers,
+    /// `remaining: {guesses} legal guesses, {answers} possible answers`
+    Remaining,
+    /// `word family: {words}`
+    WordFamily,
+    /// The warning for a word family which may take more guesses than are left, given the guesses needed and left.
+    FamilyWarning,
+    /// The suggestion to follow [`Message::FamilyWarning`], given the probe word and how many groups it makes.
+    FamilyProbe,
+    /// `undid {guess}`
+    Undid,
+    /// `nothing to undo`
+    NothingToUndo,
+    /// `target: {word} ({count} swaps)`
+    SwapTarget,
+    /// `guess {turn}/{max}: `, the prompt for a training game
+    TrainPrompt,
+    /// `gave up, the answer was {answer}`
+    GaveUp,
+    /// `'{word}' is not in the word list`
+    NotInWordList,
+    /// [`Message::NotInWordList`] with suggestions, given the word and the suggestions.
+    NotInWordListSuggest,
+    /// The hard mode error for a guess, given the guess and the earlier guess whose letters it doesn't reuse.
+    HardModeViolation,
+    /// `invalid guess: {error}`
+    InvalidGuess,
+    /// `solved in {guesses}/{max}!`
+    TrainWon,
+    /// `out of guesses, the answer was {answer}`
+    TrainLost,
+    /// `grading...`
+    Grading,
+    /// The grade of a guess, given the guess, the candidates before it, its [`Message::Score`], the solver's guess, and the solver's score.
+    Grade,
+    /// `alternatives:`
+    Alternatives,
+    /// `{guess} ({count} left)`
+    AlternativeTurn,
+    /// `solved in {guesses}`
+    AlternativeWon,
+    /// `not solved`
+    AlternativeLost,
+    /// The error for a file which couldn't be read, given the path and the error.
+    FailedToRead,
+    /// The error for a file which couldn't be written, given the path and the error.
+    FailedToWrite,
+    /// The error for an invalid `--config` file, given the path and the error.
+    InvalidConfig,
+    /// The error for a `--progress-fd` which couldn't be opened, given the descriptor and the error.
+    ProgressFdFailed,
+    /// The error for a `--score-script` which couldn't be loaded, given the path and the error.
+    #[allow(dead_code)] // only used with the scripting feature
+    InvalidScoreScript,
+    /// The error for a `--score-script` which failed while scoring, given the path and the error.
+    #[allow(dead_code)] // only used with the scripting feature
+    ScoreScriptFailed,
+    /// The error for `--score-script` without the scripting feature.
+    #[allow(dead_code)] // only used without the scripting feature
+    ScriptingDisabled,
+    /// The error for a malformed line of a `--weights` file, given the path and the line number.
+    WeightsSyntax,
+    /// The error for a word of a `--weights` file which isn't in the dictionary, given the path and the word.
+    WeightsUnknownWord,
+    /// The error for an input which isn't a `<guess>:<response>` pair, given the input.
+    UnknownInput,
+    /// The error for a different number of `--guess` and `--hint` arguments.
+    UnmatchedGuess,
+    /// `invalid input: {error}`
+    InvalidInput,
+    /// The warning for a guess in the inputs which isn't in the word list, given the guess.
+    UnknownWordWarning,
+    /// The error for a `--game` whose word list must be given with `--words`, given the game.
+    WordListNotBundled,
+    /// `invalid word list: {error}`
+    InvalidWordList,
+    /// The error for a word of the `--answers` file which isn't in the word list, given the word.
+    AnswerNotInWordList,
+    /// The error for a `--preset` which isn't defined, given its name.
+    UnknownPreset,
+    /// The error for a negative or NaN `--answer-tolerance`, given the value.
+    InvalidAnswerTolerance,
+    /// The error for a `--time-limit` which isn't a valid duration, given the value.
+    InvalidTimeLimit,
+    /// `{argument} can't be used with {other}`
+    ArgumentConflict,
+    /// The other side of [`Message::ArgumentConflict`] for options which don't apply to the shortlisted opening of a non-English word list.
+    ShortlistedOpeningConflict,
+    /// The error for a `--cache-dir` which couldn't be opened, given the directory and the error.
+    CacheFailed,
+    /// The note that the opening of a non-English word list is picked from a shortlist, given its length.
+    ShortlistedOpening,
+    /// `remaining words by hint:`
+    HintHistogram,
+    /// `no words start with '{prefix}'`
+    NoCompletions,
+    /// `{words} ... ({count} words)`, for completions cut short
+    Completions,
+    /// The reminder of what interactive mode expects, for a line it doesn't understand.
+    InteractiveUsage,
+    /// `swap {a} and {b}`
+    SwapStep,
+    /// `(output truncated to {count} sequences)`
+    InferTruncated,
+    /// The error for a share grid which no sequence of guesses could produce.
+    NoInferredSequence,
+    /// `{count} words`
+    WordCount,
+    /// `{count} words with repeated letters`
+    RepeatedLetterWords,
+    /// `{groups} anagram groups ({words} words)`
+    AnagramGroups,
+    /// The heading of the letter column of the letter statistics.
+    LetterColumn,
+    /// The heading of the column of total letter counts.
+    TotalColumn,
+    /// The heading of the column of how many words have each letter.
+    WordsColumn,
+    /// The error for an invalid `--baseline` report, given the path and the error.
+    InvalidBaseline,
+    /// The error for a `--checkpoint` which couldn't be opened, given the path and the error.
+    CheckpointFailed,
+    /// `failed to write checkpoint: {error}`
+    CheckpointWriteFailed,
+    /// `resuming with {done} of {total} words completed`
+    Resuming,
+    /// `{answer} took {guesses} guesses`
+    AnswerGuesses,
+    /// `results over {count} words:`
+    BenchResults,
+    /// `min: {guesses}`
+    BenchMin,
+    /// `max: {guesses}`
+    BenchMax,
+    /// `avg: {guesses}`
+    BenchAvg,
+    /// `std: {guesses}`
+    BenchStd,
+    /// `p95 time: {time}`
+    BenchTime,
+    /// `compared to baseline:`
+    BaselineComparison,
+    /// The warning for a baseline run on different words, given how many.
+    BaselineWordsWarning,
+    /// `(regression)`, marking a benchmark result past its threshold
+    Regression,
+    /// The error for a benchmark which regressed past its thresholds.
+    Regressed,
+}
+impl Message {
+    /// Gets the template for the message in the given locale.
+    pub fn template(self, locale: Locale) -> &'static str {
+        use Message::*;
+        match locale {
+            Locale::English => match self {
+                InputSummary => "input summary:",
+                Candidates => "candidates: {}",
+                CandidateCount => "{} candidates",
+                BestGuess => "best guess: {}",
+                BestGuesses => "best guesses:",
+                Score => "{} worst, {} avg.",
+                RemainingWords => "remaining words: {}",
+                CouldBeAnswer => "(could be the answer)",
+                AnswerScore => "(answers: {})",
+                EndgameSolved => "endgame solved exactly: {} guesses expected",
+                ProvenDepth => "solves every answer within {} guesses",
+                Approximate => "(approximate: the time limit was reached before every guess was scored)",
+                Inconsistent => "no word is consistent with the hints (check for a mistyped hint)",
+                NoAdmissibleGuess => "no word in the word list can be guessed",
+                InvalidExtraGuess => "invalid extra guess '{}'",
+                TooManyAnswers => "{} words could be the answer, but an exact search supports at most {}",
+                Remaining => "remaining: {} legal guesses, {} possible answers",
+                WordFamily => "word family: {}",
+                FamilyWarning => "warning: guessing these one at a time could take {} guesses, but only {} are left",
+                FamilyProbe => "; consider probing with {} (splits them into {} groups)",
+                Undid => "undid {}",
+                NothingToUndo => "nothing to undo",
+                SwapTarget => "target: {} ({} swaps)",
+                TrainPrompt => "guess {}/{}: ",
+                GaveUp => "gave up, the answer was {}",
+                NotInWordList => "'{}' is not in the word list",
+                NotInWordListSuggest => "'{}' is not in the word list (did you mean {}?)",
+                HardModeViolation => "'{}' doesn't reuse the letters revealed by '{}' (hard mode)",
+                InvalidGuess => "invalid guess: {}",
+                TrainWon => "solved in {}/{}!",
+                TrainLost => "out of guesses, the answer was {}",
+                Grading => "grading...",
+                Grade => "{} ({} candidates): {} | solver: {}: {}",
+                Alternatives => "alternatives:",
+                AlternativeTurn => "{} ({} left)",
+                AlternativeWon => "solved in {}",
+                AlternativeLost => "not solved",
+                FailedToRead => "failed to read {}: {}",
+                FailedToWrite => "failed to write {}: {}",
+                InvalidConfig => "invalid config file {}: {}",
+                ProgressFdFailed => "failed to open --progress-fd {}: {}",
+                InvalidScoreScript => "invalid score script {}: {}",
+                ScoreScriptFailed => "score script {} {}",
+                ScriptingDisabled => "--score-script needs the solver to be built with the scripting feature",
+                WeightsSyntax => "{}:{}: expected <word> <weight>",
+                WeightsUnknownWord => "{}: '{}' is not in the dictionary",
+                UnknownInput => "unknown input '{}' (expected <guess>:<response>, see -h for info)",
+                UnmatchedGuess => "each --guess must have a matching --hint",
+                InvalidInput => "invalid input: {}",
+                UnknownWordWarning => "warning: '{}' is not in the word list (its hint is still applied)",
+                WordListNotBundled => "the word list for {} is not bundled, so it must be given with --words",
+                InvalidWordList => "invalid word list: {}",
+                AnswerNotInWordList => "answer '{}' is not in the word list",
+                UnknownPreset => "unknown preset '{}' (define it in the --config file)",
+                InvalidAnswerTolerance => "invalid --answer-tolerance {} (expected a non-negative number of words)",
+                InvalidTimeLimit => "invalid --time-limit {} (expected a number of seconds)",
+                ArgumentConflict => "{} can't be used with {}",
+                ShortlistedOpeningConflict => "the shortlisted opening of a non-English word list (add --exact-opening to search every word)",
+                CacheFailed => "failed to open cache {}: {}",
+                ShortlistedOpening => "(the opening is chosen from the {} best words by letter coverage, see --exact-opening)",
+                HintHistogram => "remaining words by hint:",
+                NoCompletions => "no words start with '{}'",
+                Completions => "{} ... ({} words)",
+                InteractiveUsage => "expected <guess>:<response> or undo (or end a partial guess with '?' to list completions)",
+                SwapStep => "swap {} and {}",
+                InferTruncated => "(output truncated to {} sequences)",
+                NoInferredSequence => "no guess sequence is consistent with the grid",
+                WordCount => "{} words",
+                RepeatedLetterWords => "{} words with repeated letters",
+                AnagramGroups => "{} anagram groups ({} words)",
+                LetterColumn => "letter",
+                TotalColumn => "total",
+                WordsColumn => "words",
+                InvalidBaseline => "invalid baseline {}: {}",
+                CheckpointFailed => "failed to open checkpoint {}: {}",
+                CheckpointWriteFailed => "failed to write checkpoint: {}",
+                Resuming => "resuming with {} of {} words completed",
+                AnswerGuesses => "{} took {} guesses",
+                BenchResults => "results over {} words:",
+                BenchMin => "min: {}",
+                BenchMax => "max: {}",
+                BenchAvg => "avg: {}",
+                BenchStd => "std: {}",
+                BenchTime => "p95 time: {}",
+                BaselineComparison => "compared to baseline:",
+                BaselineWordsWarning => "warning: the baseline was run on a different word list or sample ({} words)",
+                Regression => "(regression)",
+                Regressed => "the benchmark regressed past its thresholds",
+            }
+            Locale::Spanish => match self {
+                InputSummary => "resumen de la entrada:",
+                Candidates => "candidatas: {}",
+                CandidateCount => "{} candidatas",
+                BestGuess => "mejor intento: {}",
+                BestGuesses => "mejores intentos:",
+                Score => "{} en el peor caso, {} de media",
+                RemainingWords => "palabras restantes: {}",
+                CouldBeAnswer => "(podría ser la respuesta)",
+                AnswerScore => "(respuestas: {})",
+                EndgameSolved => "final resuelto exactamente: {} intentos esperados",
+                ProvenDepth => "resuelve cualquier respuesta en {} intentos como máximo",
+                Approximate => "(aproximado: se alcanzó el límite de tiempo antes de puntuar todos los intentos)",
+                Inconsistent => "ninguna palabra es coherente con las pistas (comprueba si alguna pista está mal escrita)",
+                NoAdmissibleGuess => "no se puede adivinar ninguna palabra de la lista",
+                InvalidExtraGuess => "intento adicional no válido '{}'",
+                TooManyAnswers => "{} palabras podrían ser la respuesta, pero una búsqueda exacta admite como máximo {}",
+                Remaining => "quedan: {} intentos válidos, {} respuestas posibles",
+                WordFamily => "familia de palabras: {}",
+                FamilyWarning => "aviso: adivinarlas una a una podría llevar {} intentos, pero solo quedan {}",
+                FamilyProbe => "; considera sondear con {} (las divide en {} grupos)",
+                Undid => "deshecho {}",
+                NothingToUndo => "no hay nada que deshacer",
+                SwapTarget => "objetivo: {} ({} intercambios)",
+                TrainPrompt => "intento {}/{}: ",
+                GaveUp => "te rendiste, la respuesta era {}",
+                NotInWordList => "'{}' no está en la lista de palabras",
+                NotInWordListSuggest => "'{}' no está en la lista de palabras (¿quisiste decir {}?)",
+                HardModeViolation => "'{}' no reutiliza las letras reveladas por '{}' (modo difícil)",
+                InvalidGuess => "intento no válido: {}",
+                TrainWon => "¡resuelto en {}/{}!",
+                TrainLost => "sin intentos, la respuesta era {}",
+                Grading => "evaluando...",
+                Grade => "{} ({} candidatas): {} | solucionador: {}: {}",
+                Alternatives => "alternativas:",
+                AlternativeTurn => "{} (quedan {})",
+                AlternativeWon => "resuelto en {}",
+                AlternativeLost => "no resuelto",
+                FailedToRead => "no se pudo leer {}: {}",
+                FailedToWrite => "no se pudo escribir {}: {}",
+                InvalidConfig => "archivo de configuración no válido {}: {}",
+                ProgressFdFailed => "no se pudo abrir --progress-fd {}: {}",
+                InvalidScoreScript => "script de puntuación no válido {}: {}",
+                ScoreScriptFailed => "script de puntuación {}: {}",
+                ScriptingDisabled => "--score-script requiere compilar el solucionador con la característica scripting",
+                WeightsSyntax => "{}:{}: se esperaba <palabra> <peso>",
+                WeightsUnknownWord => "{}: '{}' no está en el diccionario",
+                UnknownInput => "entrada desconocida '{}' (se esperaba <intento>:<respuesta>, consulta -h)",
+                UnmatchedGuess => "cada --guess debe tener su --hint",
+                InvalidInput => "entrada no válida: {}",
+                UnknownWordWarning => "aviso: '{}' no está en la lista de palabras (su pista se aplica igualmente)",
+                WordListNotBundled => "la lista de palabras de {} no está incluida, así que debe indicarse con --words",
+                InvalidWordList => "lista de palabras no válida: {}",
+                AnswerNotInWordList => "la respuesta '{}' no está en la lista de palabras",
+                UnknownPreset => "preajuste desconocido '{}' (defínelo en el archivo de --config)",
+                InvalidAnswerTolerance => "--answer-tolerance no válido {} (se esperaba un número de palabras no negativo)",
+                InvalidTimeLimit => "--time-limit no válido {} (se esperaba un número de segundos)",
+                ArgumentConflict => "{} no se puede usar con {}",
+                ShortlistedOpeningConflict => "la apertura preseleccionada de una lista de palabras no inglesa (añade --exact-opening para buscar entre todas las palabras)",
+                CacheFailed => "no se pudo abrir la caché {}: {}",
+                ShortlistedOpening => "(la apertura se elige entre las {} mejores palabras por cobertura de letras, consulta --exact-opening)",
+                HintHistogram => "palabras restantes por pista:",
+                NoCompletions => "ninguna palabra empieza por '{}'",
+                Completions => "{} ... ({} palabras)",
+                InteractiveUsage => "se esperaba <intento>:<respuesta> o undo (o termina un intento parcial con '?' para ver cómo completarlo)",
+                SwapStep => "intercambia {} y {}",
+                InferTruncated => "(salida truncada a {} secuencias)",
+                NoInferredSequence => "ninguna secuencia de intentos es coherente con la cuadrícula",
+                WordCount => "{} palabras",
+                RepeatedLetterWords => "{} palabras con letras repetidas",
+                AnagramGroups => "{} grupos de anagramas ({} palabras)",
+                LetterColumn => "letra",
+                TotalColumn => "total",
+                WordsColumn => "palabras",
+                InvalidBaseline => "referencia no válida {}: {}",
+                CheckpointFailed => "no se pudo abrir el punto de control {}: {}",
+                CheckpointWriteFailed => "no se pudo escribir el punto de control: {}",
+                Resuming => "reanudando con {} de {} palabras completadas",
+                AnswerGuesses => "{} necesitó {} intentos",
+                BenchResults => "resultados sobre {} palabras:",
+                BenchMin => "mín.: {}",
+                BenchMax => "máx.: {}",
+                BenchAvg => "media: {}",
+                BenchStd => "desv.: {}",
+                BenchTime => "tiempo p95: {}",
+                BaselineComparison => "comparado con la referencia:",
+                BaselineWordsWarning => "aviso: la referencia se ejecutó con otra lista de palabras u otra muestra ({} palabras)",
+                Regression => "(regresión)",
+                Regressed => "el rendimiento empeoró más allá de sus umbrales",
+            }
+        }
+    }
+    /// Formats the message in the given locale, replacing each `{}` in the template with the next argument.
+    /// Missing arguments are left empty and extra ones are ignored.
+    pub fn format(self, locale: Locale, args: &[&dyn fmt::Display]) -> String {
+        let mut parts = self.template(locale).split("{}");
+        let mut res = parts.next().unwrap_or("").to_owned();
+        for (i, part) in parts.enumerate() {
+            if let Some(arg) = args.get(i) { res.push_str(&arg.to_string()); }
+            res.push_str(part);
+        }
+        res
+    }
+}
+
+#[test]
+fn test_messages() {
+    assert_eq!(Locale::from_code("es"), Locale::Spanish);
+    assert_eq!(Locale::from_code("es_MX.UTF-8"), Locale::Spanish);
+    assert_eq!(Locale::from_code("ES-es"), Locale::Spanish);
+    for code in ["en_US.UTF-8", "fr", "C", ""] {
+        assert_eq!(Locale::from_code(code), Locale::English);
+    }
+    assert_eq!(Locale::parse("en_GB"), Some(Locale::English));
+    assert_eq!(Locale::parse("fr"), None);
+    assert_eq!(Locale::parse(""), None);
+    for locale in Locale::ALL {
+        assert_eq!(Locale::parse(locale.code()), Some(locale));
+    }
+
+    assert_eq!(Message::Score.format(Locale::English, &[&3, &"1.5"]), "3 worst, 1.5 avg.");
+    assert_eq!(Message::Score.format(Locale::Spanish, &[&3, &"1.5"]), "3 en el peor caso, 1.5 de media");
+    let score = Message::Score.format(Locale::English, &[&0, &0]);
+    assert_eq!(Message::RemainingWords.format(Locale::English, &[&score]), "remaining words: 0 worst, 0 avg.");
+    assert_eq!(Message::BestGuess.format(Locale::English, &[]), "best guess: ");
+    assert_eq!(Message::InputSummary.format(Locale::Spanish, &[&"ignored"]), "resumen de la entrada:");
+
+    // every translation takes the same arguments as the english message
+    let all = [Message::InputSummary, Message::Candidates, Message::CandidateCount, Message::BestGuess, Message::BestGuesses, Message::Score,
+        Message::RemainingWords, Message::CouldBeAnswer, Message::AnswerScore, Message::EndgameSolved, Message::ProvenDepth, Message::Approximate, Message::Inconsistent, Message::NoAdmissibleGuess, Message::InvalidExtraGuess, Message::TooManyAnswers,
+        Message::Remaining, Message::WordFamily, Message::FamilyWarning, Message::FamilyProbe, Message::Undid, Message::NothingToUndo, Message::SwapTarget,
+        Message::TrainPrompt, Message::GaveUp, Message::NotInWordList, Message::NotInWordListSuggest, Message::HardModeViolation, Message::InvalidGuess,
+        Message::TrainWon, Message::TrainLost, Message::Grading, Message::Grade, Message::Alternatives, Message::AlternativeTurn, Message::AlternativeWon, Message::AlternativeLost,
+        Message::FailedToRead, Message::FailedToWrite, Message::InvalidConfig, Message::ProgressFdFailed,
+        Message::InvalidScoreScript, Message::ScoreScriptFailed, Message::ScriptingDisabled, Message::WeightsSyntax, Message::WeightsUnknownWord,
+        Message::UnknownInput, Message::UnmatchedGuess, Message::InvalidInput, Message::UnknownWordWarning, Message::WordListNotBundled,
+        Message::InvalidWordList, Message::AnswerNotInWordList, Message::UnknownPreset, Message::InvalidAnswerTolerance, Message::InvalidTimeLimit,
+        Message::ArgumentConflict, Message::ShortlistedOpeningConflict, Message::CacheFailed, Message::ShortlistedOpening, Message::HintHistogram,
+        Message::NoCompletions, Message::Completions, Message::InteractiveUsage, Message::SwapStep, Message::InferTruncated,
+        Message::NoInferredSequence, Message::WordCount, Message::RepeatedLetterWords, Message::AnagramGroups, Message::LetterColumn,
+        Message::TotalColumn, Message::WordsColumn, Message::InvalidBaseline, Message::CheckpointFailed, Message::CheckpointWriteFailed,
+        Message::Resuming, Message::AnswerGuesses, Message::BenchResults, Message::BenchMin, Message::BenchMax, Message::BenchAvg, Message::BenchStd,
+        Message::BenchTime, Message::BaselineComparison, Message::BaselineWordsWarning, Message::Regression, Message::Regressed];
+    for message in all {
+        assert_eq!(message.template(Locale::Spanish).matches("{}").count(), message.template(Locale::English).matches("{}").count(), "{:?}", message);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::*;
+
+/// The position of a word in a [`Dictionary`] (in lexicographic order), as produced by [`Dictionary::index`].
+/// Indices are only meaningful for the dictionary they came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WordIndex(usize);
+impl WordIndex {
+    pub(crate) fn new(index: usize) -> Self {
+        WordIndex(index)
+    }
+    /// Gets the position of the word in the dictionary.
+    pub fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl Dictionary {
+    /// Gets the index of the given word, or [`None`] if it is not in the dictionary.
+    pub fn index(&self, word: &str) -> Option<WordIndex> {
+        let word = self.alphabet.encode(self.word_len, word).ok()?;
+        self.index_of(&word).map(WordIndex)
+    }
+    /// Gets the word at the given index.
+    /// Panics if the index is out of bounds (e.g., it came from a larger dictionary).
+    pub fn word(&self, index: WordIndex) -> &str {
+        &self.words[index.0]
+    }
+    /// Creates a [`WordMeta`] with the given values attached to their words.
+    /// Entries for words not in the dictionary are ignored, and later entries replace earlier ones for the same word.
+    pub fn with_metadata<T, S: AsRef<str>, I: IntoIterator<Item = (S, T)>>(&self, entries: I) -> WordMeta<T> {
+        let mut res = WordMeta::new(self);
+        for (word, value) in entries {
+            if let Some(index) = self.index(word.as_ref()) {
+                res.insert(index, value);
+            }
+        }
+        res
+    }
+}
+
+/// Arbitrary data (e.g., frequencies, definitions, or tags) attached to the words of a [`Dictionary`], indexed by [`WordIndex`].
+/// Not every word needs a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordMeta<T> {
+    values: Vec<Option<T>>,
+}
+impl<T> WordMeta<T> {
+    /// Creates an empty table for the words of the given dictionary.
+    pub fn new(dictionary: &Dictionary) -> Self {
+        WordMeta { values: iter::repeat_with(|| None).take(dictionary.len()).collect() }
+    }
+    /// Gets the value attached to the word at the given index, if any.
+    /// Panics if the index is out of bounds.
+    pub fn get(&self, index: WordIndex) -> Option<&T> {
+        self.values[index.0].as_ref()
+    }
+    /// Attaches a value to the word at the given index, returning the value it replaced (if any).
+    /// Panics if the index is out of bounds.
+    pub fn insert(&mut self, index: WordIndex, value: T) -> Option<T> {
+        self.values[index.0].replace(value)
+    }
+    /// Removes the value attached to the word at the given index, if any.
+    /// Panics if the index is out of bounds.
+    pub fn remove(&mut self, index: WordIndex) -> Option<T> {
+        self.values[index.0].take()
+    }
+    /// Gets the number of words with a value attached.
+    pub fn len(&self) -> usize {
+        self.values.iter().filter(|x| x.is_some()).count()
+    }
+    /// Checks if no word has a value attached.
+    pub fn is_empty(&self) -> bool {
+        self.values.iter().all(Option::is_none)
+    }
+    /// Iterates over the words with a value attached, in index order.
+    pub fn iter(&self) -> impl Iterator<Item = (WordIndex, &T)> {
+        self.values.iter().enumerate().filter_map(|(i, x)| Some((WordIndex(i), x.as_ref()?)))
+    }
+}
+
+impl<'a> Puzzle<'a> {
+    /// Gets the indices of the words which could be the answer, in the same order as [`Puzzle::candidates`].
+    pub fn candidate_indices(&self) -> Vec<WordIndex> {
+        self.reduced().feasible_words.iter().map(|w| WordIndex(self.dictionary.index_of(w).unwrap())).collect()
+    }
+    /// Gets the words which could be the answer, in the same order as [`Puzzle::candidates`], along with their values in `meta` (if any).
+    pub fn candidates_with<'m, T>(&self, meta: &'m WordMeta<T>) -> Vec<(&'a str, Option<&'m T>)> {
+        self.candidate_indices().into_iter().map(|i| (self.dictionary.word(i), meta.get(i))).collect()
+    }
+}
+
+#[test]
+fn test_word_meta() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let hatch = dict.index("hatch").unwrap();
+    assert_eq!((hatch.get(), dict.word(hatch)), (2, "hatch"));
+    assert_eq!(dict.index("hutch"), None);
+    assert_eq!(dict.index("hat"), None);
+
+    let mut meta = dict.with_metadata([("hatch", "an opening"), ("hutch", "a pen"), ("latch", "a fastener"), ("hatch", "to emerge")]);
+    assert_eq!((meta.len(), meta.get(hatch)), (2, Some(&"to emerge")));
+    assert_eq!(meta.iter().map(|(i, x)| (dict.word(i), *x)).collect::<Vec<_>>(), &[("hatch", "to emerge"), ("latch", "a fastener")]);
+    assert_eq!(meta.insert(dict.index("batch").unwrap(), "a group"), None);
+    assert_eq!(meta.remove(hatch), Some("to emerge"));
+    assert_eq!(meta.len(), 2);
+    assert!(!meta.is_empty() && WordMeta::<()>::new(&dict).is_empty());
+
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("watch", &get_hint("watch", "latch").unwrap()).unwrap();
+    puzzle.guess("patch", &get_hint("patch", "latch").unwrap()).unwrap();
+    assert_eq!(puzzle.candidate_indices().into_iter().map(|i| dict.word(i)).collect::<Vec<_>>(), puzzle.candidates());
+    assert_eq!(puzzle.candidates_with(&meta), &[("batch", Some(&"a group")), ("catch", None), ("hatch", None), ("latch", Some(&"a fastener")), ("match", None)]);
+}
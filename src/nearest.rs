@@ -0,0 +1,47 @@
+use crate::*;
+
+impl Dictionary {
+    /// Finds the words in the dictionary within `max_distance` edits (insertions, deletions, or substitutions of single characters) of `word`,
+    /// ordered by distance and then lexicographically. This is meant for suggesting corrections to mistyped words.
+    /// The word may contain characters outside the alphabet, which never match any letter.
+    pub fn nearest(&self, word: &str, max_distance: usize) -> Vec<&str> {
+        let word: Vec<u8> = word.chars().map(|ch| self.alphabet.index_of(ch).unwrap_or(u8::MAX)).collect();
+        if word.len().abs_diff(self.word_len) > max_distance { return vec![] }
+
+        let mut res = vec![];
+        let mut row = vec![0; self.word_len + 1];
+        'words: for (i, other) in self.data.chunks_exact(self.word_len).enumerate() {
+            // standard dynamic programming over prefixes of the two words, one row per character of the input
+            for (j, x) in row.iter_mut().enumerate() { *x = j; }
+            for (i, &a) in word.iter().enumerate() {
+                let mut diag = row[0];
+                row[0] = i + 1;
+                let mut best = row[0];
+                for (j, &b) in other.iter().enumerate() {
+                    let next = (diag + (a != b) as usize).min(row[j] + 1).min(row[j + 1] + 1);
+                    diag = row[j + 1];
+                    row[j + 1] = next;
+                    best = best.min(next);
+                }
+                if best > max_distance { continue 'words } // the distance never decreases in later rows
+            }
+            let distance = row[self.word_len];
+            if distance <= max_distance { res.push((distance, i)); }
+        }
+        res.sort_unstable();
+        res.into_iter().map(|(_, i)| self.words[i].as_str()).collect()
+    }
+}
+
+#[test]
+fn test_nearest() {
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "chimp", "champ", "lower", "cater"]).unwrap();
+    assert_eq!(dict.nearest("catch", 0), &["catch"]);
+    assert_eq!(dict.nearest("catcg", 1), &["catch"]);
+    assert_eq!(dict.nearest("catcg", 2), &["catch", "batch", "cater", "hatch"]);
+    assert_eq!(dict.nearest("chmp", 1), &["champ", "chimp"]);
+    assert_eq!(dict.nearest("lowerr", 1), &["lower"]);
+    assert_eq!(dict.nearest("löwer", 1), &["lower"]);
+    assert_eq!(dict.nearest("cat", 1), Vec::<&str>::new());
+    assert_eq!(dict.nearest("zzzzz", 3), Vec::<&str>::new());
+}
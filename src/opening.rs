@@ -0,0 +1,104 @@
+use std::cmp::Reverse;
+
+use crate::*;
+
+/// Distinguishes cached opening guesses from cached best guesses for the same state.
+const OPENING_KEY: u64 = 0x6f70656e696e67; // "opening"
+
+impl<'a> Puzzle<'a> {
+    /// The default number of words shortlisted by [`Puzzle::opening_guess`].
+    pub const OPENING_SHORTLIST_LEN: usize = 100;
+
+    /// Gets the `n` words from the dictionary which cover the most of the feasible words, from best to worst.
+    /// Each word is scored by the number of feasible words containing each of its distinct letters, with ties broken by the
+    /// number of feasible words sharing a letter in the same slot, then lexicographically.
+    pub fn coverage_shortlist(&self, n: usize) -> Vec<&'a str> {
+        let this = self.reduced();
+        this.coverage_ranked(n).into_iter().map(|w| self.dictionary.word_str(&w)).collect()
+    }
+    fn coverage_ranked(&self, n: usize) -> Vec<Word<'a>> {
+        self.coverage_ranked_among(&self.all_words, n)
+    }
+    /// Equivalent to [`Puzzle::coverage_ranked`], but only ranks the given guesses.
+    pub(crate) fn coverage_ranked_among(&self, guesses: &[Word<'a>], n: usize) -> Vec<Word<'a>> {
+        let mut containing = [0u64; Alphabet::MAX_LEN];
+        let mut positional = vec![[0u64; Alphabet::MAX_LEN]; self.slots.len()];
+        for word in self.feasible_words.iter() {
+            let mut seen = BitSet32::new();
+            for (slot, &letter) in word.iter().enumerate() {
+                seen.insert(letter);
+                positional[slot][letter as usize] += 1;
+            }
+            for letter in seen { containing[letter as usize] += 1; }
+        }
+
+        let mut scored: Vec<_> = guesses.iter().map(|&word| {
+            let mut seen = BitSet32::new();
+            for &letter in word.iter() { seen.insert(letter); }
+            let coverage: u64 = seen.into_iter().map(|letter| containing[letter as usize]).sum();
+            let position: u64 = word.iter().enumerate().map(|(slot, &letter)| positional[slot][letter as usize]).sum();
+            (Reverse((coverage, position)), word)
+        }).collect();
+        if n < scored.len() {
+            if n > 0 { scored.select_nth_unstable(n - 1); }
+            scored.truncate(n);
+        }
+        scored.sort_unstable();
+        scored.into_iter().map(|x| x.1).collect()
+    }
+    /// Quickly finds a good opening guess for a large dictionary (e.g., a word list for another language, with its own [`Alphabet`]) where
+    /// [`Puzzle::best_guess`] would take minutes: the `shortlist_len` words with the best [`Puzzle::coverage_shortlist`] are scored exactly,
+    /// and the best of those is chosen by the same criteria as [`Puzzle::best_guess`] (without the endgame solver).
+    /// This is only a heuristic, so the result may be worse than [`Puzzle::best_guess`], but never when the shortlist covers the whole dictionary.
+    /// This works in any state, but is intended for the first guess, whose result can then be cached (see [`Puzzle::opening_guess_cached`]).
+    /// The errors and meaning of `threads` are the same as for [`Puzzle::best_guess`] (with [`AnyWord`]).
+    pub fn opening_guess(&self, threads: usize, shortlist_len: usize) -> Result<BestGuess, SolveErr> {
+        let this = self.reduced();
+        if this.slots.iter().any(BitSet32::is_empty) {
+            return Err(SolveErr::Inconsistent);
+        }
+        let shortlist = this.coverage_ranked(shortlist_len.max(1));
+        if shortlist.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+        let (ranked, counters) = this.rank_admissible(threads.max(1), &shortlist, 1, &mut |_| ());
+        match ranked.first() {
+            Some(&((worst_case, average), infeasible, guess)) => Ok(BestGuess { word: this.decode(&guess), worst_case, average, feasible: !infeasible, endgame: false, expected_guesses: None, counters, approximate: false, partition: this.partition(guess) }),
+            None => Err(SolveErr::Inconsistent),
+        }
+    }
+    /// Equivalent to [`Puzzle::opening_guess`], but looks up the result in `store` first and saves it there after computing it,
+    /// in the same way as [`Puzzle::best_guess_cached`] (the two never share entries).
+    pub fn opening_guess_cached(&self, threads: usize, shortlist_len: usize, store: &dyn CacheStore) -> Result<BestGuess, SolveErr> {
+        let key = stable_hash([self.state_hash(), OPENING_KEY, shortlist_len as u64]);
+        cache_best_guess(store, key, || self.opening_guess(threads, shortlist_len))
+    }
+}
+
+#[test]
+fn test_opening_guess() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield", "lower", "zzzzz"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_endgame_threshold(0);
+
+    // every word but "zzzzz" contains 'a', 't', 'c', or 'h', which appear in most words
+    let shortlist = puzzle.coverage_shortlist(100);
+    assert_eq!(shortlist.len(), dict.len());
+    assert_eq!(shortlist.last(), Some(&"zzzzz"));
+    assert_eq!(puzzle.coverage_shortlist(3), &shortlist[..3]);
+    assert_eq!(shortlist[0], "latch");
+
+    let exact = puzzle.rank_guesses(2, AnyWord, 1).unwrap().remove(0);
+    let full = puzzle.opening_guess(2, dict.len()).unwrap();
+    assert_eq!((&full.word, full.worst_case, full.average), (&exact.word, exact.worst_case, exact.average));
+    let single = puzzle.opening_guess(2, 1).unwrap();
+    assert_eq!(single.word, "latch");
+    assert_eq!(puzzle.opening_guess(2, 0).unwrap().word, "latch");
+
+    let store = MemoryStore::new();
+    let cached = puzzle.opening_guess_cached(1, 1, &store).unwrap();
+    assert_eq!((cached.word.as_str(), store.len()), ("latch", 1));
+    assert_eq!(puzzle.opening_guess_cached(1, 1, &store).unwrap().counters, SearchCounters::default());
+    assert_eq!(puzzle.best_guess_cached(1, &store).unwrap().word, puzzle.best_guess(1, AnyWord).unwrap().word);
+    assert_eq!(store.len(), 2);
+}
@@ -0,0 +1,307 @@
+use std::time::{Duration, Instant};
+
+use crate::*;
+
+/// Limits on the work [`Puzzle::best_guess_opts`] may do, for when a late answer is worse than an approximate one (see [`SolveOptions::with_limits`]).
+/// The limits are checked before scoring each guess, so a search can overrun its time limit by the time it takes to score one guess.
+/// Guesses are scored in order of letter coverage, so the most promising ones are scored before a limit is hit.
+/// If a limit is hit, the best of the guesses scored so far is returned, or the best guess by letter coverage (see [`Puzzle::coverage_shortlist`])
+/// if none were, and either way the result is marked as [`BestGuess::approximate`].
+/// The default has no limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComputeLimits {
+    /// The longest the search may take.
+    pub time: Option<Duration>,
+    /// The most guesses the search may score.
+    pub max_evaluated: Option<u64>,
+}
+impl ComputeLimits {
+    /// Checks if a search which has taken `elapsed` time and already scored `evaluated` guesses must stop.
+    pub(crate) fn reached(&self, elapsed: Duration, evaluated: u64) -> bool {
+        self.time.map(|x| elapsed >= x).unwrap_or(false) || self.max_evaluated.map(|x| evaluated >= x).unwrap_or(false)
+    }
+}
+
+/// A [`CandidateFilter`] which only admits guesses that obey hard mode, i.e., which reuse the letters revealed
+/// by every guess so far (greens in place and yellows anywhere), as created by [`Puzzle::hard_mode_filter`].
+/// This can sacrifice information, e.g., when a probe from outside the remaining words would split them better.
+#[derive(Clone)]
+pub struct HardModeFilter<'a> {
+    dictionary: &'a Dictionary,
+    word_len: usize,
+    revealed: Vec<(OwnedWord, Vec<Hint>)>,
+}
+impl CandidateFilter for HardModeFilter<'_> {
+    fn admits(&self, word: &str) -> bool {
+        self.dictionary.alphabet.encode(self.word_len, word).map(|w| self.revealed.iter().all(|(prev, hint)| obeys_hard_mode(&w, prev, hint))).unwrap_or(false)
+    }
+}
+
+/// A [`CandidateFilter`] which only admits words that could still be the answer, as created by [`Puzzle::feasible_filter`].
+/// This is mostly useful for the last guess of a game, since a guess which can't win usually narrows down the answer more.
+#[derive(Clone, Copy)]
+pub struct FeasibleFilter<'p, 'a> {
+    puzzle: &'p Puzzle<'a>,
+}
+impl CandidateFilter for FeasibleFilter<'_, '_> {
+    fn admits(&self, word: &str) -> bool {
+        self.puzzle.encode(word).map(|w| self.puzzle.could_be(w.as_ref())).unwrap_or(false)
+    }
+}
+
+/// The settings for [`Puzzle::best_guess_opts`], created with [`SolveOptions::new`] and adjusted with the `with_*` methods.
+/// New settings are added as new methods (with defaults matching the old behavior), so code using this type keeps compiling as the solver grows.
+#[derive(Clone)]
+pub struct SolveOptions<'o> {
+    threads: usize,
+    strategy: Strategy,
+    filter: Option<&'o dyn CandidateFilter>,
+    extra_guesses: &'o [&'o str],
+    max_guesses: Option<usize>,
+    endgame_threshold: Option<usize>,
+    hard_mode: bool,
+    feasible_only: bool,
+    answer_tolerance: f64,
+    limits: ComputeLimits,
+    prior: Option<&'o Prior<'o>>,
+}
+impl Default for SolveOptions<'_> {
+    fn default() -> Self {
+        SolveOptions { threads: 1, strategy: Strategy::Minimax, filter: None, extra_guesses: &[], max_guesses: None, endgame_threshold: None, hard_mode: false, feasible_only: false, answer_tolerance: 0.0, limits: ComputeLimits::default(), prior: None }
+    }
+}
+impl<'o> SolveOptions<'o> {
+    /// Creates the default options, which are equivalent to [`Puzzle::best_guess`] with one thread and [`AnyWord`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets the number of threads to search with (minimum `1`).
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+    /// Sets the strategy for choosing the guess (default [`Strategy::Minimax`]).
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+    /// Restricts which words may be suggested, as with the filter of [`Puzzle::best_guess`].
+    pub fn with_filter(mut self, filter: &'o dyn CandidateFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+    /// Adds guesses from outside the dictionary to the pool of words which may be suggested, as with [`Puzzle::best_guess_with_extra`].
+    pub fn with_extra_guesses(mut self, extra_guesses: &'o [&'o str]) -> Self {
+        self.extra_guesses = extra_guesses;
+        self
+    }
+    /// Overrides the maximum number of guesses in the game for this search (see [`Puzzle::set_max_guesses`]).
+    pub fn with_max_guesses(mut self, max_guesses: usize) -> Self {
+        self.max_guesses = Some(max_guesses);
+        self
+    }
+    /// Overrides the number of feasible words at which the exact endgame search is used (see [`Puzzle::set_endgame_threshold`]).
+    pub fn with_endgame_threshold(mut self, threshold: usize) -> Self {
+        self.endgame_threshold = Some(threshold);
+        self
+    }
+    /// Sets whether only guesses which obey hard mode may be suggested, i.e., which reuse the letters revealed
+    /// by every guess in [`Puzzle::history`] (greens in place and yellows anywhere).
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+    /// Sets whether only words which could still be the answer may be suggested (as with [`Puzzle::feasible_filter`]), whatever the strategy.
+    pub fn with_feasible_only(mut self, feasible_only: bool) -> Self {
+        self.feasible_only = feasible_only;
+        self
+    }
+    /// Sets how much worse (in remaining words, for both the worst and average case) a guess which could be the answer may score
+    /// than the best guess of [`Strategy::Minimax`] and still be suggested instead of it (default `0.0`, where it must tie).
+    /// Otherwise, a guess which can't win is suggested even if it is only microscopically better on average.
+    /// This needs a second search over the words which could be the answer, which only gets what is left of the [`ComputeLimits`] after the first,
+    /// and doesn't affect the exact endgame search, which already accounts for the chance of winning with each guess.
+    /// Panics if the tolerance is negative or NaN.
+    pub fn with_answer_tolerance(mut self, tolerance: f64) -> Self {
+        assert!(tolerance >= 0.0, "the answer tolerance must be a non-negative number of words");
+        self.answer_tolerance = tolerance;
+        self
+    }
+    /// Limits the work done by the minimax search, returning an approximate result if a limit is hit (see [`ComputeLimits`]).
+    /// The exact endgame search and the strategies which don't search are not limited.
+    pub fn with_limits(mut self, limits: ComputeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+    /// Sets how likely each word is to be the answer, as used by [`Strategy::ProbTwoLeft`] and [`Strategy::ExpectedRemaining`]
+    /// (default [`Prior::uniform`]). The prior must be defined over the dictionary of the puzzle.
+    pub fn with_prior(mut self, prior: &'o Prior<'o>) -> Self {
+        self.prior = Some(prior);
+        self
+    }
+}
+
+impl<'a> Puzzle<'a> {
+    /// Creates a filter which only admits the guesses allowed in hard mode after the guesses in [`Puzzle::history`],
+    /// e.g., to pass to [`Puzzle::best_guess`] so that the suggestion is always a legal hard mode guess.
+    /// Hints recorded with [`Puzzle::apply_hint_raw`] are not counted, since they are not guesses.
+    pub fn hard_mode_filter(&self) -> HardModeFilter<'a> {
+        let revealed = self.history.iter().map(|(word, hint)| (self.encode(word).unwrap(), hint.clone())).collect();
+        HardModeFilter { dictionary: self.dictionary, word_len: self.slots.len(), revealed }
+    }
+    /// Creates a filter which only admits the words which could still be the answer (see [`Puzzle::candidates`]),
+    /// e.g., to pass to [`Puzzle::best_guess`] so that only those words are scored.
+    pub fn feasible_filter(&self) -> FeasibleFilter<'_, 'a> {
+        FeasibleFilter { puzzle: self }
+    }
+    /// Finds the best guess according to the given options, which collect all the solver settings in one place.
+    /// The default options give the same result as [`Puzzle::best_guess`].
+    ///
+    /// The guess is chosen by the [`Strategy`] of the options: [`Strategy::Minimax`] and [`Strategy::FeasibleOnly`] use [`Puzzle::best_guess`]
+    /// (with [`SolveOptions::with_answer_tolerance`] for the former),
+    /// [`Strategy::ProbTwoLeft`], [`Strategy::Entropy`], [`Strategy::Lookahead`], [`Strategy::Exact`], and [`Strategy::ExpectedRemaining`] pick the word
+    /// with [`Puzzle::best_guess_prob_left`], [`Puzzle::best_guess_entropy`], [`Puzzle::best_guess_lookahead`], [`Puzzle::best_guess_exact`],
+    /// or [`Puzzle::best_guess_weighted`] (without extra guesses) and then score it,
+    /// and [`Strategy::Fixed`] just scores the given word (ignoring the filter and hard mode), failing with [`SolveErr::InvalidExtraGuess`] if it is not a valid word.
+    /// Otherwise, fails under the same conditions as [`Puzzle::best_guess_with_extra`].
+    pub fn best_guess_opts(&self, opts: &SolveOptions) -> Result<BestGuess, SolveErr> {
+        let mut puzzle = self.reduced().clone();
+        if let Some(max_guesses) = opts.max_guesses { puzzle.set_max_guesses(max_guesses); }
+        if let Some(threshold) = opts.endgame_threshold { puzzle.set_endgame_threshold(threshold); }
+
+        let hard_mode = opts.hard_mode.then(|| puzzle.hard_mode_filter());
+        let feasible_only = opts.feasible_only || opts.strategy == Strategy::FeasibleOnly;
+        let admits = |word: &str| opts.filter.map(|f| f.admits(word)).unwrap_or(true) && hard_mode.as_ref().map(|f| f.admits(word)).unwrap_or(true)
+            && (!feasible_only || puzzle.feasible_filter().admits(word));
+
+        let uniform;
+        let prior = match opts.prior {
+            Some(prior) => prior,
+            None => { uniform = Prior::uniform(puzzle.dictionary); &uniform }
+        };
+        let word = match &opts.strategy {
+            Strategy::FeasibleOnly => return puzzle.best_guess_limited(opts.threads, admits, opts.extra_guesses, &opts.limits),
+            Strategy::Minimax => {
+                let start = Instant::now();
+                let best = puzzle.best_guess_limited(opts.threads, admits, opts.extra_guesses, &opts.limits)?;
+                if best.feasible || best.endgame || opts.answer_tolerance == 0.0 { return Ok(best) }
+                let feasible = |word: &str| admits(word) && puzzle.feasible_filter().admits(word);
+                let left = ComputeLimits {
+                    time: opts.limits.time.map(|x| x.saturating_sub(start.elapsed())),
+                    max_evaluated: opts.limits.max_evaluated.map(|x| x.saturating_sub(best.counters.evaluated)),
+                };
+                let mut alt = match puzzle.best_guess_limited(opts.threads, feasible, &[], &left) {
+                    Ok(x) => x,
+                    Err(_) => return Ok(best), // no admissible word could be the answer
+                };
+                let within = |x: f64, y: f64| x <= y + opts.answer_tolerance;
+                if !within(alt.worst_case as f64, best.worst_case as f64) || !within(alt.average.to_f64(), best.average.to_f64()) { return Ok(best) }
+                alt.counters += best.counters;
+                alt.approximate |= best.approximate;
+                return Ok(alt);
+            }
+            Strategy::ProbTwoLeft { k } => puzzle.best_guess_prob_left(opts.threads, admits, *k, prior)?.word,
+            Strategy::Entropy => puzzle.best_guess_entropy(opts.threads, admits)?.word,
+            Strategy::Lookahead { depth, breadth } => puzzle.best_guess_lookahead(opts.threads, admits, *depth, *breadth)?.word,
+            Strategy::Exact => puzzle.best_guess_exact(opts.threads, admits)?.word,
+            Strategy::ExpectedRemaining => puzzle.best_guess_weighted(opts.threads, admits, prior)?.word,
+            Strategy::Fixed(word) => word.clone(),
+        };
+        let guess = puzzle.encode(&word).map_err(|_| SolveErr::InvalidExtraGuess(word.clone()))?;
+        let (worst_case, average) = puzzle.score_guess(guess.as_ref(), None).ok_or(SolveErr::Inconsistent)?;
+        let partition = puzzle.partition(guess.as_ref());
+        Ok(BestGuess { word, worst_case, average, feasible: puzzle.is_feasible(guess.as_ref()), endgame: false, expected_guesses: None, counters: SearchCounters::default(), approximate: false, partition })
+    }
+}
+
+#[test]
+fn test_solve_options() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "champ", "lower", "bloat"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+    assert_eq!(puzzle.best_guess_opts(&SolveOptions::new()).unwrap(), puzzle.best_guess(1, AnyWord).unwrap());
+    let mut no_endgame = puzzle.clone();
+    no_endgame.set_endgame_threshold(0);
+    let best = puzzle.best_guess_opts(&SolveOptions::new().with_threads(4).with_endgame_threshold(0)).unwrap();
+    assert_eq!((best.word, best.endgame), (no_endgame.best_guess(1, AnyWord).unwrap().word, false));
+
+    let no_c = |w: &str| !w.starts_with('c');
+    let opts = SolveOptions::new().with_filter(&no_c).with_extra_guesses(&["mbhpt"]);
+    assert_eq!(puzzle.best_guess_opts(&opts).unwrap(), puzzle.best_guess_with_extra(1, no_c, &["mbhpt"]).unwrap());
+    let opts = SolveOptions::new().with_strategy(Strategy::FeasibleOnly).with_endgame_threshold(0);
+    assert!(puzzle.candidates().contains(&puzzle.best_guess_opts(&opts).unwrap().word.as_str()));
+    assert_eq!(puzzle.best_guess_opts(&opts).unwrap(), puzzle.best_guess_opts(&opts.clone().with_strategy(Strategy::Minimax).with_feasible_only(true)).unwrap());
+    let filter = puzzle.feasible_filter();
+    assert!(filter.admits("hatch") && filter.admits("chimp") && !filter.admits("bloat") && !filter.admits("lower") && !filter.admits("hat"));
+    let entropy = puzzle.best_guess_opts(&SolveOptions::new().with_strategy(Strategy::Entropy).with_feasible_only(true)).unwrap();
+    assert_eq!(entropy.word, puzzle.best_guess_entropy(1, filter).unwrap().word);
+    assert!(entropy.feasible);
+    // with one guess left, only feasible words are suggested
+    assert!(puzzle.candidates().contains(&puzzle.best_guess_opts(&SolveOptions::new().with_max_guesses(2)).unwrap().word.as_str()));
+
+    // hard mode must reuse the revealed 'l', 'a', and 't', which only "latch" (and "bloat" itself) does
+    let mut hard = Puzzle::new(&dict);
+    hard.guess("bloat", &get_hint("bloat", "latch").unwrap()).unwrap();
+    assert_eq!(hard.best_guess_opts(&SolveOptions::new().with_hard_mode(true)).unwrap().word, "latch");
+    assert!(matches!(hard.best_guess_opts(&SolveOptions::new().with_hard_mode(true).with_filter(&|w: &str| w != "latch" && w != "bloat")), Err(SolveErr::NoAdmissibleGuess)));
+    assert_ne!(hard.best_guess_opts(&SolveOptions::new().with_filter(&|w: &str| w != "latch")).unwrap().word, "latch");
+    assert_eq!(hard.best_guess(1, hard.hard_mode_filter()).unwrap().word, "latch");
+    let filter = hard.hard_mode_filter();
+    assert!(filter.admits("latch") && filter.admits("bloat") && !filter.admits("chimp") && !filter.admits("zzzzz"));
+    assert!(puzzle.hard_mode_filter().admits("lower") && !Puzzle::new(&dict).hard_mode_filter().admits("lowe"));
+
+    let fixed = hard.best_guess_opts(&SolveOptions::new().with_strategy(Strategy::Fixed("watch".into()))).unwrap();
+    assert_eq!((fixed.word.as_str(), fixed.worst_case, fixed.endgame), ("watch", 1, false));
+    assert_eq!(fixed.partition.iter().map(|x| x.1).sum::<u64>(), 1);
+    assert!(matches!(hard.best_guess_opts(&SolveOptions::new().with_strategy(Strategy::Fixed("hat".into()))), Err(SolveErr::InvalidExtraGuess(x)) if x == "hat"));
+    let prob = hard.best_guess_opts(&SolveOptions::new().with_strategy(Strategy::ProbTwoLeft { k: 1 })).unwrap();
+    assert_eq!(prob.word, hard.suggest(&Strategy::ProbTwoLeft { k: 1 }, 1).unwrap());
+
+    // out of budget before scoring anything falls back to letter coverage, but the reported score is still exact
+    let opts = SolveOptions::new().with_endgame_threshold(0);
+    let exact = puzzle.best_guess_opts(&opts).unwrap();
+    assert!(!exact.approximate);
+    let limited = puzzle.best_guess_opts(&opts.clone().with_limits(ComputeLimits { max_evaluated: Some(1000), ..Default::default() })).unwrap();
+    assert_eq!(limited, exact); // guesses are scored in a different order, so only the counters differ
+    for limits in [ComputeLimits { max_evaluated: Some(0), time: None }, ComputeLimits { max_evaluated: None, time: Some(Duration::ZERO) }] {
+        let approx = puzzle.best_guess_opts(&opts.clone().with_limits(limits)).unwrap();
+        assert!(approx.approximate);
+        assert_eq!(approx.counters.evaluated, 0);
+        assert_eq!(approx.word, puzzle.coverage_shortlist(1)[0]);
+        let scored = puzzle.evaluate_guess(&approx.word).unwrap().unwrap();
+        assert_eq!((approx.worst_case, approx.average), (scored.worst_case, scored.average));
+    }
+    let partial = puzzle.best_guess_opts(&opts.with_limits(ComputeLimits { max_evaluated: Some(1), ..Default::default() })).unwrap();
+    assert!(partial.approximate && partial.counters.evaluated == 1);
+    assert_eq!(partial.word, puzzle.coverage_shortlist(1)[0]);
+
+    // blimp leaves at most 2 words (1.3333 on average), and batch leaves at most 3 (2.0000 on average) but could win
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "champ", "blimp"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("chimp", &get_hint("chimp", "hatch").unwrap()).unwrap();
+    let opts = SolveOptions::new().with_endgame_threshold(0);
+    let best = puzzle.best_guess_opts(&opts).unwrap();
+    assert_eq!((best.word.as_str(), best.worst_case, best.average, best.feasible), ("blimp", 2, Average::new(4, 3), false));
+    for (tolerance, word) in [(0.0, "blimp"), (0.9, "blimp"), (1.0, "batch")] {
+        let tolerant = puzzle.best_guess_opts(&opts.clone().with_answer_tolerance(tolerance)).unwrap();
+        assert_eq!(tolerant.word, word, "{}", tolerance);
+        assert_eq!(tolerant.feasible, word == "batch");
+    }
+    let batch = puzzle.best_guess_opts(&opts.clone().with_answer_tolerance(1.0)).unwrap();
+    assert_eq!((batch.worst_case, batch.average), (3, Average::new(2, 1)));
+    assert_eq!(batch.partition, puzzle.best_guess_opts(&opts.clone().with_strategy(Strategy::Fixed("batch".into()))).unwrap().partition);
+    assert!(batch.counters.evaluated > best.counters.evaluated);
+    // the second search only gets what the first left of the limits
+    let limits = ComputeLimits { max_evaluated: Some(best.counters.evaluated), time: None };
+    let limited = puzzle.best_guess_opts(&opts.clone().with_answer_tolerance(1.0).with_limits(limits)).unwrap();
+    assert_eq!(limited.counters.evaluated, best.counters.evaluated);
+    // if the filter rejects every word which could be the answer, there is nothing to prefer
+    let no_atch = |w: &str| !w.ends_with("atch");
+    assert_eq!(puzzle.best_guess_opts(&opts.clone().with_answer_tolerance(1.0).with_filter(&no_atch)).unwrap().word, "blimp");
+}
+
+#[test]
+#[should_panic(expected = "non-negative")]
+fn test_negative_answer_tolerance() {
+    let _ = SolveOptions::new().with_answer_tolerance(-1.0);
+}
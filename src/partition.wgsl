@@ -0,0 +1,44 @@
+// Counts the answers giving each hint for every guess, with one invocation per (answer, guess) pair.
+// Hints are computed exactly as by `hint_code` in lib.rs, and words are stored as one letter index per u32.
+
+struct Params {
+    guesses: u32,
+    answers: u32,
+    word_len: u32,
+    buckets: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> guesses: array<u32>;
+@group(0) @binding(2) var<storage, read> answers: array<u32>;
+@group(0) @binding(3) var<storage, read_write> counts: array<atomic<u32>>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let answer = id.x;
+    let guess = id.y;
+    if (answer >= params.answers || guess >= params.guesses) { return; }
+    let g = guess * params.word_len;
+    let a = answer * params.word_len;
+
+    var unmatched: array<u32, 32>;
+    for (var i = 0u; i < params.word_len; i++) {
+        if (guesses[g + i] != answers[a + i]) { unmatched[answers[a + i]] += 1u; }
+    }
+
+    var code = 0u;
+    var place = 1u;
+    for (var i = 0u; i < params.word_len; i++) {
+        let letter = guesses[g + i];
+        var digit = 0u;
+        if (letter == answers[a + i]) {
+            digit = 2u;
+        } else if (unmatched[letter] > 0u) {
+            unmatched[letter] -= 1u;
+            digit = 1u;
+        }
+        code += digit * place;
+        place *= 3u;
+    }
+    atomicAdd(&counts[guess * params.buckets + code], 1u);
+}
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::*;
+
+/// A compact, hashable representation of a full hint (one [`Hint`] per slot).
+///
+/// Each pattern has a stable numeric code (see [`HintPattern::code`]), which is the hints read as a base-3 number
+/// with [`Hint::Absent`] as `0`, [`Hint::Present`] as `1`, and [`Hint::Correct`] as `2`, where the first slot is the least significant digit.
+/// For example, `cpaac` has code `2 + 1*3 + 0*9 + 0*27 + 2*81 = 167`.
+/// This encoding will not change between versions, so codes may be stored or shared with other programs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HintPattern {
+    code: u32,
+    len: u8,
+}
+impl HintPattern {
+    /// The longest hint which can be represented.
+    pub const MAX_LEN: usize = 20;
+
+    pub(crate) fn from_code_unchecked(code: u32, len: usize) -> Self {
+        HintPattern { code, len: len as u8 }
+    }
+    /// Creates a pattern from a sequence of hints.
+    /// Panics if there are more than [`HintPattern::MAX_LEN`] hints.
+    pub fn from_hints(hints: &[Hint]) -> Self {
+        assert!(hints.len() <= Self::MAX_LEN, "hint too long");
+        let code = hints.iter().rev().fold(0, |acc, h| acc * 3 + match h {
+            Hint::Absent => 0,
+            Hint::Present => 1,
+            Hint::Correct => 2,
+        });
+        Self::from_code_unchecked(code, hints.len())
+    }
+    /// Creates a pattern from its code (see [`HintPattern`]) and the number of slots.
+    /// Returns [`None`] if `len` is more than [`HintPattern::MAX_LEN`] or `code` is not less than `3^len`.
+    pub fn from_code(code: u32, len: usize) -> Option<Self> {
+        if len > Self::MAX_LEN || code >= 3u32.pow(len as u32) { return None }
+        Some(Self::from_code_unchecked(code, len))
+    }
+    /// Gets the code for the pattern (see [`HintPattern`]).
+    /// Together with [`HintPattern::word_len`], this uniquely identifies the pattern.
+    pub fn code(self) -> u32 {
+        self.code
+    }
+    /// Gets the number of slots in the pattern.
+    pub fn word_len(self) -> usize {
+        self.len as usize
+    }
+    /// Expands the pattern back into a sequence of hints.
+    pub fn hints(self) -> Vec<Hint> {
+        let mut code = self.code;
+        (0..self.len).map(|_| {
+            let digit = code % 3;
+            code /= 3;
+            match digit {
+                0 => Hint::Absent,
+                1 => Hint::Present,
+                _ => Hint::Correct,
+            }
+        }).collect()
+    }
+}
+impl fmt::Display for HintPattern {
+    /// Formats the pattern using the single-character codes from [`Hint::to_char`].
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for hint in self.hints() {
+            write!(f, "{}", hint.to_char())?;
+        }
+        Ok(())
+    }
+}
+
+/// Counts how many of the `answers` produce each hint pattern for the given `guess`.
+/// Unlike [`get_hint`], the words may consist of any characters, which are compared exactly.
+/// Fails if any answer has a different length than the guess, or if the guess is longer than [`HintPattern::MAX_LEN`].
+pub fn partition_counts<'a>(guess: &'a str, answers: &[&'a str]) -> Result<HashMap<HintPattern, usize>, WordError<'a>> {
+    // only the guess letters matter for the hint, so every other character can share a single index
+    let mut seen: Vec<char> = vec![];
+    for ch in guess.chars() {
+        if !seen.contains(&ch) { seen.push(ch); }
+    }
+    let normalize = |ch: char| seen.iter().position(|&x| x == ch).unwrap_or(Alphabet::MAX_LEN - 1) as u8;
+
+    let guess_len = guess.chars().count();
+    if guess_len > HintPattern::MAX_LEN {
+        return Err(WordError::WrongWordLen { word: guess, expected_len: HintPattern::MAX_LEN });
+    }
+    let encoded_guess: Vec<u8> = guess.chars().map(normalize).collect();
+
+    let mut res = HashMap::new();
+    let mut encoded_answer = Vec::with_capacity(guess_len);
+    for &answer in answers {
+        encoded_answer.clear();
+        encoded_answer.extend(answer.chars().map(normalize));
+        if encoded_answer.len() != guess_len {
+            return Err(WordError::WrongWordLen { word: answer, expected_len: guess_len });
+        }
+        let pattern = HintPattern::from_code_unchecked(hint_code(&encoded_guess, &encoded_answer), guess_len);
+        *res.entry(pattern).or_insert(0) += 1;
+    }
+    Ok(res)
+}
+
+#[test]
+fn test_partition_counts() {
+    use Hint::*;
+    let pattern = HintPattern::from_hints(&[Correct, Present, Absent, Absent, Correct]);
+    assert_eq!(pattern.hints(), &[Correct, Present, Absent, Absent, Correct]);
+    assert_eq!(pattern.word_len(), 5);
+    assert_eq!(pattern.to_string(), "cpaac");
+    assert_eq!(HintPattern::from_hints(&[]).hints(), &[]);
+    assert_eq!(pattern.code(), 167);
+    assert_eq!(HintPattern::from_code(167, 5), Some(pattern));
+    assert_eq!(HintPattern::from_code(0, 3).unwrap().hints(), &[Absent; 3]);
+    assert_eq!(HintPattern::from_code(3u32.pow(5) - 1, 5).unwrap().hints(), &[Correct; 5]);
+    assert_eq!(HintPattern::from_code(3u32.pow(5), 5), None);
+    assert_eq!(HintPattern::from_code(0, HintPattern::MAX_LEN + 1), None);
+    assert_eq!(HintPattern::from_code(0, 0), Some(HintPattern::from_hints(&[])));
+
+    let answers = ["batch", "catch", "hatch", "latch", "chimp", "champ", "lower", "geese", "eerie"];
+    for guess in ["catch", "eerie", "zzzzz", "hutch"] {
+        let counts = partition_counts(guess, &answers).unwrap();
+        assert_eq!(counts.values().sum::<usize>(), answers.len());
+        for answer in answers {
+            let pattern = HintPattern::from_hints(&get_hint(guess, answer).unwrap());
+            assert_eq!(counts[&pattern], answers.iter().filter(|x| get_hint(guess, x).unwrap() == pattern.hints()).count());
+        }
+    }
+    let counts = partition_counts("catch", &answers).unwrap();
+    assert_eq!(counts[&HintPattern::from_hints(&[Absent, Correct, Correct, Correct, Correct])], 3);
+    assert_eq!(partition_counts("🟩a🟩", &["a🟩🟩", "bbb"]).unwrap().len(), 2);
+
+    assert!(matches!(partition_counts("catch", &["cat"]), Err(WordError::WrongWordLen { word: "cat", expected_len: 5 })));
+    assert!(matches!(partition_counts("abcdefghijklmnopqrstu", &[]), Err(WordError::WrongWordLen { expected_len: 20, .. })));
+    assert!(partition_counts("catch", &[]).unwrap().is_empty());
+}
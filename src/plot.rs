@@ -0,0 +1,29 @@
+use std::fmt::{self, Write};
+
+/// Renders a horizontal bar chart as text, with one `<label> <bar> <value>` line per row.
+/// Labels are left-aligned to the longest one, and bars are scaled so that the largest value spans `width` characters
+/// (rounding up, so any nonzero value gets at least one character).
+pub fn bar_chart<L: fmt::Display>(rows: &[(L, u64)], width: usize) -> String {
+    let labels: Vec<String> = rows.iter().map(|(label, _)| label.to_string()).collect();
+    let label_width = labels.iter().map(|x| x.chars().count()).max().unwrap_or(0);
+    let max = rows.iter().map(|&(_, value)| value).max().unwrap_or(0).max(1);
+
+    let mut res = String::new();
+    for (label, &(_, value)) in labels.iter().zip(rows) {
+        let len = (value as u128 * width as u128).div_ceil(max as u128) as usize;
+        writeln!(res, "{:<label_width$} {:<width$} {}", label, "#".repeat(len), value).unwrap();
+    }
+    res
+}
+
+#[test]
+fn test_bar_chart() {
+    assert_eq!(bar_chart::<&str>(&[], 10), "");
+    assert_eq!(bar_chart(&[("aaaaa", 8), ("ccccc", 1), ("pa", 4), ("x", 0)], 4), concat!(
+        "aaaaa #### 8\n",
+        "ccccc #    1\n",
+        "pa    ##   4\n",
+        "x          0\n",
+    ));
+    assert_eq!(bar_chart(&[("a", 0)], 3), "a     0\n");
+}
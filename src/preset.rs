@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use crate::*;
+
+/// A named bundle of solver settings, which can be applied to [`SolveOptions`] with [`Preset::apply`].
+/// Besides the built-in presets (see [`Preset::builtin`]), presets can be defined in a config file (see [`parse_presets`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preset {
+    pub name: String,
+    pub strategy: Strategy,
+    pub limits: ComputeLimits,
+    /// Overrides [`Puzzle::set_endgame_threshold`] if present.
+    pub endgame_threshold: Option<usize>,
+    /// The number of threads to use if none were requested.
+    pub threads: Option<usize>,
+}
+impl Preset {
+    /// Creates a preset with the given name and the default settings of [`SolveOptions::new`].
+    pub fn new(name: &str) -> Self {
+        Preset { name: name.into(), strategy: Strategy::Minimax, limits: ComputeLimits::default(), endgame_threshold: None, threads: None }
+    }
+    /// A preset for real-time variants (like Squabble), which trades the quality of the suggestion for a bounded search:
+    /// only words which could be the answer are considered, the search stops after 30ms (falling back to letter coverage if needed),
+    /// and the exact endgame search is only used for a handful of words.
+    pub fn speed() -> Self {
+        Preset {
+            strategy: Strategy::FeasibleOnly,
+            limits: ComputeLimits { time: Some(Duration::from_millis(30)), max_evaluated: None },
+            endgame_threshold: Some(4),
+            ..Self::new("speed")
+        }
+    }
+    /// Gets the built-in presets.
+    pub fn builtin() -> Vec<Preset> {
+        vec![Self::speed()]
+    }
+    /// Finds the preset with the given name, preferring those in `custom` (e.g., from a config file) over the built-in ones.
+    pub fn find(name: &str, custom: &[Preset]) -> Option<Preset> {
+        custom.iter().find(|x| x.name == name).cloned().or_else(|| Self::builtin().into_iter().find(|x| x.name == name))
+    }
+    /// Applies the settings of the preset to the options (threads are only set by [`Preset::threads`] if present).
+    pub fn apply<'o>(&self, mut opts: SolveOptions<'o>) -> SolveOptions<'o> {
+        if let Some(threads) = self.threads { opts = opts.with_threads(threads); }
+        if let Some(threshold) = self.endgame_threshold { opts = opts.with_endgame_threshold(threshold); }
+        opts.with_strategy(self.strategy.clone()).with_limits(self.limits)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PresetError {
+    /// A line (numbered from `1`) which could not be parsed.
+    Syntax { line: usize },
+    /// A setting which is not known, or which appears before any preset.
+    UnknownKey { line: usize, key: String },
+    /// A setting with a value which could not be parsed.
+    InvalidValue { line: usize },
+}
+
+/// Parses a strategy name: `minimax`, `feasible-only`, `entropy`, `exact`, `expected-remaining`, `prob-left:<k>`, `lookahead:<depth>`, or `fixed:<word>`.
+pub fn parse_strategy(s: &str) -> Option<Strategy> {
+    Some(match s.split_once(':') {
+        None if s == "minimax" => Strategy::Minimax,
+        None if s == "feasible-only" => Strategy::FeasibleOnly,
+        None if s == "entropy" => Strategy::Entropy,
+        None if s == "exact" => Strategy::Exact,
+        None if s == "expected-remaining" => Strategy::ExpectedRemaining,
+        Some(("prob-left", k)) => Strategy::ProbTwoLeft { k: k.parse().ok()? },
+        Some(("lookahead", depth)) => Strategy::Lookahead { depth: depth.parse().ok()?, breadth: Puzzle::LOOKAHEAD_BREADTH },
+        Some(("fixed", word)) if !word.is_empty() => Strategy::Fixed(word.into()),
+        _ => return None,
+    })
+}
+
+/// Parses the presets in a config file, which has a `[name]` line to start each preset followed by `key = value` settings for it.
+/// Blank lines and lines starting with `#` are ignored. The settings are `strategy` (see [`parse_strategy`]), `time-limit` (in seconds),
+/// `max-evaluated`, `endgame-threshold`, and `threads`, and any which are omitted keep the defaults of [`Preset::new`].
+/// ```text
+/// [quick]
+/// strategy = feasible-only
+/// time-limit = 0.1
+/// ```
+pub fn parse_presets(text: &str) -> Result<Vec<Preset>, PresetError> {
+    let mut res: Vec<Preset> = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line_num = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') { continue }
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').map(str::trim).filter(|x| !x.is_empty()).ok_or(PresetError::Syntax { line: line_num })?;
+            res.push(Preset::new(name));
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or(PresetError::Syntax { line: line_num })?;
+        let (key, value) = (key.trim(), value.trim());
+        let preset = res.last_mut().ok_or_else(|| PresetError::UnknownKey { line: line_num, key: key.into() })?;
+        let invalid = || PresetError::InvalidValue { line: line_num };
+        match key {
+            "strategy" => preset.strategy = parse_strategy(value).ok_or_else(invalid)?,
+            "time-limit" => preset.limits.time = Some(value.parse().ok().and_then(|x| Duration::try_from_secs_f64(x).ok()).ok_or_else(invalid)?),
+            "max-evaluated" => preset.limits.max_evaluated = Some(value.parse().map_err(|_| invalid())?),
+            "endgame-threshold" => preset.endgame_threshold = Some(value.parse().map_err(|_| invalid())?),
+            "threads" => preset.threads = Some(value.parse().map_err(|_| invalid())?),
+            _ => return Err(PresetError::UnknownKey { line: line_num, key: key.into() }),
+        }
+    }
+    Ok(res)
+}
+
+#[test]
+fn test_presets() {
+    let presets = parse_presets("# my presets\n[quick]\nstrategy = prob-left:2\ntime-limit = 0.5\n\n[ speed ]\nthreads=3\nmax-evaluated = 100\n").unwrap();
+    assert_eq!(presets, [
+        Preset { strategy: Strategy::ProbTwoLeft { k: 2 }, limits: ComputeLimits { time: Some(Duration::from_millis(500)), max_evaluated: None }, ..Preset::new("quick") },
+        Preset { threads: Some(3), limits: ComputeLimits { time: None, max_evaluated: Some(100) }, ..Preset::new("speed") },
+    ]);
+    assert_eq!(Preset::find("speed", &presets), Some(presets[1].clone()));
+    assert_eq!(Preset::find("speed", &[]), Some(Preset::speed()));
+    assert_eq!(Preset::find("slow", &presets), None);
+
+    assert_eq!(parse_presets("[a]\nstrategy: minimax"), Err(PresetError::Syntax { line: 2 }));
+    assert_eq!(parse_presets("[a\n"), Err(PresetError::Syntax { line: 1 }));
+    assert_eq!(parse_presets("threads = 1"), Err(PresetError::UnknownKey { line: 1, key: "threads".into() }));
+    assert_eq!(parse_presets("[a]\ncolor = red"), Err(PresetError::UnknownKey { line: 2, key: "color".into() }));
+    assert_eq!(parse_presets("[a]\n\nstrategy = fastest"), Err(PresetError::InvalidValue { line: 3 }));
+    assert_eq!(parse_presets("[a]\ntime-limit = -1"), Err(PresetError::InvalidValue { line: 2 }));
+    assert_eq!(parse_strategy("fixed:crane"), Some(Strategy::Fixed("crane".into())));
+    assert_eq!(parse_strategy("entropy"), Some(Strategy::Entropy));
+    assert_eq!(parse_strategy("exact"), Some(Strategy::Exact));
+    assert_eq!(parse_strategy("expected-remaining"), Some(Strategy::ExpectedRemaining));
+    assert_eq!(parse_strategy("lookahead:2"), Some(Strategy::Lookahead { depth: 2, breadth: Puzzle::LOOKAHEAD_BREADTH }));
+
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "lower"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+    // without the time limit, since whether it is reached depends on the load of the machine
+    let unlimited = Preset { limits: ComputeLimits::default(), ..Preset::speed() };
+    let best = puzzle.best_guess_opts(&unlimited.apply(SolveOptions::new())).unwrap();
+    assert!(best.feasible);
+    let expected = SolveOptions::new().with_strategy(Strategy::FeasibleOnly).with_endgame_threshold(4);
+    assert_eq!(best.word, puzzle.best_guess_opts(&expected).unwrap().word);
+    assert!(puzzle.best_guess_opts(&Preset::speed().apply(SolveOptions::new())).unwrap().feasible);
+}
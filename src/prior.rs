@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use crate::*;
+
+/// Adjusts the weight of a word in a [`Prior`].
+pub trait PriorModifier {
+    /// Computes the new weight for the given word from its current weight.
+    fn modify(&self, word: &str, weight: u32) -> u32;
+}
+impl<F: Fn(&str, u32) -> u32> PriorModifier for F {
+    fn modify(&self, word: &str, weight: u32) -> u32 {
+        self(word, weight)
+    }
+}
+
+/// Relative integer weights for how likely each word in a [`Dictionary`] is to be the answer.
+/// Weights only ever affect which answers are considered likely, never which words may be guessed.
+#[derive(Clone)]
+pub struct Prior<'a> {
+    dictionary: &'a Dictionary,
+    weights: Vec<u32>,
+}
+impl<'a> Prior<'a> {
+    /// The weight given to every word by [`Prior::uniform`], which leaves room for modifiers to scale weights down.
+    pub const DEFAULT_WEIGHT: u32 = 1000;
+
+    /// Creates a prior where every word is equally likely.
+    pub fn uniform(dictionary: &'a Dictionary) -> Self {
+        Prior { dictionary, weights: vec![Self::DEFAULT_WEIGHT; dictionary.len()] }
+    }
+    /// Applies a modifier to the weight of every word.
+    pub fn with_modifier<M: PriorModifier>(mut self, modifier: M) -> Self {
+        for (weight, word) in iter::zip(&mut self.weights, &self.dictionary.words) {
+            *weight = modifier.modify(word, *weight);
+        }
+        self
+    }
+    /// Sets the weights of the given words (e.g., from how often they are used), leaving the rest unchanged.
+    /// Fails with the first word which is not in the dictionary.
+    pub fn with_weights<'b, T: IntoIterator<Item = (&'b str, u32)>>(mut self, weights: T) -> Result<Self, &'b str> {
+        for (word, weight) in weights {
+            self.weights[self.dictionary.index(word).ok_or(word)?.get()] = weight;
+        }
+        Ok(self)
+    }
+    /// Gets the [`Dictionary`] this prior is defined over.
+    pub fn dictionary(&self) -> &'a Dictionary {
+        self.dictionary
+    }
+    /// Gets the weight of the given word, which is zero for words not in the dictionary.
+    pub fn weight(&self, word: &str) -> u32 {
+        match self.dictionary.alphabet.encode(self.dictionary.word_len, word) {
+            Ok(word) => self.dictionary.index_of(&word).map(|i| self.weights[i]).unwrap_or(0),
+            Err(_) => 0,
+        }
+    }
+    /// Gets the weight of the word at the given index in the dictionary.
+    pub(crate) fn weight_at(&self, index: usize) -> u32 {
+        self.weights[index]
+    }
+    /// Gets the `n` most likely of the given words along with their weights, from most to least likely (ties in lexicographic order).
+    pub fn most_likely<'b, T: IntoIterator<Item = &'b str>>(&self, words: T, n: usize) -> Vec<(&'b str, u32)> {
+        let mut res: Vec<_> = words.into_iter().map(|w| (w, self.weight(w))).collect();
+        res.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        res.truncate(n);
+        res
+    }
+    /// Gets the sum of all weights.
+    pub fn total(&self) -> u64 {
+        self.weights.iter().map(|&x| x as u64).sum()
+    }
+}
+
+/// A [`PriorModifier`] that divides the weight of words which are unlikely to be chosen as answers based on their english morphology,
+/// such as plurals, past tenses, and proper nouns (which curated answer lists rarely use).
+/// The word endings used are heuristics, so the weights of matching words are only reduced rather than zeroed.
+#[derive(Debug, Clone)]
+pub struct Morphology {
+    penalty: u32,
+    plurals: bool,
+    past_tenses: bool,
+    proper_nouns: BTreeSet<String>,
+}
+impl Morphology {
+    /// Creates a modifier that divides the weight of plurals and past tenses by `penalty` (minimum `1`).
+    pub fn new(penalty: u32) -> Self {
+        Morphology { penalty: penalty.max(1), plurals: true, past_tenses: true, proper_nouns: Default::default() }
+    }
+    /// Sets whether words that look like plurals (e.g., ending in `s` but not `ss`, `us`, or `is`) are penalized.
+    pub fn with_plurals(mut self, plurals: bool) -> Self {
+        self.plurals = plurals;
+        self
+    }
+    /// Sets whether words that look like past tenses (e.g., ending in `ed` but not `eed`) are penalized.
+    pub fn with_past_tenses(mut self, past_tenses: bool) -> Self {
+        self.past_tenses = past_tenses;
+        self
+    }
+    /// Adds words that should be penalized as proper nouns.
+    /// Dictionaries are lowercase, so these cannot be detected from the words alone.
+    pub fn with_proper_nouns<'b, T: IntoIterator<Item = &'b str>>(mut self, words: T) -> Self {
+        self.proper_nouns.extend(words.into_iter().map(str::to_owned));
+        self
+    }
+    fn is_plural(word: &str) -> bool {
+        word.ends_with('s') && !["ss", "us", "is"].iter().any(|x| word.ends_with(x))
+    }
+    fn is_past_tense(word: &str) -> bool {
+        word.ends_with("ed") && !word.ends_with("eed")
+    }
+}
+impl PriorModifier for Morphology {
+    fn modify(&self, word: &str, weight: u32) -> u32 {
+        let penalized = (self.plurals && Self::is_plural(word))
+            || (self.past_tenses && Self::is_past_tense(word))
+            || self.proper_nouns.contains(word);
+        if penalized { weight / self.penalty } else { weight }
+    }
+}
+
+#[test]
+fn test_prior_morphology() {
+    let dict = Dictionary::with_words(5, ["crane", "tales", "glass", "bonus", "bored", "breed", "paris", "texas", "aided"]).unwrap();
+    let prior = Prior::uniform(&dict);
+    assert_eq!(prior.weight("crane"), Prior::DEFAULT_WEIGHT);
+    assert_eq!(prior.weight("zzzzz"), 0);
+    assert_eq!(prior.weight("cranes"), 0);
+    assert_eq!(prior.total(), 9 * Prior::DEFAULT_WEIGHT as u64);
+
+    let prior = Prior::uniform(&dict).with_modifier(Morphology::new(10).with_proper_nouns(["texas"]));
+    let weights: Vec<_> = dict.words().map(|w| (w, prior.weight(w))).collect();
+    assert_eq!(weights, &[("aided", 100), ("bonus", 1000), ("bored", 100), ("breed", 1000), ("crane", 1000), ("glass", 1000), ("paris", 1000), ("tales", 100), ("texas", 100)]);
+
+    let prior = Prior::uniform(&dict).with_modifier(Morphology::new(10).with_plurals(false).with_past_tenses(false));
+    assert!(dict.words().all(|w| prior.weight(w) == Prior::DEFAULT_WEIGHT));
+    let prior = prior.with_modifier(|w: &str, x: u32| if w.starts_with('b') { x * 2 } else { x });
+    assert_eq!((prior.weight("bored"), prior.weight("aided")), (2000, 1000));
+
+    let weighted = Prior::uniform(&dict).with_weights([("crane", 5), ("bored", 0)]).unwrap();
+    assert_eq!((weighted.weight("crane"), weighted.weight("bored"), weighted.weight("tales")), (5, 0, Prior::DEFAULT_WEIGHT));
+    assert!(matches!(Prior::uniform(&dict).with_weights([("crane", 5), ("zzzzz", 1)]), Err("zzzzz")));
+
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("glass", &get_hint("glass", "bored").unwrap()).unwrap();
+    assert_eq!(puzzle.candidates(), &["bored", "breed"]);
+    assert_eq!(prior.most_likely(puzzle.candidates(), 10), &[("bored", 2000), ("breed", 2000)]);
+    assert_eq!(prior.most_likely(dict.words(), 4), &[("bonus", 2000), ("bored", 2000), ("breed", 2000), ("aided", 1000)]);
+}
@@ -0,0 +1,141 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::*;
+
+/// The ranking of a probe: `(groups, smallest largest group, feasible)`, where larger is better.
+type ProbeKey = (usize, Reverse<usize>, bool);
+
+/// A guess chosen to split the feasible words into as many groups as possible, as produced by [`Puzzle::best_probe`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Probe {
+    /// The word to guess.
+    pub word: String,
+    /// The number of distinct hints the feasible words would produce, i.e., the number of groups they are split into.
+    pub groups: usize,
+    /// The size of the largest group.
+    pub largest_group: usize,
+    /// Whether the probe word could itself be the answer.
+    pub feasible: bool,
+}
+
+/// The word families among the feasible words and the risk they pose, as produced by [`Puzzle::family_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FamilyReport<'a> {
+    /// The families of feasible words, from largest to smallest (see [`Puzzle::clusters`]).
+    pub families: Vec<Vec<&'a str>>,
+    /// The number of guesses which guessing feasible words one at a time could need to get through the largest family,
+    /// since each guess may rule out only itself. This is zero if there are no families.
+    pub worst_chain: usize,
+    /// Whether the worst chain is longer than the number of guesses left, so guessing feasible words could lose the game.
+    pub at_risk: bool,
+    /// A probe which splits the feasible words better than any feasible guess (see [`Puzzle::best_probe`]), recommended when at risk.
+    /// This is [`None`] if not at risk, or if the best probe is a feasible word anyway.
+    pub probe: Option<Probe>,
+}
+
+impl<'a> Puzzle<'a> {
+    /// Clusters the feasible words into families which differ only in a single slot from another member (e.g., `fight`, `light`, `might`),
+    /// which are the families that are hard to distinguish by guessing feasible words alone.
+    /// Families with only one word are omitted, and the rest are sorted from largest to smallest (ties in lexicographic order).
+    pub fn clusters(&self) -> Vec<Vec<&'a str>> {
+        let this = self.reduced();
+        let words = &this.feasible_words;
+        let mut parents: Vec<usize> = (0..words.len()).collect();
+        fn root(parents: &mut [usize], mut x: usize) -> usize {
+            while parents[x] != x {
+                parents[x] = parents[parents[x]];
+                x = parents[x];
+            }
+            x
+        }
+        for i in 0..words.len() {
+            for j in i + 1..words.len() {
+                if iter::zip(words[i].iter(), words[j].iter()).filter(|(a, b)| a != b).count() == 1 {
+                    let (a, b) = (root(&mut parents, i), root(&mut parents, j));
+                    parents[a.max(b)] = a.min(b);
+                }
+            }
+        }
+
+        let mut families: BTreeMap<usize, Vec<&'a str>> = BTreeMap::new();
+        for i in 0..words.len() {
+            let r = root(&mut parents, i);
+            families.entry(r).or_default().push(self.dictionary.word_str(&words[i]));
+        }
+        let mut res: Vec<_> = families.into_values().filter(|x| x.len() > 1).collect();
+        res.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+        res
+    }
+    /// Finds the word from the dictionary which splits the feasible words into the most groups by hint (ideally, all singletons),
+    /// even if it cannot be the answer itself; this is the classic technique for endgames like the `_ight` family.
+    /// Ties are broken by the smallest largest group, then by preferring feasible words, then lexicographically.
+    /// Returns [`None`] if there are no feasible words.
+    pub fn best_probe(&self) -> Option<Probe> {
+        let this = self.reduced();
+        if this.feasible_words.is_empty() { return None }
+
+        let mut best: Option<(ProbeKey, Word)> = None;
+        let mut patterns: BTreeMap<Vec<Hint>, usize> = BTreeMap::new();
+        for &guess in this.all_words.iter() {
+            patterns.clear();
+            for answer in this.feasible_words.iter() {
+                *patterns.entry(hint_impl(&guess, answer)).or_default() += 1;
+            }
+            let feasible = this.feasible_words.binary_search(&guess).is_ok();
+            let key = (patterns.len(), Reverse(patterns.values().copied().max().unwrap_or(0)), feasible);
+            if best.as_ref().map(|x| key > x.0).unwrap_or(true) { best = Some((key, guess)); }
+        }
+
+        best.map(|((groups, Reverse(largest_group), feasible), word)| Probe { word: this.decode(&word), groups, largest_group, feasible })
+    }
+    /// Detects families of feasible words which differ in a single slot (see [`Puzzle::clusters`]),
+    /// and checks whether guessing feasible words risks a chain of guesses longer than the guesses left (see [`Puzzle::turns_left`]).
+    /// If so, the best probe is recommended instead.
+    pub fn family_report(&self) -> FamilyReport<'a> {
+        let families = self.clusters();
+        let worst_chain = families.first().map(Vec::len).unwrap_or(0);
+        let at_risk = worst_chain > self.turns_left();
+        let probe = if at_risk { self.best_probe().filter(|x| !x.feasible) } else { None };
+        FamilyReport { families, worst_chain, at_risk, probe }
+    }
+}
+
+#[test]
+fn test_probe() {
+    let dict = Dictionary::with_words(5, ["fight", "light", "might", "night", "sight", "tight", "fling", "fumes", "lower"]).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "night").unwrap()).unwrap();
+    assert_eq!(puzzle.candidates(), &["fight", "might", "night", "sight", "tight"]);
+    assert_eq!(puzzle.clusters(), &[vec!["fight", "might", "night", "sight", "tight"]]);
+
+    // no feasible word splits the family into more than two groups, but "fumes" can be used to probe three of the letters at once
+    let probe = puzzle.best_probe().unwrap();
+    assert_eq!(probe, Probe { word: "fumes".into(), groups: 4, largest_group: 2, feasible: false });
+
+    let report = puzzle.family_report();
+    assert_eq!((report.families.len(), report.worst_chain, report.at_risk, report.probe), (1, 5, false, None));
+    let mut late = puzzle.clone();
+    late.set_turn(Game::DEFAULT_MAX_GUESSES - 2);
+    let report = late.family_report();
+    assert_eq!((report.worst_chain, report.at_risk), (5, true));
+    assert_eq!(report.probe.unwrap().word, "fumes");
+
+    puzzle.guess("fumes", &get_hint("fumes", "night").unwrap()).unwrap();
+    assert_eq!(puzzle.candidates(), &["night", "tight"]);
+    assert_eq!(puzzle.clusters(), &[vec!["night", "tight"]]);
+    assert_eq!(puzzle.best_probe().unwrap(), Probe { word: "night".into(), groups: 2, largest_group: 1, feasible: true });
+
+    // with two left, guessing either one is as good as any probe
+    puzzle.set_turn(Game::DEFAULT_MAX_GUESSES - 1);
+    let report = puzzle.family_report();
+    assert_eq!((report.worst_chain, report.at_risk, report.probe), (2, true, None));
+
+    puzzle.guess("night", &get_hint("night", "night").unwrap()).unwrap();
+    assert!(puzzle.clusters().is_empty());
+    assert_eq!(puzzle.family_report().worst_chain, 0);
+    assert_eq!(puzzle.best_probe().unwrap(), Probe { word: "night".into(), groups: 1, largest_group: 1, feasible: true });
+
+    puzzle.guess("night", &[Hint::Absent; 5]).unwrap();
+    assert_eq!(puzzle.best_probe(), None);
+}
@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+
+use crate::*;
+
+/// A named configuration for wordle or one of its popular clones, bundling the word length, alphabet, turn limit, and word list.
+/// All of these games give hints for repeated letters by the same rules as wordle (see [`get_hint`]),
+/// so a profile only has to pick the right [`Dictionary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameProfile {
+    /// The original game, using the bundled english word list.
+    Wordle,
+    /// A wordle clone with a list of lewd words, which is not bundled and must be supplied.
+    Lewdle,
+    /// A wordle clone where every answer and guess is a 5-digit prime number.
+    Primel,
+    /// A wordle clone with words about Taylor Swift, whose list is not bundled and must be supplied.
+    Taylordle,
+}
+impl GameProfile {
+    /// Gets all the supported profiles.
+    pub fn all() -> &'static [GameProfile] {
+        &[GameProfile::Wordle, GameProfile::Lewdle, GameProfile::Primel, GameProfile::Taylordle]
+    }
+    /// Gets the lowercase name of the game (e.g., `"primel"`).
+    pub fn name(self) -> &'static str {
+        match self {
+            GameProfile::Wordle => "wordle",
+            GameProfile::Lewdle => "lewdle",
+            GameProfile::Primel => "primel",
+            GameProfile::Taylordle => "taylordle",
+        }
+    }
+    /// Looks up a profile by its name, as given by [`GameProfile::name`].
+    pub fn from_name(name: &str) -> Option<GameProfile> {
+        Self::all().iter().copied().find(|x| x.name() == name)
+    }
+    /// Gets the number of letters in each word.
+    pub fn word_len(self) -> usize {
+        5
+    }
+    /// Gets the number of guesses allowed in each game.
+    pub fn max_guesses(self) -> usize {
+        Game::DEFAULT_MAX_GUESSES
+    }
+    /// Gets the alphabet that words are composed of (the digits `0..=9` for primel).
+    pub fn alphabet(self) -> Alphabet {
+        match self {
+            GameProfile::Primel => Alphabet::new('0'..='9').unwrap(),
+            _ => Alphabet::english(),
+        }
+    }
+    /// Gets the bundled words for the game, separated by whitespace.
+    /// Returns [`None`] if the game's word list is not bundled, in which case it must be supplied to [`GameProfile::dictionary_with`].
+    pub fn words(self) -> Option<Cow<'static, str>> {
+        match self {
+            GameProfile::Wordle => Some(Cow::Borrowed(include_str!("guess-list.txt"))),
+            GameProfile::Primel => Some(Cow::Owned(prime_words(self.word_len()).join(" "))),
+            GameProfile::Lewdle | GameProfile::Taylordle => None,
+        }
+    }
+    /// Creates a [`Dictionary`] from the bundled words, or returns [`None`] if they are not bundled (see [`GameProfile::words`]).
+    pub fn dictionary(self) -> Option<Dictionary> {
+        Some(self.dictionary_with(&self.words()?).unwrap())
+    }
+    /// Creates a [`Dictionary`] for the game from the given whitespace-separated words.
+    /// If a word is invalid for the game (incorrect length or contains characters outside the alphabet), returns [`Err`].
+    pub fn dictionary_with(self, words: &str) -> Result<Dictionary, WordError<'_>> {
+        Dictionary::with_alphabet(self.alphabet(), self.word_len(), words.split_whitespace())
+    }
+}
+
+impl Dictionary {
+    /// The most digits supported by [`Dictionary::primes`].
+    pub const MAX_PRIME_DIGITS: usize = 7;
+
+    /// Creates a dictionary of every prime number with exactly `digits` digits (no leading zeros), over the digits `0..=9`, as used by primel.
+    /// The primes are computed when called, which takes a few milliseconds for 5 digits.
+    /// Panics if `digits` is zero or more than [`Dictionary::MAX_PRIME_DIGITS`].
+    pub fn primes(digits: usize) -> Self {
+        let words = prime_words(digits);
+        Dictionary::with_alphabet(GameProfile::Primel.alphabet(), digits, words.iter().map(String::as_str)).unwrap()
+    }
+}
+
+/// Finds the prime numbers with exactly `digits` digits with a sieve of Eratosthenes, in ascending order.
+fn prime_words(digits: usize) -> Vec<String> {
+    assert!(digits > 0 && digits <= Dictionary::MAX_PRIME_DIGITS, "unsupported number of digits");
+    let (start, end) = (10usize.pow(digits as u32 - 1), 10usize.pow(digits as u32));
+    let mut composite = vec![false; end];
+    let mut res = vec![];
+    for i in 2..end {
+        if composite[i] { continue }
+        if i >= start { res.push(i.to_string()); }
+        for j in (i * i..end).step_by(i) { composite[j] = true; }
+    }
+    res
+}
+
+#[test]
+fn test_profiles() {
+    for &profile in GameProfile::all() {
+        assert_eq!(GameProfile::from_name(profile.name()), Some(profile));
+        assert_eq!(profile.words().is_some(), profile.dictionary().is_some());
+    }
+    assert_eq!(GameProfile::from_name("scrabble"), None);
+    assert!(GameProfile::Wordle.dictionary().unwrap().contains("crane"));
+
+    let primel = GameProfile::Primel.dictionary().unwrap();
+    assert_eq!(primel.fingerprint(), Dictionary::primes(5).fingerprint());
+    assert_eq!(primel.len(), 8363);
+    assert!(primel.contains("10007") && primel.contains("99991"));
+    assert!(!primel.contains("10001") && !primel.contains("09973") && !primel.contains("crane"));
+    assert_eq!(primel.alphabet().get_hint("11113", "13331").unwrap(), &[Hint::Correct, Hint::Present, Hint::Absent, Hint::Absent, Hint::Present]);
+
+    assert_eq!(Dictionary::primes(1).words().collect::<Vec<_>>(), &["2", "3", "5", "7"]);
+    assert_eq!(Dictionary::primes(2).len(), 21);
+    let mut puzzle = Puzzle::new(&primel);
+    puzzle.guess("12345", &primel.alphabet().get_hint("12345", "65027").unwrap()).unwrap();
+    assert!(puzzle.candidates().contains(&"65027") && puzzle.candidates().len() < primel.len() / 10);
+
+    assert!(GameProfile::Taylordle.words().is_none());
+    let taylordle = GameProfile::Taylordle.dictionary_with("swift\nkarma  style").unwrap();
+    assert_eq!(taylordle.words().collect::<Vec<_>>(), &["karma", "style", "swift"]);
+    assert!(GameProfile::Lewdle.dictionary_with("toolong").is_err());
+}
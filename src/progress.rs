@@ -0,0 +1,110 @@
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use crate::*;
+
+/// A progress update for a long computation, such as from [`Puzzle::best_guess_progress`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of items (e.g., guesses) which have been processed.
+    pub done: usize,
+    /// The total number of items to process.
+    pub total: usize,
+    /// The time spent so far.
+    pub elapsed: Duration,
+    /// The best guess found so far, if the computation is a search and anything has been found yet.
+    pub best: Option<RankedGuess>,
+}
+impl Progress {
+    /// Gets the fraction of the work which is done, from `0.0` to `1.0`.
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 { 1.0 } else { self.done as f64 / self.total as f64 }
+    }
+    /// Estimates the time remaining by assuming the remaining items take as long as the ones so far, or [`None`] if nothing is done yet.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.done == 0 { return None }
+        Some(self.elapsed.mul_f64((self.total - self.done) as f64 / self.done as f64))
+    }
+    /// Formats the update as a single line of JSON (without a trailing newline), for writing as NDJSON.
+    /// For example: `{"done":50,"total":100,"percent":50.0,"elapsed_ms":120,"eta_ms":120,"best":{"word":"raise","worst_case":168,"average":61.0008,"feasible":true}}`.
+    /// The `eta_ms` and `best` fields are `null` when not known.
+    pub fn to_json(&self) -> String {
+        let mut res = String::new();
+        write!(res, "{{\"done\":{},\"total\":{},\"percent\":{:.1},\"elapsed_ms\":{},", self.done, self.total, 100.0 * self.fraction(), self.elapsed.as_millis()).unwrap();
+        match self.eta() {
+            Some(eta) => write!(res, "\"eta_ms\":{},", eta.as_millis()).unwrap(),
+            None => res.push_str("\"eta_ms\":null,"),
+        }
+        match &self.best {
+            Some(best) => {
+                res.push_str("\"best\":{\"word\":\"");
+                for ch in best.word.chars() {
+                    match ch {
+                        '"' | '\\' => { res.push('\\'); res.push(ch); }
+                        ch if (ch as u32) < 0x20 => write!(res, "\\u{:04x}", ch as u32).unwrap(),
+                        ch => res.push(ch),
+                    }
+                }
+                write!(res, "\",\"worst_case\":{},\"average\":{},\"feasible\":{}}}}}", best.worst_case, best.average, best.feasible).unwrap();
+            }
+            None => res.push_str("\"best\":null}"),
+        }
+        res
+    }
+}
+
+/// Tracks the progress of a search over guesses (which may be shared between threads), reporting at most once per percent.
+pub(crate) struct SearchProgress<'a, 'p> {
+    start: Instant,
+    done: usize,
+    total: usize,
+    reported: usize,
+    best: Option<RankKey<'a>>,
+    callback: &'p mut (dyn FnMut(&Progress) + Send),
+}
+impl<'a, 'p> SearchProgress<'a, 'p> {
+    pub(crate) fn new(total: usize, callback: &'p mut (dyn FnMut(&Progress) + Send)) -> Self {
+        SearchProgress { start: Instant::now(), done: 0, total, reported: 0, best: None, callback }
+    }
+    /// Records that a guess was scored (with [`None`] if it was pruned), reporting progress if another percent is done.
+    pub(crate) fn finish_one(&mut self, puzzle: &Puzzle<'a>, key: Option<RankKey<'a>>) {
+        self.done += 1;
+        if let Some(key) = key {
+            if self.best.map(|best| key < best).unwrap_or(true) { self.best = Some(key); }
+        }
+        let percent = self.done * 100 / self.total;
+        if percent > self.reported || self.done == self.total {
+            self.reported = percent;
+            let best = self.best.map(|((worst_case, average), infeasible, guess)| {
+                RankedGuess { word: puzzle.decode(&guess), worst_case, average, feasible: !infeasible }
+            });
+            (self.callback)(&Progress { done: self.done, total: self.total, elapsed: self.start.elapsed(), best });
+        }
+    }
+}
+
+#[test]
+fn test_progress() {
+    let mut progress = Progress { done: 0, total: 4, elapsed: Duration::from_millis(10), best: None };
+    assert_eq!(progress.eta(), None);
+    assert_eq!(progress.to_json(), r#"{"done":0,"total":4,"percent":0.0,"elapsed_ms":10,"eta_ms":null,"best":null}"#);
+
+    progress.done = 1;
+    progress.best = Some(RankedGuess { word: "ab\"c".into(), worst_case: 3, average: Average::new(5, 2), feasible: false });
+    assert_eq!(progress.eta(), Some(Duration::from_millis(30)));
+    assert_eq!(progress.to_json(), r#"{"done":1,"total":4,"percent":25.0,"elapsed_ms":10,"eta_ms":30,"best":{"word":"ab\"c","worst_case":3,"average":2.5000,"feasible":false}}"#);
+
+    let dict = testkit::dictionary(testkit::ANAGRAM_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.set_endgame_threshold(0);
+    for threads in [1, 4] {
+        let mut updates = vec![];
+        let best = puzzle.best_guess_progress(threads, AnyWord, |x: &Progress| updates.push(x.clone())).unwrap();
+        let expected = puzzle.best_guess(threads, AnyWord).unwrap();
+        assert_eq!((&best.word, best.worst_case, best.average), (&expected.word, expected.worst_case, expected.average));
+        assert!(updates.windows(2).all(|x| x[0].done < x[1].done));
+        let last = updates.last().unwrap();
+        assert_eq!((last.done, last.total), (dict.len(), dict.len()));
+        assert_eq!(last.best.as_ref().unwrap().word, best.word);
+    }
+}
@@ -0,0 +1,101 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::*;
+
+/// A guess chosen to maximize the probability that few words remain after making it, as produced by [`Puzzle::best_guess_prob_left`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbLeftGuess {
+    /// The word to guess.
+    pub word: String,
+    /// The total prior weight of the answers which would leave at most `k` words after making the guess.
+    pub weight: u64,
+    /// The total prior weight of all the words which could be the answer.
+    pub total_weight: u64,
+    /// Whether the guess could itself be the answer.
+    pub feasible: bool,
+}
+impl ProbLeftGuess {
+    /// Gets the probability that at most `k` words remain after making the guess.
+    pub fn probability(&self) -> f64 {
+        if self.total_weight == 0 { return 0.0 }
+        self.weight as f64 / self.total_weight as f64
+    }
+}
+
+impl<'a> Puzzle<'a> {
+    /// From the words in the dictionary which are admitted by `filter`, finds the guess which maximizes the probability
+    /// that at most `k` words could still be the answer after making it (where guessing the answer leaves zero), with answers weighted by `prior`.
+    /// This is a risk-averse alternative to [`Puzzle::best_guess`], which minimizes the worst case instead.
+    /// Ties are broken by preferring words which could be the answer, then lexicographically.
+    /// With only one guess left, only words which could be the answer are suggested (unless the filter rejects all of them).
+    ///
+    /// The guesses are scored in parallel using the given number of threads (minimum `1`).
+    /// Fails under the same conditions as [`Puzzle::best_guess`].
+    pub fn best_guess_prob_left<F: CandidateFilter>(&self, threads: usize, filter: F, k: usize, prior: &Prior) -> Result<ProbLeftGuess, SolveErr> {
+        let this = self.reduced();
+        if this.feasible_words.is_empty() {
+            return Err(SolveErr::Inconsistent);
+        }
+        let mut admissible: Vec<_> = this.all_words.iter().copied().filter(|w| filter.admits(&this.decode(w))).collect();
+        if admissible.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+        if this.turns_left() == 1 && admissible.iter().any(|&w| this.could_be(w)) {
+            admissible.retain(|&w| this.could_be(w));
+        }
+
+        let weights: Vec<u64> = this.feasible_words.iter().map(|w| prior.weight(this.dictionary.word_str(w)) as u64).collect();
+        let chunk_size = admissible.len().div_ceil(threads.max(1));
+        let (weight, feasible, Reverse(guess)) = crossbeam::scope(|scope| {
+            let threads: Vec<_> = admissible.chunks(chunk_size).map(|guesses| {
+                let (this, weights) = (&this, &weights);
+                scope.spawn(move |_| guesses.iter().map(|&guess| (this.weight_left(guess, k, weights), this.could_be(guess), Reverse(guess))).max().unwrap())
+            }).collect();
+            threads.into_iter().map(|x| x.join().unwrap()).max().unwrap()
+        }).unwrap();
+
+        Ok(ProbLeftGuess { word: this.decode(&guess), weight, total_weight: weights.iter().sum(), feasible })
+    }
+    /// Sums the weights of the feasible words which would leave at most `k` words after guessing `guess`.
+    fn weight_left(&self, guess: Word, k: usize, weights: &[u64]) -> u64 {
+        let solved = hint_code(&guess, &guess);
+        let mut groups: HashMap<u32, (usize, u64)> = HashMap::new();
+        for (answer, &weight) in iter::zip(self.feasible_words.iter(), weights) {
+            let group = groups.entry(hint_code(&guess, answer)).or_default();
+            group.0 += 1;
+            group.1 += weight;
+        }
+        groups.into_iter().filter(|&(code, (size, _))| code == solved || size <= k).map(|(_, (_, weight))| weight).sum()
+    }
+}
+
+#[test]
+fn test_best_guess_prob_left() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "lower", "wombs"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+    let candidates = puzzle.candidates();
+    let prior = Prior::uniform(&dict);
+
+    for k in [0, 1, 2, 3] {
+        let best = puzzle.best_guess_prob_left(3, AnyWord, k, &prior).unwrap();
+        let left = |guess: &str| partition_counts(guess, &candidates).unwrap().into_iter()
+            .filter(|&(pattern, n)| n <= k || pattern.hints().iter().all(|&h| h == Hint::Correct)).map(|x| x.1).sum::<usize>();
+        assert!(dict.words().all(|w| left(w) <= left(&best.word)));
+        assert_eq!(best.weight, left(&best.word) as u64 * Prior::DEFAULT_WEIGHT as u64);
+        assert_eq!(best.total_weight, candidates.len() as u64 * Prior::DEFAULT_WEIGHT as u64);
+        assert_eq!(best, puzzle.best_guess_prob_left(1, AnyWord, k, &prior).unwrap());
+    }
+    let best = puzzle.best_guess_prob_left(2, AnyWord, usize::MAX, &prior).unwrap();
+    assert_eq!((best.word.as_str(), best.probability(), best.feasible), (candidates[0], 1.0, true));
+
+    let skewed = Prior::uniform(&dict).with_modifier(|w: &str, x| if w == "patch" { x * 100 } else { x });
+    assert_eq!(puzzle.best_guess_prob_left(2, AnyWord, 0, &skewed).unwrap().word, "patch");
+    assert_eq!(puzzle.suggest(&Strategy::ProbTwoLeft { k: 0 }, 2).unwrap(), candidates[0]);
+
+    assert!(matches!(puzzle.best_guess_prob_left(1, |_: &str| false, 2, &prior), Err(SolveErr::NoAdmissibleGuess)));
+    puzzle.guess("batch", &[Hint::Correct; 5]).unwrap();
+    puzzle.guess("catch", &[Hint::Correct; 5]).unwrap();
+    assert!(matches!(puzzle.best_guess_prob_left(1, AnyWord, 2, &prior), Err(SolveErr::Inconsistent)));
+}
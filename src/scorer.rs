@@ -0,0 +1,207 @@
+use std::cmp::{Ordering, Reverse};
+
+use crate::*;
+
+/// A rule for scoring the guesses considered by [`Puzzle::best_guess_by`], which suggests the guess with the highest score.
+/// Unlike [`Strategy`], which selects between the solver's built-in searches, this makes the scoring rule itself pluggable,
+/// e.g., to experiment with new objectives. The built-in rules are [`MinimaxScorer`], [`EntropyScorer`], [`FrequencyScorer`], [`RandomScorer`],
+/// and [`CompositeScorer`] (which blends several of them).
+pub trait GuessScorer: Sync {
+    /// The score of a guess, where greater is better. Scores which are incomparable (like NaN) are treated as ties.
+    type Score: PartialOrd + Send;
+
+    /// Scores `guess` given the words which could still be the answer (`candidates`, in lexicographic order)
+    /// and the number of them which would remain after each hint the guess could receive (`partition`, largest first).
+    fn score(&self, guess: &str, candidates: &[&str], partition: &[(HintPattern, u64)]) -> Self::Score;
+}
+
+/// A [`GuessScorer`] which minimizes the worst-case number of remaining words, then the average case, like [`Puzzle::best_guess`] (without the endgame search).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinimaxScorer;
+impl GuessScorer for MinimaxScorer {
+    type Score = Reverse<(u64, Average)>;
+    fn score(&self, _: &str, candidates: &[&str], partition: &[(HintPattern, u64)]) -> Self::Score {
+        let worst_case = partition.iter().map(|x| x.1).max().unwrap_or(0);
+        Reverse((worst_case, Average::new(candidates.len() as u64, partition.len() as u64)))
+    }
+}
+
+/// A [`GuessScorer`] which maximizes the expected information gained from the hint, like [`Puzzle::best_guess_entropy`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EntropyScorer;
+impl GuessScorer for EntropyScorer {
+    type Score = f64;
+    fn score(&self, _: &str, candidates: &[&str], partition: &[(HintPattern, u64)]) -> Self::Score {
+        partition_entropy(partition.iter().map(|x| x.1).collect(), candidates.len() as u64)
+    }
+}
+
+/// A [`GuessScorer`] which maximizes letter coverage: the number of words which could still be the answer containing each distinct letter of the guess, summed.
+/// This ignores the hints the guess could receive, so it is a cheap heuristic rather than a search.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrequencyScorer;
+impl GuessScorer for FrequencyScorer {
+    type Score = usize;
+    fn score(&self, guess: &str, candidates: &[&str], _: &[(HintPattern, u64)]) -> Self::Score {
+        let mut letters: Vec<char> = guess.chars().collect();
+        letters.sort_unstable();
+        letters.dedup();
+        letters.into_iter().map(|ch| candidates.iter().filter(|w| w.contains(ch)).count()).sum()
+    }
+}
+
+/// A [`GuessScorer`] which gives every guess a pseudorandom score determined by the seed, e.g., as a baseline for comparing other rules.
+/// The scores are stable across runs and platforms.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomScorer {
+    pub seed: u64,
+}
+impl GuessScorer for RandomScorer {
+    type Score = u64;
+    fn score(&self, guess: &str, _: &[&str], _: &[(HintPattern, u64)]) -> Self::Score {
+        stable_hash(std::iter::once(self.seed).chain(guess.chars().map(|ch| ch as u64)))
+    }
+}
+
+/// A [`GuessScorer`] which blends several objectives with the given weights, e.g., to tune how much the solver gambles on guessing the answer.
+/// Each objective is scaled to be between `0` and `1` (so the weights are comparable), and the score is the weighted sum of
+/// the fractions of the words which could be the answer that are left in the worst case (`worst_case`) and in the average case (`average_case`),
+/// which are subtracted, and the entropy of the hint as a fraction of the most possible (`entropy`) and the probability that the guess is the answer
+/// when every word is equally likely (`answer_probability`), which are added. The default has every weight zero, so every guess ties.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CompositeScorer {
+    pub worst_case: f64,
+    pub average_case: f64,
+    pub entropy: f64,
+    pub answer_probability: f64,
+}
+impl CompositeScorer {
+    /// Parses comma-separated `<objective>=<weight>` pairs, where the objectives are `worst`, `average`, `entropy`, and `answer`
+    /// (e.g., `worst=1,entropy=0.5`). Objectives which are not given have a weight of zero.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut res = CompositeScorer::default();
+        for pair in s.split(',') {
+            let (key, value) = pair.split_once('=')?;
+            let weight = match key.trim() {
+                "worst" => &mut res.worst_case,
+                "average" => &mut res.average_case,
+                "entropy" => &mut res.entropy,
+                "answer" => &mut res.answer_probability,
+                _ => return None,
+            };
+            *weight = value.trim().parse().ok().filter(|x: &f64| x.is_finite())?;
+        }
+        Some(res)
+    }
+}
+impl GuessScorer for CompositeScorer {
+    type Score = f64;
+    fn score(&self, guess: &str, candidates: &[&str], partition: &[(HintPattern, u64)]) -> Self::Score {
+        let n = candidates.len().max(1) as f64;
+        let worst_case = partition.iter().map(|x| x.1).max().unwrap_or(0) as f64 / n;
+        let average_case = 1.0 / partition.len().max(1) as f64; // the average group has n / groups words
+        let entropy = if n > 1.0 { EntropyScorer.score(guess, candidates, partition) / n.log2() } else { 0.0 };
+        let answer_probability = if candidates.binary_search(&guess).is_ok() { 1.0 / n } else { 0.0 };
+        self.entropy * entropy + self.answer_probability * answer_probability - self.worst_case * worst_case - self.average_case * average_case
+    }
+}
+
+impl<'a> Puzzle<'a> {
+    /// From the words in the dictionary which are admitted by `filter`, finds the guess with the highest score according to `scorer`.
+    /// Ties are broken by preferring words which could be the answer, then lexicographically.
+    /// With only one guess left, only words which could be the answer are suggested (unless the filter rejects all of them).
+    /// The endgame search is never used, and the worst and average cases in the result are for the chosen guess, whatever the scorer.
+    ///
+    /// The guesses are scored in parallel using the given number of threads (minimum `1`).
+    /// Fails under the same conditions as [`Puzzle::best_guess`].
+    pub fn best_guess_by<F: CandidateFilter, S: GuessScorer>(&self, threads: usize, filter: F, scorer: &S) -> Result<BestGuess, SolveErr> {
+        let this = self.reduced();
+        if this.feasible_words.is_empty() {
+            return Err(SolveErr::Inconsistent);
+        }
+        let mut admissible: Vec<_> = this.all_words.iter().copied().filter(|w| filter.admits(&this.decode(w))).collect();
+        if admissible.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+        if this.turns_left() == 1 && admissible.iter().any(|&w| this.could_be(w)) {
+            admissible.retain(|&w| this.could_be(w));
+        }
+
+        let candidates = this.candidates();
+        let better = |a: &(S::Score, bool, Reverse<Word<'a>>), b: &(S::Score, bool, Reverse<Word<'a>>)| {
+            a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal).then_with(|| (a.1, a.2).cmp(&(b.1, b.2)))
+        };
+        let chunk_size = admissible.len().div_ceil(threads.max(1));
+        let (_, feasible, Reverse(guess)) = crossbeam::scope(|scope| {
+            let threads: Vec<_> = admissible.chunks(chunk_size).map(|guesses| {
+                let (this, candidates) = (&this, &candidates);
+                scope.spawn(move |_| guesses.iter().map(|&guess| {
+                    let score = scorer.score(&this.decode(&guess), candidates, &this.partition(guess));
+                    (score, this.could_be(guess), Reverse(guess))
+                }).max_by(better).unwrap())
+            }).collect();
+            threads.into_iter().map(|x| x.join().unwrap()).max_by(better).unwrap()
+        }).unwrap();
+
+        let partition = this.partition(guess);
+        let Reverse((worst_case, average)) = MinimaxScorer.score("", &candidates, &partition);
+        Ok(BestGuess { word: this.decode(&guess), worst_case, average, feasible, endgame: false, expected_guesses: None, counters: SearchCounters::default(), approximate: false, partition })
+    }
+}
+
+#[test]
+fn test_best_guess_by() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "lower", "wombs"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+    puzzle.set_endgame_threshold(0);
+
+    let minimax = puzzle.best_guess_by(3, AnyWord, &MinimaxScorer).unwrap();
+    let expected = puzzle.best_guess(1, AnyWord).unwrap();
+    assert_eq!((&minimax.word, minimax.worst_case, minimax.average, minimax.feasible, &minimax.partition),
+        (&expected.word, expected.worst_case, expected.average, expected.feasible, &expected.partition));
+    assert_eq!(puzzle.best_guess_by(2, AnyWord, &EntropyScorer).unwrap().word, puzzle.best_guess_entropy(1, AnyWord).unwrap().word);
+
+    // every candidate contains 'a', 't', 'c', and 'h', so the coverage of the other letter decides
+    let candidates = puzzle.candidates();
+    let frequency = puzzle.best_guess_by(1, AnyWord, &FrequencyScorer).unwrap();
+    assert!(dict.words().all(|w| FrequencyScorer.score(w, &candidates, &[]) <= FrequencyScorer.score(&frequency.word, &candidates, &[])));
+
+    let random = puzzle.best_guess_by(2, AnyWord, &RandomScorer { seed: 7 }).unwrap();
+    assert_eq!(random, puzzle.best_guess_by(1, AnyWord, &RandomScorer { seed: 7 }).unwrap());
+    let words: std::collections::BTreeSet<_> = (0..20).map(|seed| puzzle.best_guess_by(1, AnyWord, &RandomScorer { seed }).unwrap().word).collect();
+    assert!(words.len() > 1);
+
+    // a custom scorer which prefers the last word in the dictionary
+    struct Last;
+    impl GuessScorer for Last {
+        type Score = String;
+        fn score(&self, guess: &str, _: &[&str], _: &[(HintPattern, u64)]) -> String { guess.into() }
+    }
+    assert_eq!(puzzle.best_guess_by(1, AnyWord, &Last).unwrap().word, "wombs");
+    assert_eq!(puzzle.best_guess_by(1, |w: &str| w.ends_with("atch"), &Last).unwrap().word, "watch");
+    assert!(matches!(puzzle.best_guess_by(1, |_: &str| false, &Last), Err(SolveErr::NoAdmissibleGuess)));
+
+    // a single objective agrees with its own scorer, and enough weight on guessing the answer only suggests words which could be
+    let worst = CompositeScorer { worst_case: 1.0, ..Default::default() };
+    assert_eq!(puzzle.best_guess_by(1, AnyWord, &worst).unwrap().worst_case, minimax.worst_case);
+    let entropy = CompositeScorer { entropy: 2.0, ..Default::default() };
+    assert_eq!(puzzle.best_guess_by(3, AnyWord, &entropy).unwrap().word, puzzle.best_guess_by(1, AnyWord, &EntropyScorer).unwrap().word);
+    let gamble = CompositeScorer { answer_probability: 100.0, ..entropy };
+    assert!(puzzle.best_guess_by(1, AnyWord, &gamble).unwrap().feasible);
+    let mut partition: Vec<(HintPattern, u64)> = partition_counts("chimp", &candidates).unwrap().into_iter().map(|(p, n)| (p, n as u64)).collect();
+    partition.sort_by_key(|&(p, n)| (Reverse(n), p.code()));
+    let score = CompositeScorer { worst_case: 1.0, average_case: 2.0, entropy: 3.0, answer_probability: 4.0 }.score("chimp", &candidates, &partition);
+    let n = candidates.len() as f64;
+    let feasible = if candidates.contains(&"chimp") { 1.0 } else { 0.0 };
+    let expected = 3.0 * EntropyScorer.score("chimp", &candidates, &partition) / n.log2() + 4.0 * feasible / n
+        - partition[0].1 as f64 / n - 2.0 / partition.len() as f64;
+    assert!((score - expected).abs() < 1e-9);
+    assert_eq!(CompositeScorer::default().score("chimp", &candidates, &partition), 0.0);
+
+    assert_eq!(CompositeScorer::parse("worst=1, entropy=0.5"), Some(CompositeScorer { worst_case: 1.0, entropy: 0.5, ..Default::default() }));
+    assert_eq!(CompositeScorer::parse("average=2,answer=-1"), Some(CompositeScorer { average_case: 2.0, answer_probability: -1.0, ..Default::default() }));
+    for bad in ["", "worst", "worst=x", "speed=1", "worst=inf", "worst=1,"] {
+        assert_eq!(CompositeScorer::parse(bad), None, "{}", bad);
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::Mutex;
+
+use rhai::{Array, Dynamic, Engine, Scope, AST};
+
+use crate::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    /// The script could not be parsed.
+    Syntax(String),
+    /// The script does not define `fn score(guess, remaining, partition, feasible)`.
+    MissingScore,
+    /// The script failed while scoring a guess, or returned something other than a number.
+    Runtime { guess: String, error: String },
+}
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScriptError::Syntax(e) => write!(f, "syntax error: {}", e),
+            ScriptError::MissingScore => write!(f, "the script must define fn score(guess, remaining, partition, feasible)"),
+            ScriptError::Runtime { guess, error } => write!(f, "failed to score '{}': {}", guess, error),
+        }
+    }
+}
+
+/// A [`GuessScorer`] which runs a user-supplied [Rhai](https://rhai.rs) script, so that new scoring rules can be tried without recompiling.
+/// The script must define a function `score(guess, remaining, partition, feasible)`, which is given the guess (a string),
+/// the number of words which could still be the answer, the number of them left after each hint the guess could receive (an array, largest first),
+/// and whether the guess could itself be the answer. It returns a number, where greater is better. For example, this minimizes the worst case:
+/// ```text
+/// fn score(guess, remaining, partition, feasible) { -partition[0] }
+/// ```
+/// Each call is limited to [`ScriptScorer::MAX_OPERATIONS`] operations, so a script which doesn't terminate still fails.
+/// A failed call scores the guess as NaN (a tie with everything) and is kept for [`ScriptScorer::take_error`].
+pub struct ScriptScorer {
+    engine: Engine,
+    ast: AST,
+    error: Mutex<Option<ScriptError>>,
+}
+impl ScriptScorer {
+    /// The maximum number of operations the script may perform to score a single guess.
+    pub const MAX_OPERATIONS: u64 = 1_000_000;
+
+    /// Compiles the given script, failing if it is invalid or does not define the `score` function.
+    pub fn new(source: &str) -> Result<Self, ScriptError> {
+        let mut engine = Engine::new();
+        engine.set_max_operations(Self::MAX_OPERATIONS);
+        let ast = engine.compile(source).map_err(|e| ScriptError::Syntax(e.to_string()))?;
+        if !ast.iter_functions().any(|f| f.name == "score" && f.params.len() == 4) {
+            return Err(ScriptError::MissingScore);
+        }
+        Ok(ScriptScorer { engine, ast, error: Mutex::new(None) })
+    }
+    /// Takes the first error from scoring a guess since the last call (if any), e.g., to report it after a search.
+    pub fn take_error(&self) -> Option<ScriptError> {
+        self.error.lock().unwrap().take()
+    }
+}
+impl GuessScorer for ScriptScorer {
+    type Score = f64;
+    fn score(&self, guess: &str, candidates: &[&str], partition: &[(HintPattern, u64)]) -> Self::Score {
+        let sizes: Array = partition.iter().map(|x| Dynamic::from_int(x.1 as i64)).collect();
+        let feasible = candidates.binary_search(&guess).is_ok();
+        let args = (guess.to_owned(), candidates.len() as i64, sizes, feasible);
+        let res = self.engine.call_fn::<Dynamic>(&mut Scope::new(), &self.ast, "score", args).map_err(|e| e.to_string()).and_then(|x| {
+            x.as_float().or_else(|_| x.as_int().map(|x| x as f64)).map_err(|t| format!("expected a number, got {}", t))
+        });
+        res.unwrap_or_else(|error| {
+            self.error.lock().unwrap().get_or_insert(ScriptError::Runtime { guess: guess.into(), error });
+            f64::NAN
+        })
+    }
+}
+
+#[test]
+fn test_script_scorer() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "lower", "wombs"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+    puzzle.set_endgame_threshold(0);
+
+    // a script can reproduce a built-in rule
+    let worst_case = ScriptScorer::new("fn score(guess, remaining, partition, feasible) { -partition[0] * 1000 + partition.len() }").unwrap();
+    let minimax = puzzle.best_guess_by(2, AnyWord, &worst_case).unwrap();
+    assert_eq!(minimax.word, puzzle.best_guess(1, AnyWord).unwrap().word);
+    assert_eq!(worst_case.take_error(), None);
+
+    let feasible = ScriptScorer::new("fn score(guess, remaining, partition, feasible) { if feasible { 1.5 } else { 0.0 } }").unwrap();
+    assert!(puzzle.best_guess_by(1, AnyWord, &feasible).unwrap().feasible);
+    let last = ScriptScorer::new(r#"fn score(guess, remaining, partition, feasible) { if guess == "wombs" { remaining } else { 0 } }"#).unwrap();
+    assert_eq!(puzzle.best_guess_by(1, AnyWord, &last).unwrap().word, "wombs");
+
+    assert!(matches!(ScriptScorer::new("fn score(guess) { 1 }"), Err(ScriptError::MissingScore)));
+    assert!(matches!(ScriptScorer::new("fn score(guess, remaining, partition, feasible) {"), Err(ScriptError::Syntax(_))));
+    for source in ["fn score(guess, remaining, partition, feasible) { \"high\" }", "fn score(guess, remaining, partition, feasible) { loop {} }"] {
+        let broken = ScriptScorer::new(source).unwrap();
+        assert!(puzzle.best_guess_by(1, AnyWord, &broken).is_ok());
+        assert!(matches!(broken.take_error(), Some(ScriptError::Runtime { .. })), "{}", source);
+        assert_eq!(broken.take_error(), None);
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use crate::*;
+
+/// The answers which take a solver the most guesses to find, as produced by [`Puzzle::hardest_answers`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HardestAnswers {
+    /// The number of guesses (from the current state, including the last guess) the solver needs to find each of the answers,
+    /// which may be more than the guesses left in the game (i.e., the solver loses). This is [`None`] if the solver never finds them,
+    /// because it makes a guess which gains no information (e.g., by repeating a [`Strategy::Fixed`] guess).
+    pub guesses: Option<usize>,
+    /// The hardest answers, in lexicographic order.
+    pub answers: Vec<String>,
+    /// The guesses the solver makes against the first of the answers, ending with it (or with the guess that gains no information).
+    pub line: Vec<String>,
+}
+
+impl<'a> Puzzle<'a> {
+    /// Finds the words which could be the answer that take the solver the most guesses to find when it plays by `strategy` from the current state,
+    /// e.g., for a puzzle setter who wants to stump players who use a solver.
+    /// The solver is deterministic, so this plays out its whole game tree: each guess it would make splits the answers by the hint they give,
+    /// and every group is followed to its end. This takes one search per state of the tree (at most one per answer),
+    /// which is fast for strategies like [`Strategy::Minimax`] once the opening guesses have narrowed down the answer.
+    /// The `threads` input is forwarded to [`Puzzle::suggest`].
+    /// Fails with [`SolveErr::Inconsistent`] if no word could be the answer, with [`SolveErr::InvalidExtraGuess`] if the strategy suggests an invalid word,
+    /// or with any error from [`Puzzle::suggest`].
+    pub fn hardest_answers(&self, strategy: &Strategy, threads: usize) -> Result<HardestAnswers, SolveErr> {
+        let answers = self.candidates();
+        if answers.is_empty() {
+            return Err(SolveErr::Inconsistent);
+        }
+        let (guesses, mut hardest) = self.hardest_among(strategy, threads, &answers, 0)?;
+        hardest.sort_unstable();
+
+        // replay the game against the first answer for its line
+        let mut puzzle = self.clone();
+        let mut line = vec![];
+        loop {
+            let guess = puzzle.suggest(strategy, threads)?;
+            line.push(guess.clone());
+            if guess == hardest[0] { break }
+            let remaining = puzzle.candidates().len();
+            let hint = self.dictionary.alphabet.get_hint(&guess, hardest[0]).map_err(|_| SolveErr::InvalidExtraGuess(guess.clone()))?;
+            puzzle.guess(&guess, &hint).map_err(|_| SolveErr::InvalidExtraGuess(guess.clone()))?;
+            if puzzle.candidates().len() == remaining { break } // no progress, so the answer is never found
+        }
+
+        Ok(HardestAnswers { guesses: (guesses != usize::MAX).then_some(guesses), answers: hardest.into_iter().map(String::from).collect(), line })
+    }
+    /// Finds the number of guesses the solver needs for the hardest of the given answers (the feasible words), after `made` guesses from the original state,
+    /// along with every answer which needs that many. Answers which are never found need [`usize::MAX`] guesses.
+    fn hardest_among(&self, strategy: &Strategy, threads: usize, answers: &[&'a str], made: usize) -> Result<(usize, Vec<&'a str>), SolveErr> {
+        let guess = self.suggest(strategy, threads)?;
+        let mut groups: BTreeMap<HintPattern, (Vec<Hint>, Vec<&'a str>)> = BTreeMap::new();
+        for &answer in answers {
+            let hint = self.dictionary.alphabet.get_hint(&guess, answer).map_err(|_| SolveErr::InvalidExtraGuess(guess.clone()))?;
+            groups.entry(HintPattern::from_hints(&hint)).or_insert_with(|| (hint, vec![])).1.push(answer);
+        }
+
+        let mut worst = (0, vec![]);
+        for (hint, group) in groups.into_values() {
+            let (guesses, group) = if group[0] == guess {
+                (made + 1, group) // the only answer with the all-correct hint is the guess itself
+            } else if group.len() == answers.len() {
+                (usize::MAX, group) // no information was gained, so the solver would make the same guess forever
+            } else {
+                let mut child = self.clone();
+                child.guess(&guess, &hint).map_err(|_| SolveErr::InvalidExtraGuess(guess.clone()))?;
+                child.hardest_among(strategy, threads, &group, made + 1)?
+            };
+            if guesses > worst.0 {
+                worst = (guesses, group);
+            } else if guesses == worst.0 {
+                worst.1.extend(group);
+            }
+        }
+        Ok(worst)
+    }
+}
+
+#[test]
+fn test_hardest_answers() {
+    // plays a game from the state against every answer, counting the guesses
+    fn reference(puzzle: &Puzzle, strategy: &Strategy, answer: &str) -> usize {
+        let mut puzzle = puzzle.clone();
+        for turn in 1.. {
+            let guess = puzzle.suggest(strategy, 1).unwrap();
+            if guess == answer { return turn }
+            puzzle.guess(&guess, &get_hint(&guess, answer).unwrap()).unwrap();
+        }
+        unreachable!()
+    }
+
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "wield", "flows"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    for strategy in [Strategy::Minimax, Strategy::FeasibleOnly, Strategy::Entropy] {
+        for history in [vec![], vec![("wield", get_hint("wield", "catch").unwrap())]] {
+            puzzle.reset();
+            puzzle.apply_history(&history).unwrap();
+            let hardest = puzzle.hardest_answers(&strategy, 2).unwrap();
+            let counts: Vec<(&str, usize)> = puzzle.candidates().into_iter().map(|a| (a, reference(&puzzle, &strategy, a))).collect();
+            let most = counts.iter().map(|x| x.1).max().unwrap();
+            assert_eq!(hardest.guesses, Some(most), "{:?} {:?}", strategy, history);
+            assert_eq!(hardest.answers, counts.iter().filter(|x| x.1 == most).map(|x| x.0).collect::<Vec<_>>());
+            assert_eq!((hardest.line.len(), hardest.line.last()), (most, Some(&hardest.answers[0])));
+            assert_eq!(hardest, puzzle.hardest_answers(&strategy, 1).unwrap());
+        }
+    }
+
+    // guessing only the _atch words finds one per guess, so the last one takes more guesses than the game allows
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let hardest = Puzzle::new(&dict).hardest_answers(&Strategy::FeasibleOnly, 1).unwrap();
+    assert_eq!((hardest.guesses, hardest.answers.len()), (Some(testkit::ATCH_WORDS.len()), 1));
+
+    // a fixed guess gains nothing the second time, so the other answers are never found
+    let stuck = Puzzle::new(&dict).hardest_answers(&Strategy::Fixed("batch".into()), 1).unwrap();
+    assert_eq!(stuck, HardestAnswers { guesses: None, answers: testkit::ATCH_WORDS[1..].iter().map(|&x| x.into()).collect(), line: vec!["batch".into(), "batch".into()] });
+    assert!(matches!(Puzzle::new(&dict).hardest_answers(&Strategy::Fixed("bat".into()), 1), Err(SolveErr::InvalidExtraGuess(x)) if x == "bat"));
+    let mut inconsistent = Puzzle::new(&dict);
+    inconsistent.guess("batch", &[Hint::Absent; 5]).unwrap();
+    assert!(matches!(inconsistent.hardest_answers(&Strategy::Minimax, 1), Err(SolveErr::Inconsistent)));
+}
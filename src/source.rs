@@ -0,0 +1,131 @@
+use std::fmt::{self, Write};
+
+use crate::*;
+
+/// Where a word list came from, so deployments can audit which list produced a suggestion (see [`Dictionary::with_source`]).
+/// Only the words themselves affect [`Dictionary::fingerprint`], so cached results are invalidated whenever a list changes, whatever its metadata says.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionarySource {
+    /// A human-readable name for the list (e.g., a file name).
+    pub name: String,
+    /// The version or release date of the list, if known.
+    pub version: Option<String>,
+    /// The license the list is distributed under, if known.
+    pub license: Option<String>,
+    /// Where the list can be found, if known.
+    pub url: Option<String>,
+    /// The number of words in the list, if known (filled in by [`Dictionary::with_source`]).
+    pub word_count: Option<usize>,
+}
+impl DictionarySource {
+    /// Creates a source with the given name and no other details.
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        DictionarySource { name: name.into(), version: None, license: None, url: None, word_count: None }
+    }
+    /// Sets the version or release date of the list.
+    pub fn with_version<S: Into<String>>(mut self, version: S) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+    /// Sets the license of the list.
+    pub fn with_license<S: Into<String>>(mut self, license: S) -> Self {
+        self.license = Some(license.into());
+        self
+    }
+    /// Sets the URL of the list.
+    pub fn with_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+    /// Sets the number of words in the list.
+    pub fn with_word_count(mut self, word_count: usize) -> Self {
+        self.word_count = Some(word_count);
+        self
+    }
+}
+impl fmt::Display for DictionarySource {
+    /// Describes the source on one line, e.g., `guesses.txt, version 2022-02-01, CC0-1.0 license, 12972 words (https://example.com/guesses.txt)`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(version) = &self.version { write!(f, ", version {}", version)?; }
+        if let Some(license) = &self.license { write!(f, ", {} license", license)?; }
+        if let Some(word_count) = self.word_count { write!(f, ", {} words", word_count)?; }
+        if let Some(url) = &self.url { write!(f, " ({})", url)?; }
+        Ok(())
+    }
+}
+
+impl GameProfile {
+    /// Describes the built-in word list for the game (see [`GameProfile::words`]), whose version is that of this crate.
+    pub fn source(self) -> DictionarySource {
+        DictionarySource::new(format!("built-in {} word list", self.name())).with_version(env!("CARGO_PKG_VERSION"))
+    }
+}
+
+/// Appends `value` to `out` as a JSON string literal, or `null` if missing.
+pub(crate) fn write_json_string(out: &mut String, value: Option<&str>) {
+    let value = match value {
+        Some(x) => x,
+        None => return out.push_str("null"),
+    };
+    out.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            ch if (ch as u32) < 0x20 => write!(out, "\\u{:04x}", ch as u32).unwrap(),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+impl Dictionary {
+    /// Attaches metadata about where the words came from, replacing any previous source.
+    /// The word count of the source is set to that of the dictionary.
+    pub fn with_source(mut self, source: DictionarySource) -> Self {
+        self.source = Some(source.with_word_count(self.len()));
+        self
+    }
+    /// Gets the metadata about where the words came from, if any was given (see [`Dictionary::with_source`]).
+    pub fn source(&self) -> Option<&DictionarySource> {
+        self.source.as_ref()
+    }
+    /// Describes the word list as a single line of JSON, including its source (fields are `null` when unknown), word count, and fingerprint.
+    /// For example: `{"name":"guesses.txt","version":"2022-02-01","license":null,"url":null,"word_count":12972,"fingerprint":"0123456789abcdef"}`.
+    pub fn provenance_json(&self) -> String {
+        let source = self.source.as_ref();
+        let mut res = String::from("{\"name\":");
+        write_json_string(&mut res, source.map(|x| x.name.as_str()));
+        for (key, value) in [("version", source.and_then(|x| x.version.as_deref())), ("license", source.and_then(|x| x.license.as_deref())), ("url", source.and_then(|x| x.url.as_deref()))] {
+            write!(res, ",\"{}\":", key).unwrap();
+            write_json_string(&mut res, value);
+        }
+        let word_count = source.and_then(|x| x.word_count).unwrap_or_else(|| self.len());
+        write!(res, ",\"word_count\":{},\"fingerprint\":\"{:016x}\"}}", word_count, self.fingerprint()).unwrap();
+        res
+    }
+}
+
+#[test]
+fn test_source() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    assert_eq!(dict.source(), None);
+    assert_eq!(dict.provenance_json(), format!("{{\"name\":null,\"version\":null,\"license\":null,\"url\":null,\"word_count\":7,\"fingerprint\":\"{:016x}\"}}", dict.fingerprint()));
+
+    let source = DictionarySource::new("my \"atch\" list").with_version("2022-02-01").with_license("CC0-1.0").with_url("https://example.com/atch.txt");
+    let dict = dict.with_source(source.clone().with_word_count(100));
+    assert_eq!(dict.source(), Some(&source.clone().with_word_count(7)));
+    assert_eq!(dict.source().unwrap().to_string(), "my \"atch\" list, version 2022-02-01, CC0-1.0 license, 7 words (https://example.com/atch.txt)");
+    assert_eq!(DictionarySource::new("words.txt").to_string(), "words.txt");
+    assert!(dict.provenance_json().starts_with(r#"{"name":"my \"atch\" list","version":"2022-02-01","license":"CC0-1.0","url":"https://example.com/atch.txt","word_count":7,"#));
+    assert_eq!(dict.fingerprint(), testkit::dictionary(testkit::ATCH_WORDS).fingerprint());
+
+    assert_eq!(GameProfile::Wordle.source().name, "built-in wordle word list");
+    assert_eq!(GameProfile::Wordle.source().version.as_deref(), Some(env!("CARGO_PKG_VERSION")));
+
+    let mut out = String::new();
+    write_json_string(&mut out, Some("a\\b\n\u{1}ñ"));
+    assert_eq!(out, r#""a\\b\n\u0001ñ""#);
+}
@@ -0,0 +1,69 @@
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::*;
+
+/// How long a write waits for another process holding the database lock before giving up.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A [`CacheStore`] backed by a single SQLite database file, which (unlike [`FileStore`]) keeps a large cache in one file.
+/// SQLite handles the locking, so several processes can safely share the database.
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+impl SqliteStore {
+    /// Opens a store in the given database file, creating it (and its table) if needed.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::with_connection(Connection::open(path).map_err(io::Error::other)?)
+    }
+    /// Opens a store in a private in-memory database, e.g., for tests.
+    pub fn open_in_memory() -> io::Result<Self> {
+        Self::with_connection(Connection::open_in_memory().map_err(io::Error::other)?)
+    }
+    fn with_connection(conn: Connection) -> io::Result<Self> {
+        conn.busy_timeout(BUSY_TIMEOUT).map_err(io::Error::other)?;
+        conn.execute("CREATE TABLE IF NOT EXISTS cache (key INTEGER PRIMARY KEY, value BLOB NOT NULL)", ()).map_err(io::Error::other)?;
+        Ok(SqliteStore { conn: Mutex::new(conn) })
+    }
+}
+impl CacheStore for SqliteStore {
+    fn get(&self, key: u64) -> io::Result<Option<Vec<u8>>> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row("SELECT value FROM cache WHERE key = ?1", [key as i64], |row| row.get(0)).optional().map_err(io::Error::other)
+    }
+    fn put(&self, key: u64, value: &[u8]) -> io::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute("INSERT OR REPLACE INTO cache (key, value) VALUES (?1, ?2)", (key as i64, value)).map_err(io::Error::other)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_sqlite_store() {
+    let path = std::env::temp_dir().join(format!("wordle-solver-test-cache-{}.sqlite", std::process::id()));
+    let store = SqliteStore::open(&path).unwrap();
+    assert_eq!(store.get(u64::MAX).unwrap(), None);
+    store.put(u64::MAX, b"first").unwrap();
+    store.put(u64::MAX, b"second").unwrap();
+    store.put(1, b"").unwrap();
+    assert_eq!(store.get(u64::MAX).unwrap().as_deref(), Some(&b"second"[..]));
+    assert_eq!(store.get(1).unwrap().as_deref(), Some(&b""[..]));
+
+    // entries are shared with another connection to the same file
+    let other = SqliteStore::open(&path).unwrap();
+    assert_eq!(other.get(u64::MAX).unwrap().as_deref(), Some(&b"second"[..]));
+    drop((store, other));
+    std::fs::remove_file(&path).unwrap();
+
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["wield", "blimp"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("wield", &get_hint("wield", "catch").unwrap()).unwrap();
+    let store = SqliteStore::open_in_memory().unwrap();
+    let best = puzzle.best_guess_cached(1, &store).unwrap();
+    assert_eq!(puzzle.best_guess_cached(1, &store).unwrap().word, best.word);
+    assert!(store.get(puzzle.state_hash()).unwrap().is_some());
+}
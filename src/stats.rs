@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use crate::*;
+
+/// Summary statistics of a [`Dictionary`], as produced by [`Dictionary::stats`].
+/// All per-letter tables are indexed in the order of [`DictionaryStats::letters`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryStats {
+    /// The letters of the dictionary's alphabet, in order.
+    pub letters: Vec<char>,
+    /// The total number of occurrences of each letter over all words.
+    pub letter_counts: Vec<usize>,
+    /// The number of words containing each letter at least once.
+    pub word_counts: Vec<usize>,
+    /// The number of occurrences of each letter in each slot, indexed by `[slot][letter]`.
+    pub positional_counts: Vec<Vec<usize>>,
+    /// The number of words containing at least one repeated letter.
+    pub duplicate_letter_words: usize,
+    /// The number of groups of two or more words which are anagrams of one another.
+    pub anagram_groups: usize,
+    /// The total number of words which belong to an anagram group.
+    pub anagram_words: usize,
+}
+
+impl Dictionary {
+    /// Computes letter frequency tables and other statistics about the words in the dictionary.
+    pub fn stats(&self) -> DictionaryStats {
+        let letters = self.alphabet.chars().to_vec();
+        let mut letter_counts = vec![0; letters.len()];
+        let mut word_counts = vec![0; letters.len()];
+        let mut positional_counts = vec![vec![0; letters.len()]; self.word_len];
+        let mut duplicate_letter_words = 0;
+        let mut anagrams: HashMap<Vec<u8>, usize> = HashMap::new();
+
+        for word in self.data.chunks_exact(self.word_len) {
+            let mut seen = BitSet32::new();
+            let mut duplicate = false;
+            for (slot, &letter) in word.iter().enumerate() {
+                letter_counts[letter as usize] += 1;
+                positional_counts[slot][letter as usize] += 1;
+                if seen.contains(letter) { duplicate = true; }
+                seen.insert(letter);
+            }
+            for letter in seen {
+                word_counts[letter as usize] += 1;
+            }
+            duplicate_letter_words += duplicate as usize;
+
+            let mut sorted = word.to_vec();
+            sorted.sort_unstable();
+            *anagrams.entry(sorted).or_default() += 1;
+        }
+
+        let groups = anagrams.values().filter(|&&n| n > 1);
+        DictionaryStats {
+            letters, letter_counts, word_counts, positional_counts, duplicate_letter_words,
+            anagram_groups: groups.clone().count(),
+            anagram_words: groups.sum(),
+        }
+    }
+}
+
+#[test]
+fn test_stats() {
+    let dict = testkit::dictionary(testkit::ANAGRAM_WORDS);
+    let stats = dict.stats();
+    let idx = |ch: char| stats.letters.iter().position(|&x| x == ch).unwrap();
+    assert_eq!(stats.letter_counts.iter().sum::<usize>(), 5 * dict.len());
+    assert_eq!(stats.word_counts[idx('a')], dict.len());
+    assert_eq!(stats.letter_counts[idx('n')], 1); // only "crane"
+    assert_eq!(stats.positional_counts[0][idx('s')], 3); // "slate", "stale", "steal"
+    assert_eq!(stats.duplicate_letter_words, 0);
+    // {caret, cater, crate, react, trace}, {least, slate, stale, steal, tales, teals}
+    assert_eq!((stats.anagram_groups, stats.anagram_words), (2, 11));
+
+    let stats = testkit::dictionary(testkit::DUPLICATE_WORDS).stats();
+    assert_eq!(stats.duplicate_letter_words, testkit::DUPLICATE_WORDS.len());
+}
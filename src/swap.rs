@@ -0,0 +1,138 @@
+use crate::*;
+
+/// A swap of the letters in two slots, given as a pair of slot indices.
+pub type Swap = (usize, usize);
+
+/// Finds a shortest sequence of swaps which rearranges `from` into `to` (which must be a permutation of `from`).
+/// Swapping two letters which complete each other's slots is always optimal, so those are taken greedily;
+/// otherwise (only possible with repeated letters) every choice is searched with a simple lower bound for pruning.
+fn min_swaps(from: &[u8], to: &[u8]) -> Vec<Swap> {
+    fn search(cur: &mut [u8], to: &[u8], swaps: &mut Vec<Swap>, best: &mut Option<Vec<Swap>>) {
+        let mismatched = iter::zip(cur.iter(), to).filter(|(a, b)| a != b).count();
+        if let Some(best) = best {
+            if swaps.len() + mismatched.div_ceil(2) >= best.len() { return }
+        }
+        let i = match iter::zip(cur.iter(), to).position(|(a, b)| a != b) {
+            Some(i) => i,
+            None => {
+                *best = Some(swaps.clone());
+                return;
+            }
+        };
+
+        let pair = (i + 1..cur.len()).find(|&j| cur[j] == to[i] && to[j] == cur[i]);
+        let choices: Vec<usize> = match pair {
+            Some(j) => vec![j],
+            None => (i + 1..cur.len()).filter(|&j| cur[j] == to[i] && cur[j] != to[j]).collect(),
+        };
+        for j in choices {
+            cur.swap(i, j);
+            swaps.push((i, j));
+            search(cur, to, swaps, best);
+            swaps.pop();
+            cur.swap(i, j);
+        }
+    }
+
+    let mut best = None;
+    search(&mut from.to_vec(), to, &mut vec![], &mut best);
+    best.expect("not a permutation")
+}
+
+/// A Waffle-like puzzle, where all the letters of the answer are known but scrambled,
+/// and the hint after each swap marks which letters are already in the right place.
+/// The constraints are tracked by an underlying [`Puzzle`], with the current arrangement treated as a guess.
+#[derive(Clone)]
+pub struct SwapPuzzle<'a> {
+    puzzle: Puzzle<'a>,
+    letters: OwnedWord,
+    sorted: OwnedWord,
+}
+impl<'a> SwapPuzzle<'a> {
+    /// Creates a new swap puzzle from the scrambled `letters` and the initial hint for them.
+    pub fn new<'b>(dictionary: &'a Dictionary, letters: &'b str, hint: &'b [Hint]) -> Result<Self, GuessError<'b>> {
+        let mut puzzle = Puzzle::new(dictionary);
+        puzzle.guess(letters, hint)?;
+        puzzle.force_reduce();
+        let letters = puzzle.encode(letters).unwrap();
+        let mut sorted = letters.0.clone();
+        sorted.sort_unstable();
+        Ok(SwapPuzzle { puzzle, letters, sorted: OwnedWord(sorted) })
+    }
+    /// Gets the current arrangement of the letters.
+    pub fn letters(&self) -> String {
+        self.puzzle.decode(&self.letters)
+    }
+    /// Gets the words in the dictionary which could be the answer, i.e., rearrangements of the letters consistent with all hints so far.
+    pub fn candidates(&self) -> Vec<&'a str> {
+        let mut buf = vec![];
+        self.puzzle.feasible_words.iter().filter(|word| {
+            buf.clear();
+            buf.extend_from_slice(word);
+            buf.sort_unstable();
+            buf == *self.sorted
+        }).map(|word| self.puzzle.dictionary.word_str(word)).collect()
+    }
+    /// Swaps the letters in slots `a` and `b` and applies the resulting hint for the new arrangement.
+    /// Panics if either slot is out of bounds.
+    pub fn swap<'b>(&mut self, a: usize, b: usize, hint: &'b [Hint]) -> Result<(), GuessError<'b>> {
+        if hint.len() != self.letters.len() { return Err(GuessError::WrongHintLen { hint, expected_len: self.letters.len() }); }
+        self.letters.0.swap(a, b);
+        self.puzzle.guess_impl(self.letters.as_ref(), hint);
+        Ok(())
+    }
+    /// Picks the candidate answer which can be reached in the fewest swaps (ties broken by dictionary order),
+    /// and returns it along with the swaps needed to reach it.
+    pub fn best_swaps(&self) -> Result<(&'a str, Vec<Swap>), SolveErr> {
+        let mut best: Option<(&'a str, Vec<Swap>)> = None;
+        for candidate in self.candidates() {
+            let swaps = min_swaps(&self.letters, &self.puzzle.encode(candidate).unwrap());
+            if best.as_ref().map(|x| swaps.len() < x.1.len()).unwrap_or(true) {
+                best = Some((candidate, swaps));
+            }
+        }
+        best.ok_or(SolveErr::Inconsistent)
+    }
+}
+
+#[test]
+fn test_min_swaps() {
+    fn check(from: &str, to: &str, expected: usize) {
+        let swaps = min_swaps(from.as_bytes(), to.as_bytes());
+        assert_eq!(swaps.len(), expected, "{} -> {}: {:?}", from, to, swaps);
+        let mut cur = from.as_bytes().to_vec();
+        for (a, b) in swaps { cur.swap(a, b); }
+        assert_eq!(cur, to.as_bytes());
+    }
+    check("abcde", "abcde", 0);
+    check("bacde", "abcde", 1);
+    check("bcdea", "abcde", 4);
+    check("badce", "abcde", 2);
+    check("aabbc", "bbaac", 2);
+    check("abab", "baba", 2);
+    check("aabcb", "abcab", 2);
+}
+
+#[test]
+fn test_swap_puzzle() {
+    use Hint::*;
+    let dict = Dictionary::with_words(5, ["steal", "stale", "least", "slate", "tales", "teals", "batch", "catch"]).unwrap();
+
+    // stale and steal are both consistent with the first hint and equally close, so the tie goes to dictionary order
+    let mut puzzle = SwapPuzzle::new(&dict, "stlea", &get_hint("stlea", "steal").unwrap()).unwrap();
+    assert_eq!(puzzle.letters(), "stlea");
+    assert_eq!(puzzle.candidates(), &["stale", "steal"]);
+    assert_eq!(puzzle.best_swaps().unwrap(), ("stale", vec![(2, 4), (3, 4)]));
+
+    puzzle.swap(2, 3, &get_hint("stela", "steal").unwrap()).unwrap();
+    assert_eq!(puzzle.letters(), "stela");
+    assert_eq!(puzzle.candidates(), &["steal"]);
+    assert_eq!(puzzle.best_swaps().unwrap(), ("steal", vec![(3, 4)]));
+    puzzle.swap(3, 4, &[Correct; 5]).unwrap();
+    assert_eq!(puzzle.best_swaps().unwrap(), ("steal", vec![]));
+
+    assert!(matches!(puzzle.swap(0, 1, &[Correct]), Err(GuessError::WrongHintLen { .. })));
+    let puzzle = SwapPuzzle::new(&dict, "ahcbt", &[Present; 5]).unwrap();
+    assert_eq!(puzzle.candidates(), &["batch"]);
+    assert!(matches!(SwapPuzzle::new(&dict, "sxale", &[Correct; 5]).unwrap().best_swaps(), Err(SolveErr::Inconsistent)));
+}
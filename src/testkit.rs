@@ -0,0 +1,193 @@
+//! Canonical fixtures for regression testing code which embeds the solver.
+//!
+//! Everything here is plain static data (plus a few helpers to build [`Dictionary`] values from it),
+//! so downstream crates can share the same puzzles without copying word lists around.
+
+use crate::{Dictionary, Hint};
+
+use Hint::{Absent as A, Correct as C, Present as P};
+
+/// A tiny dictionary of the classic `_atch` family, which is notoriously slow to solve by only guessing feasible words.
+pub const ATCH_WORDS: &[&str] = &["batch", "catch", "hatch", "latch", "match", "patch", "watch"];
+
+/// A tiny dictionary made up of anagrams and near-anagrams of common opening words.
+pub const ANAGRAM_WORDS: &[&str] = &["caret", "cater", "crane", "crate", "least", "react", "slate", "stale", "steal", "tales", "teals", "trace"];
+
+/// A tiny dictionary where almost every word contains a repeated letter.
+pub const DUPLICATE_WORDS: &[&str] = &["abbey", "babes", "ebbed", "eerie", "ether", "geese", "kebab", "otter", "sheep", "there", "three", "totem"];
+
+/// A small but varied dictionary of common words, large enough that the best guess isn't obvious but small enough to check by brute force.
+pub const MINI_WORDS: &[&str] = &[
+    "about", "above", "actor", "adopt", "after", "again", "alarm", "album", "alien", "anger",
+    "apple", "arena", "audio", "badge", "baker", "beach", "bench", "black", "blade", "bloom",
+    "board", "brave", "bread", "brick", "cabin", "candy", "chair", "chalk", "charm", "chess",
+    "cider", "climb", "cloud", "coral", "crane", "crowd", "dance", "dream", "eagle", "earth",
+    "fable", "flame", "frost", "ghost", "grape", "honey", "lemon", "music", "ocean", "piano",
+];
+
+/// Builds a five-letter [`Dictionary`] from one of the word lists in this module.
+pub fn dictionary(words: &[&str]) -> Dictionary {
+    Dictionary::with_words(5, words.iter().copied()).unwrap()
+}
+
+/// Finds the words which could be the answer after the given guesses by simulating [`get_hint`](crate::get_hint) against every word.
+/// This is far slower than [`Puzzle::candidates`](crate::Puzzle::candidates), but obviously correct, so it serves as a reference for differential testing.
+/// The result is sorted and deduplicated, like the candidates of a puzzle over the same words.
+pub fn reference_candidates<'a, W: AsRef<str>, H: AsRef<[Hint]>>(words: &[&'a str], history: &[(W, H)]) -> Vec<&'a str> {
+    let mut res: Vec<&str> = words.iter().copied().filter(|word| {
+        history.iter().all(|(guess, hint)| crate::get_hint_chars(guess.as_ref(), word).map(|x| x == hint.as_ref()).unwrap_or(false))
+    }).collect();
+    res.sort_unstable();
+    res.dedup();
+    res
+}
+
+/// A `(guess, answer, hint)` triple for checking hint generation.
+pub type HintCase = (&'static str, &'static str, &'static [Hint]);
+
+/// Known-tricky hint cases involving repeated letters in the guess, the answer, or both.
+pub const DUPLICATE_LETTER_CASES: &[HintCase] = &[
+    ("hello", "pogos", &[A, A, A, A, P]),
+    ("holop", "pogas", &[A, C, A, A, P]),
+    ("holop", "pooas", &[A, C, A, P, P]),
+    ("holop", "pogos", &[A, C, A, C, P]),
+    ("holop", "pogao", &[A, C, A, P, P]),
+    ("holop", "oogaa", &[A, C, A, P, A]),
+    ("pogos", "hello", &[A, P, A, A, A]),
+    ("pogas", "holop", &[P, C, A, A, A]),
+    ("pooas", "holop", &[P, C, P, A, A]),
+    ("pogos", "holop", &[P, C, A, C, A]),
+    ("pogao", "holop", &[P, C, A, A, P]),
+    ("oogaa", "holop", &[P, C, A, A, A]),
+    ("oogaa", "hloop", &[P, P, A, A, A]),
+    ("oogaa", "hollp", &[A, C, A, A, A]),
+    ("speed", "abide", &[A, A, P, A, P]),
+    ("geese", "there", &[A, A, C, A, C]),
+    ("eerie", "ether", &[C, P, P, A, A]),
+];
+
+/// An expected result of [`Puzzle::best_guess`](crate::Puzzle::best_guess) on a fresh puzzle with the endgame solver disabled.
+#[derive(Debug, Clone, Copy)]
+pub struct SolverCase {
+    /// The dictionary to construct the puzzle from.
+    pub words: &'static [&'static str],
+    /// The expected best guess.
+    pub best_guess: &'static str,
+    /// The expected worst-case number of remaining words after making the best guess.
+    pub worst_case: u64,
+}
+
+/// Expected solver outputs for the fixture dictionaries in this module, each of which the tests confirm by brute force.
+pub const SOLVER_CASES: &[SolverCase] = &[
+    SolverCase { words: ATCH_WORDS, best_guess: "batch", worst_case: 6 },
+    SolverCase { words: ANAGRAM_WORDS, best_guess: "teals", worst_case: 2 },
+    SolverCase { words: DUPLICATE_WORDS, best_guess: "ether", worst_case: 2 },
+    SolverCase { words: MINI_WORDS, best_guess: "brave", worst_case: 3 },
+];
+
+#[test]
+fn test_fixtures() {
+    for &(guess, answer, hint) in DUPLICATE_LETTER_CASES {
+        assert_eq!(crate::get_hint(guess, answer).unwrap(), hint, "{} vs {}", guess, answer);
+    }
+    for case in SOLVER_CASES {
+        let dict = dictionary(case.words);
+        let mut puzzle = crate::Puzzle::new(&dict);
+        puzzle.set_endgame_threshold(0);
+        let crate::BestGuess { word, worst_case: worst, .. } = puzzle.best_guess(2, crate::AnyWord).unwrap();
+        assert_eq!((word.as_str(), worst), (case.best_guess, case.worst_case));
+    }
+}
+
+#[test]
+fn test_golden_strategies() {
+    use std::collections::HashMap;
+    use crate::{Average, Puzzle, SolveOptions, Strategy};
+
+    // scores every guess from scratch with get_hint, independent of the solver's constraint tracking and pruning
+    let groups = |guess: &str, answers: &[&str]| {
+        let mut res: HashMap<Vec<Hint>, u64> = HashMap::new();
+        for answer in answers { *res.entry(crate::get_hint(guess, answer).unwrap()).or_default() += 1; }
+        res
+    };
+    let fixtures = [ATCH_WORDS, ANAGRAM_WORDS, DUPLICATE_WORDS, MINI_WORDS];
+    for words in fixtures {
+        let dict = dictionary(words);
+        let mut states = vec![vec![]];
+        for answer in [words[0], words[words.len() / 2]] {
+            let opener = words[words.len() - 1];
+            states.push(vec![(opener, crate::get_hint(opener, answer).unwrap())]);
+        }
+        for history in states {
+            let mut puzzle = Puzzle::new(&dict);
+            puzzle.apply_history(&history).unwrap();
+            let answers: Vec<&str> = words.iter().copied().filter(|w| history.iter().all(|(g, h)| crate::is_consistent(g, h, w))).collect();
+            assert_eq!(puzzle.candidates(), answers);
+            if answers.len() < 2 { continue }
+
+            let minimax = |pool: &[&'static str]| pool.iter().map(|&w| {
+                let groups = groups(w, &answers);
+                ((*groups.values().max().unwrap(), Average::new(answers.len() as u64, groups.len() as u64)), !answers.contains(&w), w)
+            }).min().unwrap();
+            for (strategy, pool) in [(Strategy::Minimax, words), (Strategy::FeasibleOnly, answers.as_slice())] {
+                let ((worst_case, average), _, word) = minimax(pool);
+                let best = puzzle.best_guess_opts(&SolveOptions::new().with_strategy(strategy.clone()).with_endgame_threshold(0)).unwrap();
+                assert_eq!((best.word.as_str(), best.worst_case, best.average), (word, worst_case, average), "{:?} {:?} {:?}", words, history, strategy);
+            }
+            for k in [1, 2] {
+                let (_, _, std::cmp::Reverse(word)) = words.iter().map(|&w| {
+                    let left: u64 = answers.iter().filter(|&&a| a == w || groups(w, &answers)[&crate::get_hint(w, a).unwrap()] <= k as u64).count() as u64;
+                    (left, answers.contains(&w), std::cmp::Reverse(w))
+                }).max().unwrap();
+                assert_eq!(puzzle.suggest(&Strategy::ProbTwoLeft { k }, 2).unwrap(), word, "{:?} {:?} k={}", words, history, k);
+            }
+            let entropy = |w: &str| crate::partition_entropy(groups(w, &answers).into_values().collect(), answers.len() as u64);
+            let word = words.iter().copied().reduce(|a, b| match entropy(b).total_cmp(&entropy(a)).then(answers.contains(&b).cmp(&answers.contains(&a))) {
+                std::cmp::Ordering::Greater => b,
+                _ => a,
+            }).unwrap();
+            assert_eq!(puzzle.suggest(&Strategy::Entropy, 2).unwrap(), word, "{:?} {:?}", words, history);
+        }
+    }
+}
+
+#[test]
+fn test_differential() {
+    use crate::{GameProfile, Puzzle, SolverRng};
+
+    // seeded random games against the reference, half over a few letters so that nearly every word repeats some of them
+    let english = GameProfile::Wordle.words().unwrap();
+    let english: Vec<&str> = english.split_whitespace().collect();
+    for seed in 0..2000 {
+        let mut rng = SolverRng::new(seed);
+        let words: Vec<String> = match seed % 2 {
+            0 => {
+                let letters: Vec<char> = ('a'..='z').take(3 + rng.below(4) as usize).collect();
+                (0..20 + rng.below(60)).map(|_| (0..5).map(|_| *rng.choose(&letters).unwrap()).collect()).collect()
+            }
+            _ => (0..100).map(|_| rng.choose(&english).unwrap().to_string()).collect(),
+        };
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let dict = dictionary(&words);
+        let answer = *rng.choose(&words).unwrap();
+
+        let mut puzzle = Puzzle::new(&dict);
+        let mut history = vec![];
+        for _ in 0..6 {
+            let guess = match rng.below(2) {
+                0 => *rng.choose(&words).unwrap(),
+                _ => *rng.choose(&puzzle.candidates()).unwrap(),
+            };
+            let hint = crate::get_hint(guess, answer).unwrap();
+            puzzle.guess(guess, &hint).unwrap();
+            history.push((guess, hint));
+
+            let expected = reference_candidates(&words, &history);
+            assert_eq!(puzzle.candidates(), expected, "seed {}: {:?}", seed, history);
+            let mut batched = Puzzle::new(&dict);
+            batched.apply_history(&history).unwrap();
+            assert_eq!(batched.candidates(), expected, "seed {}: {:?}", seed, history);
+            if guess == answer { break }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+/// The minimum number of `(guess, feasible word)` pairs worth handing to each thread in [`Puzzle::best_guess`](crate::Puzzle::best_guess).
+/// Below this, spawning and cloning the puzzle for another thread costs more than it saves
+/// (measured on the bundled English list, where a single pair takes on the order of tens of nanoseconds).
+const MIN_WORK_PER_THREAD: usize = 1 << 16;
+
+/// Recommends a number of threads for a search over `dictionary_size` guesses against `feasible_size` possible answers.
+/// Small searches (e.g., endgames) get fewer threads, and large ones are capped at the number of physical cores,
+/// since the search is compute-bound and gains little from SMT siblings.
+/// The result is always at least `1`.
+pub fn recommended_threads(dictionary_size: usize, feasible_size: usize) -> usize {
+    let work = dictionary_size.saturating_mul(feasible_size);
+    (work / MIN_WORK_PER_THREAD).clamp(1, num_cpus::get_physical().max(1))
+}
+
+#[test]
+fn test_recommended_threads() {
+    assert_eq!(recommended_threads(0, 0), 1);
+    assert_eq!(recommended_threads(12972, 2), 1);
+    assert_eq!(recommended_threads(usize::MAX, usize::MAX), num_cpus::get_physical().max(1));
+    assert!(recommended_threads(12972, 12972) >= recommended_threads(12972, 100));
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+/// A single step of reasoning performed by the solver, as reported by [`Puzzle::guess_traced`](crate::Puzzle::guess_traced).
+/// Slots are numbered from zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceStep {
+    /// A hint marked the letter as correct in the slot.
+    Correct { slot: usize, letter: char },
+    /// A hint marked the letter as present, but not in the slot.
+    NotInSlot { slot: usize, letter: char },
+    /// A hint marked the letter as absent, and it had no other occurrences in the guess.
+    Absent { letter: char },
+    /// A hint raised the minimum number of occurrences of the letter.
+    MinCount { letter: char, min: usize },
+    /// A hint lowered the maximum number of occurrences of the letter.
+    MaxCount { letter: char, max: usize },
+    /// Words which no longer satisfy the constraints were eliminated.
+    Feasible { before: usize, after: usize },
+    /// The letter was eliminated from the slot because no feasible word has it there.
+    Unused { slot: usize, letter: char },
+    /// The letter must be in the slot because its minimum number of occurrences equals the number of slots it can be in.
+    Forced { slot: usize, letter: char },
+    /// The letter was eliminated from the slot because it is already fixed in as many other slots as its maximum number of occurrences.
+    MaxReached { slot: usize, letter: char },
+    /// The letter was eliminated from the slot because every unfixed slot is needed for the required occurrences of other letters.
+    Reserved { slot: usize, letter: char },
+}
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TraceStep::Correct { slot, letter } => write!(f, "slot {} must be '{}' because it was marked correct", slot, letter),
+            TraceStep::NotInSlot { slot, letter } => write!(f, "slot {} can no longer be '{}' because it was marked present (but elsewhere)", slot, letter),
+            TraceStep::Absent { letter } => write!(f, "'{}' can no longer be in any slot because it was marked absent", letter),
+            TraceStep::MinCount { letter, min } => write!(f, "'{}' must occur at least {} time(s)", letter, min),
+            TraceStep::MaxCount { letter, max } => write!(f, "'{}' can occur at most {} time(s)", letter, max),
+            TraceStep::Feasible { before, after } => write!(f, "{} of {} words no longer fit the constraints ({} remaining)", before - after, before, after),
+            TraceStep::Unused { slot, letter } => write!(f, "slot {} can no longer be '{}' because no feasible word has '{}' there", slot, letter, letter),
+            TraceStep::Forced { slot, letter } => write!(f, "slot {} must be '{}' because there are no other slots left for its required occurrences", slot, letter),
+            TraceStep::MaxReached { slot, letter } => write!(f, "slot {} can no longer be '{}' because all of its allowed occurrences are already placed", slot, letter),
+            TraceStep::Reserved { slot, letter } => write!(f, "slot {} can no longer be '{}' because every unfixed slot is needed for letters which must occur more times", slot, letter),
+        }
+    }
+}
+
+#[test]
+fn test_guess_traced() {
+    use crate::*;
+    let dict = Dictionary::with_words(5, ["batch", "catch", "hatch", "latch", "lunch", "chimp", "lower"]).unwrap();
+    let hint = get_hint("lower", "latch").unwrap();
+
+    let mut steps = vec![];
+    let mut traced = Puzzle::new(&dict);
+    traced.guess_traced("lower", &hint, |x| steps.push(x)).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &hint).unwrap();
+    assert_eq!(traced.to_string(), puzzle.to_string());
+    assert_eq!(traced.candidates(), puzzle.candidates());
+
+    assert_eq!(&steps[..3], &[
+        TraceStep::Absent { letter: 'e' },
+        TraceStep::MaxCount { letter: 'e', max: 0 },
+        TraceStep::Correct { slot: 0, letter: 'l' },
+    ]);
+    assert!(steps.contains(&TraceStep::Feasible { before: 7, after: 2 }));
+    assert!(steps.contains(&TraceStep::Unused { slot: 1, letter: 'h' }));
+    assert_eq!(TraceStep::Unused { slot: 2, letter: 'r' }.to_string(), "slot 2 can no longer be 'r' because no feasible word has 'r' there");
+
+    let mut steps = vec![];
+    traced.guess_traced("lunch", &get_hint("lunch", "latch").unwrap(), |x| steps.push(x)).unwrap();
+    assert!(steps.contains(&TraceStep::Feasible { before: 2, after: 1 }));
+    assert!(!steps.contains(&TraceStep::Correct { slot: 3, letter: 'c' })); // already known from the previous reductions
+}
@@ -0,0 +1,102 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::*;
+
+/// A guess chosen to minimize the expected number of remaining words when some answers are more likely than others,
+/// as produced by [`Puzzle::best_guess_weighted`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WeightedGuess {
+    /// The word to guess.
+    pub word: String,
+    /// The expected number of words which could still be the answer after making the guess, with answers weighted by the prior.
+    /// Guessing the answer leaves zero.
+    pub expected_remaining: Average,
+    /// Whether the guess could itself be the answer.
+    pub feasible: bool,
+}
+
+impl<'a> Puzzle<'a> {
+    /// From the words in the dictionary which are admitted by `filter`, finds the guess which minimizes the expected number of words
+    /// which could still be the answer after making it (where guessing the answer leaves zero), with answers weighted by `prior`.
+    /// Unlike [`Puzzle::best_guess`], which treats every word as equally likely, this favors splitting up the likely answers
+    /// (e.g., common words, with a prior from word frequencies). If every word which could be the answer has zero weight, they are treated as equally likely.
+    /// Ties are broken by preferring words which could be the answer, then lexicographically.
+    /// With only one guess left, only words which could be the answer are suggested (unless the filter rejects all of them).
+    ///
+    /// The guesses are scored in parallel using the given number of threads (minimum `1`).
+    /// Fails under the same conditions as [`Puzzle::best_guess`].
+    pub fn best_guess_weighted<F: CandidateFilter>(&self, threads: usize, filter: F, prior: &Prior) -> Result<WeightedGuess, SolveErr> {
+        let this = self.reduced();
+        if this.feasible_words.is_empty() {
+            return Err(SolveErr::Inconsistent);
+        }
+        let mut admissible: Vec<_> = this.all_words.iter().copied().filter(|w| filter.admits(&this.decode(w))).collect();
+        if admissible.is_empty() {
+            return Err(SolveErr::NoAdmissibleGuess);
+        }
+        if this.turns_left() == 1 && admissible.iter().any(|&w| this.could_be(w)) {
+            admissible.retain(|&w| this.could_be(w));
+        }
+
+        let mut weights: Vec<u64> = this.feasible_words.iter().map(|w| prior.weight(this.dictionary.word_str(w)) as u64).collect();
+        if weights.iter().all(|&x| x == 0) { weights.fill(1); }
+        let chunk_size = admissible.len().div_ceil(threads.max(1));
+        let (expected_remaining, feasible, Reverse(guess)) = crossbeam::scope(|scope| {
+            let threads: Vec<_> = admissible.chunks(chunk_size).map(|guesses| {
+                let (this, weights) = (&this, &weights);
+                scope.spawn(move |_| guesses.iter().map(|&guess| (this.expected_remaining(guess, weights), !this.could_be(guess), Reverse(Reverse(guess)))).min().unwrap())
+            }).collect();
+            threads.into_iter().map(|x| x.join().unwrap()).min().unwrap()
+        }).unwrap();
+
+        Ok(WeightedGuess { word: this.decode(&guess.0), expected_remaining, feasible: !feasible })
+    }
+    /// Computes the expected number of feasible words left after guessing `guess`, where each feasible word is the answer with probability proportional to its weight.
+    fn expected_remaining(&self, guess: Word, weights: &[u64]) -> Average {
+        let solved = hint_code(&guess, &guess);
+        let mut groups: HashMap<u32, (u64, u64)> = HashMap::new();
+        for (answer, &weight) in iter::zip(self.feasible_words.iter(), weights) {
+            let group = groups.entry(hint_code(&guess, answer)).or_default();
+            group.0 += 1;
+            group.1 += weight;
+        }
+        let total = groups.iter().filter(|x| *x.0 != solved).map(|(_, &(size, weight))| size * weight).sum();
+        Average::new(total, weights.iter().sum())
+    }
+}
+
+#[test]
+fn test_best_guess_weighted() {
+    let dict = Dictionary::with_words(5, testkit::ATCH_WORDS.iter().copied().chain(["chimp", "blimp", "lower", "wombs"])).unwrap();
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("lower", &get_hint("lower", "hatch").unwrap()).unwrap();
+    let candidates = puzzle.candidates();
+
+    // scores every guess from scratch with get_hint
+    let expected = |guess: &str, prior: &Prior| {
+        let groups = partition_counts(guess, &candidates).unwrap();
+        let total: u64 = candidates.iter().filter(|&&a| a != guess).map(|a| groups[&HintPattern::from_hints(&get_hint(guess, a).unwrap())] as u64 * prior.weight(a) as u64).sum();
+        Average::new(total, candidates.iter().map(|a| prior.weight(a) as u64).sum())
+    };
+    let uniform = Prior::uniform(&dict);
+    let skewed = Prior::uniform(&dict).with_modifier(|w: &str, x| if w == "match" { x * 50 } else { x });
+    for prior in [&uniform, &skewed] {
+        let best = puzzle.best_guess_weighted(3, AnyWord, prior).unwrap();
+        assert_eq!(best, puzzle.best_guess_weighted(1, AnyWord, prior).unwrap());
+        assert_eq!(best.expected_remaining, expected(&best.word, prior));
+        assert!(dict.words().all(|w| expected(w, prior) >= best.expected_remaining));
+        assert_eq!(best.feasible, candidates.contains(&best.word.as_str()));
+    }
+
+    // chimp leaves one word after any hint, but when one answer is far more likely than the rest, guessing it is best
+    assert_eq!(puzzle.best_guess_weighted(1, AnyWord, &uniform).unwrap().word, "chimp");
+    assert_eq!(puzzle.best_guess_weighted(1, AnyWord, &skewed).unwrap().word, "match");
+    let zero = Prior::uniform(&dict).with_modifier(|_: &str, _| 0);
+    assert_eq!(puzzle.best_guess_weighted(1, AnyWord, &zero).unwrap().word, puzzle.best_guess_weighted(1, AnyWord, &uniform).unwrap().word);
+
+    let opts = SolveOptions::new().with_strategy(Strategy::ExpectedRemaining).with_prior(&skewed);
+    assert_eq!(puzzle.best_guess_opts(&opts).unwrap().word, "match");
+    assert_eq!(puzzle.suggest(&Strategy::ExpectedRemaining, 2).unwrap(), puzzle.best_guess_weighted(1, AnyWord, &uniform).unwrap().word);
+    assert!(matches!(puzzle.best_guess_weighted(1, |_: &str| false, &uniform), Err(SolveErr::NoAdmissibleGuess)));
+}
@@ -0,0 +1,214 @@
+use std::fmt::Write;
+
+use crate::*;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum YamlError {
+    /// A line (numbered from `1`) which could not be parsed.
+    Syntax { line: usize },
+    /// The document is for words of a different length than the dictionary.
+    WrongWordLen { expected: usize, found: usize },
+    /// The first history entry whose guess or hint is invalid for the dictionary (see [`Dictionary::validate_history`]).
+    InvalidGuess { line: usize },
+    /// A constraint which uses a letter outside the alphabet, or has the wrong number of slots.
+    InvalidConstraint { line: usize },
+}
+
+/// Quotes a string for output, escaping only quotes and backslashes.
+fn quote(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 2);
+    res.push('"');
+    for ch in s.chars() {
+        if ch == '"' || ch == '\\' { res.push('\\'); }
+        res.push(ch);
+    }
+    res.push('"');
+    res
+}
+/// Parses a (possibly) quoted string from the start of `s`, returning it and the rest of the input.
+/// Unquoted strings end at the first `:` or the end of the input, and are trimmed.
+fn unquote(s: &str) -> Option<(String, &str)> {
+    let s = s.trim_start();
+    let mut chars = s.char_indices();
+    if !s.starts_with('"') {
+        let end = s.find(':').unwrap_or(s.len());
+        return Some((s[..end].trim().to_owned(), &s[end..]));
+    }
+    chars.next();
+    let mut res = String::new();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '"' => return Some((res, &s[i + 1..])),
+            '\\' => res.push(chars.next()?.1),
+            _ => res.push(ch),
+        }
+    }
+    None
+}
+/// Parses a `key: value` entry, where both may be quoted. The value is empty for section headers.
+fn entry(s: &str) -> Option<(String, String)> {
+    let (key, rest) = unquote(s)?;
+    let rest = rest.trim_start().strip_prefix(':')?.trim();
+    let (value, rest) = unquote(rest)?;
+    if !rest.trim().is_empty() { return None }
+    Some((key, value))
+}
+/// Parses an inclusive range written as `min..=max`.
+fn range(s: &str) -> Option<(usize, usize)> {
+    let (min, max) = s.split_once("..=")?;
+    Some((min.trim().parse().ok()?, max.trim().parse().ok()?))
+}
+
+impl<'a> Puzzle<'a> {
+    /// Exports the puzzle as a small, human-editable YAML document, which can be loaded again with [`Puzzle::from_yaml`].
+    /// The document records the guess history and turn limits, and also the current constraints if they differ from what the history implies
+    /// (e.g., after [`Puzzle::relax_letter`]). Deleting the constraints section makes them be rebuilt from the history,
+    /// so a mistyped hint can be fixed by editing it in the history.
+    pub fn to_yaml(&self) -> String {
+        let letters = self.dictionary.alphabet.chars();
+        let mut res = String::from("# wordle-solver puzzle\n");
+        writeln!(res, "word_len: {}", self.slots.len()).unwrap();
+        writeln!(res, "turn: {}", self.turn).unwrap();
+        writeln!(res, "max_guesses: {}", self.max_guesses).unwrap();
+        res.push_str("history:\n");
+        for (word, hint) in self.history.iter() {
+            let hint: String = hint.iter().map(|h| h.to_char()).collect();
+            writeln!(res, "  - {}: {}", quote(word), hint).unwrap();
+        }
+
+        let mut replayed = self.restart();
+        replayed.apply_history(&self.history).unwrap();
+        if replayed.given != self.given {
+            res.push_str("constraints:\n  slots:\n");
+            for slot in self.given.slots.iter() {
+                let allowed: String = slot.into_iter().map(|x| letters[x as usize]).collect();
+                writeln!(res, "    - {}", quote(&allowed)).unwrap();
+            }
+            res.push_str("  counts:\n");
+            for (&(min, max), &letter) in iter::zip(&self.given.letter_counts, letters) {
+                if (min, max) != (0, self.slots.len()) {
+                    writeln!(res, "    {}: {}..={}", quote(&letter.to_string()), min, max).unwrap();
+                }
+            }
+        }
+        res
+    }
+    /// Loads a puzzle from a YAML document produced by [`Puzzle::to_yaml`] (possibly edited by hand).
+    /// The history is replayed in order, and if a constraints section is present, it replaces the constraints implied by the history.
+    /// Letters not listed under `counts` may occur any number of times.
+    pub fn from_yaml(dictionary: &'a Dictionary, text: &str) -> Result<Self, YamlError> {
+        enum Section { None, History, Constraints, Slots, Counts }
+
+        let mut res = Puzzle::new(dictionary);
+        let mut section = Section::None;
+        let mut turn = None;
+        let mut constraints: Option<Constraints> = None;
+        let mut slots_line = 0;
+        let mut history: Vec<(String, Vec<Hint>)> = vec![];
+        let mut history_lines = vec![];
+        for (i, raw) in text.lines().enumerate() {
+            let line = i + 1;
+            let content = raw.trim();
+            if content.is_empty() || content.starts_with('#') { continue }
+            let indent = raw.len() - raw.trim_start().len();
+
+            let (key, value) = match content.strip_prefix('-') {
+                Some(item) => (None, item.trim().to_owned()),
+                None => {
+                    let (key, value) = entry(content).ok_or(YamlError::Syntax { line })?;
+                    (Some(key), value)
+                }
+            };
+            match (indent, key.as_deref()) {
+                (0, Some("word_len")) => {
+                    let found = value.parse().map_err(|_| YamlError::Syntax { line })?;
+                    if found != dictionary.word_len { return Err(YamlError::WrongWordLen { expected: dictionary.word_len, found }); }
+                }
+                (0, Some("turn")) => turn = Some(value.parse().map_err(|_| YamlError::Syntax { line })?),
+                (0, Some("max_guesses")) => res.max_guesses = value.parse().map_err(|_| YamlError::Syntax { line })?,
+                (0, Some("history")) => section = Section::History,
+                (0, Some("constraints")) => {
+                    section = Section::Constraints;
+                    constraints = Some(Constraints { slots: vec![], letter_counts: [(0, dictionary.word_len); Alphabet::MAX_LEN] });
+                }
+                (0, Some(_)) => return Err(YamlError::Syntax { line }),
+                (_, Some("slots")) if matches!(section, Section::Constraints | Section::Counts) => { section = Section::Slots; slots_line = line; }
+                (_, Some("counts")) if matches!(section, Section::Constraints | Section::Slots) => section = Section::Counts,
+                (_, None) if matches!(section, Section::History) => {
+                    let (word, hint) = entry(&value).ok_or(YamlError::Syntax { line })?;
+                    let hint: Vec<Hint> = hint.chars().map(Hint::from_char).collect::<Option<_>>().ok_or(YamlError::InvalidGuess { line })?;
+                    history.push((word, hint));
+                    history_lines.push(line);
+                }
+                (_, None) if matches!(section, Section::Slots) => {
+                    let (allowed, rest) = unquote(&value).ok_or(YamlError::Syntax { line })?;
+                    if !rest.trim().is_empty() { return Err(YamlError::Syntax { line }); }
+                    let mut slot = BitSet32::new();
+                    for ch in allowed.chars() {
+                        slot.insert(dictionary.alphabet.index_of(ch).ok_or(YamlError::InvalidConstraint { line })?);
+                    }
+                    constraints.as_mut().unwrap().slots.push(slot);
+                }
+                (_, Some(letter)) if matches!(section, Section::Counts) => {
+                    let mut chars = letter.chars();
+                    let ch = match (chars.next(), chars.next()) {
+                        (Some(ch), None) => dictionary.alphabet.index_of(ch).ok_or(YamlError::InvalidConstraint { line })?,
+                        _ => return Err(YamlError::InvalidConstraint { line }),
+                    };
+                    constraints.as_mut().unwrap().letter_counts[ch as usize] = range(&value).ok_or(YamlError::Syntax { line })?;
+                }
+                _ => return Err(YamlError::Syntax { line }),
+            }
+        }
+
+        // the whole history is checked before any of it is applied, so the first invalid entry is reported
+        dictionary.validate_history(&history).map_err(|e| YamlError::InvalidGuess { line: history_lines[e.turn] })?;
+        res.apply_history(&history).unwrap();
+        if let Some(mut constraints) = constraints {
+            if constraints.slots.is_empty() {
+                let mut allowed = BitSet32::new();
+                for i in 0..dictionary.alphabet.chars().len() { allowed.insert(i as u8); }
+                constraints.slots = vec![allowed; dictionary.word_len];
+            }
+            if constraints.slots.len() != dictionary.word_len { return Err(YamlError::InvalidConstraint { line: slots_line }); }
+            res.given = Arc::new(constraints);
+            res.rederive();
+        }
+        if let Some(turn) = turn { res.turn = turn; }
+        res.force_reduce();
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_yaml() {
+    let dict = testkit::dictionary(testkit::ATCH_WORDS);
+    let mut puzzle = Puzzle::new(&dict);
+    puzzle.guess("watch", &get_hint("watch", "latch").unwrap()).unwrap();
+    puzzle.set_max_guesses(4);
+    let yaml = puzzle.to_yaml();
+    assert!(!yaml.contains("constraints"));
+    let loaded = Puzzle::from_yaml(&dict, &yaml).unwrap();
+    assert_eq!(loaded.candidates(), puzzle.candidates());
+    assert_eq!((loaded.history(), loaded.turns_left()), (puzzle.history(), 3));
+
+    // fixing a mistyped hint by hand
+    let mut mistyped = Puzzle::new(&dict);
+    mistyped.guess("watch", &[Hint::Absent; 5]).unwrap();
+    assert!(mistyped.candidates().is_empty());
+    let fixed = mistyped.to_yaml().replace("aaaaa", "acccc");
+    assert_eq!(Puzzle::from_yaml(&dict, &fixed).unwrap().candidates(), puzzle.candidates());
+
+    // constraints which can't be rebuilt from the history are kept
+    puzzle.relax_letter('w').unwrap();
+    let yaml = puzzle.to_yaml();
+    assert!(yaml.contains("constraints"));
+    assert_eq!(Puzzle::from_yaml(&dict, &yaml).unwrap().candidates(), puzzle.candidates());
+
+    assert_eq!(Puzzle::from_yaml(&dict, "word_len: 4\n").err(), Some(YamlError::WrongWordLen { expected: 5, found: 4 }));
+    assert_eq!(Puzzle::from_yaml(&dict, "history:\n  - \"watch\": xyz\n").err(), Some(YamlError::InvalidGuess { line: 2 }));
+    assert_eq!(Puzzle::from_yaml(&dict, "history:\n  - watch: aaaaa\n  - hatch: aaa\n").err(), Some(YamlError::InvalidGuess { line: 3 }));
+    assert_eq!(Puzzle::from_yaml(&dict, "history:\n  - watch: aaaaa\n  - hatch: aaaap\n  - h4tch: aaaaa\n").err(), Some(YamlError::InvalidGuess { line: 3 }));
+    assert_eq!(Puzzle::from_yaml(&dict, "bogus\n").err(), Some(YamlError::Syntax { line: 1 }));
+}
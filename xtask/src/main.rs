@@ -0,0 +1,62 @@
+//! Development tasks for wordle-solver, run with `cargo xtask <task>`:
+//!
+//! - `man [DIR]` generates the manual pages (one for the program and one per subcommand) into `DIR` (default `target/man`).
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The name of the program, which the manual pages are named after.
+const BIN_NAME: &str = "wordle_solver";
+
+#[allow(dead_code)] // only the argument definitions (and the locales they accept) are used here
+mod cli {
+    use clap::{Parser, Subcommand, Args as ClapArgs, CommandFactory};
+    use wordle_solver::*;
+
+    mod messages {
+        include!("../../src/messages.rs");
+    }
+    use messages::*;
+
+    include!("../../src/cli.rs");
+
+    /// Gets the definition of the command line interface.
+    pub fn command() -> clap::Command<'static> {
+        Cli::command()
+    }
+}
+
+/// Renders the manual page for a command into `DIR/NAME.1`.
+fn write_page(dir: &Path, cmd: clap::Command) -> io::Result<()> {
+    let mut page = vec![];
+    let path = dir.join(format!("{}.1", cmd.get_name()));
+    clap_mangen::Man::new(cmd).render(&mut page)?;
+    std::fs::write(path, page)
+}
+
+/// Generates the manual pages for the program and each of its subcommands (as `wordle_solver-<subcommand>.1`).
+fn man(dir: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let mut cmd = cli::command().name(BIN_NAME).about("solve wordle puzzles and their clones");
+    cmd.build();
+    for sub in cmd.get_subcommands().filter(|x| !x.is_hide_set() && x.get_name() != "help") {
+        write_page(dir, sub.clone().name(format!("{}-{}", BIN_NAME, sub.get_name())))?;
+    }
+    write_page(dir, cmd)
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let res = match args.iter().map(String::as_str).collect::<Vec<_>>().as_slice() {
+        ["man"] => man(Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/../target/man"))),
+        ["man", dir] => man(&PathBuf::from(dir)),
+        _ => {
+            eprintln!("usage: cargo xtask man [DIR]");
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = res {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}